@@ -1,4 +1,6 @@
-use criterion::{Criterion, criterion_group, criterion_main};
+use std::fmt::Write;
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
 use serde::{Deserialize, Serialize};
 
 use clojure_reader::parse::{self, SourceReader};
@@ -20,6 +22,59 @@ struct Data<'a> {
   lisp: Vec<Lisp<'a>>,
 }
 
+#[derive(Debug, Deserialize)]
+struct Record<'a> {
+  id: u64,
+  name: &'a str,
+  email: &'a str,
+  score: f64,
+  tags: Vec<&'a str>,
+  active: bool,
+  history: Vec<(i64, &'a str)>,
+}
+
+impl Record<'_> {
+  // Reads every field, like code using the records would, so none of them is optimized away
+  fn checksum(&self) -> u64 {
+    let texts = [self.name, self.email].into_iter().chain(self.tags.iter().copied());
+    let texts = texts.chain(self.history.iter().map(|(_, event)| *event));
+    let times = self.history.iter().map(|(time, _)| time.unsigned_abs());
+    (texts.map(str::len).sum::<usize>() as u64)
+      .wrapping_add(times.fold(self.id, u64::wrapping_add))
+      .wrapping_add(self.score.to_bits())
+      .wrapping_add(u64::from(self.active))
+  }
+}
+
+// A data dump of `records` maps, roughly 350 bytes each, with the indentation, comments and
+// long strings that real dumps have
+fn dump(records: usize) -> String {
+  let mut edn = String::from(";; generated for benchmarks\n[");
+  for id in 0..records {
+    write!(
+      edn,
+      "
+ {{:id {id}
+  :name \"user-{id} 猫\"
+  :email \"user{id}@example.com\"
+  :score {}.{}
+  :tags [\"clojure\" \"edn\" \"rust\" \"tag-{}\"]
+  :active {}
+  ; events, newest last
+  :history [[{} \"created the account\"] [{} \"updated the \\\"profile\\\" page with a longer description\"]]}}",
+      id % 1000,
+      id % 97,
+      id % 13,
+      id % 2 == 0,
+      1_700_000_000 + id,
+      1_700_086_400 + id,
+    )
+    .unwrap();
+  }
+  edn.push_str("]\n");
+  edn
+}
+
 fn criterion_benchmark(c: &mut Criterion) {
   let edn = "{
         :cat \"猫\"
@@ -69,5 +124,38 @@ fn criterion_benchmark(c: &mut Criterion) {
   });
 }
 
-criterion_group!(benches, criterion_benchmark);
+fn large_inputs(c: &mut Criterion) {
+  for (name, records) in [("1MB", 3_000), ("8MB", 24_000)] {
+    let edn = dump(records);
+    let mut group = c.benchmark_group(format!("dump_{name}"));
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(edn.len() as u64));
+
+    group.bench_function("parse_as_edn", |b| b.iter(|| parse::parse_as_edn(&edn).unwrap()));
+
+    group.bench_function("parse_node", |b| {
+      b.iter(|| {
+        let mut reader = SourceReader::new(&edn);
+        parse::parse(&mut reader).unwrap()
+      })
+    });
+
+    group.bench_function("events", |b| {
+      b.iter(|| {
+        let mut reader = SourceReader::new(&edn);
+        parse::Events::new(&mut reader).map(Result::unwrap).count()
+      })
+    });
+
+    group.bench_function("deserialize", |b| {
+      b.iter(|| {
+        let records = clojure_reader::from_str::<Vec<Record<'_>>>(&edn).unwrap();
+        records.iter().map(Record::checksum).fold(0, u64::wrapping_add)
+      })
+    });
+    group.finish();
+  }
+}
+
+criterion_group!(benches, criterion_benchmark, large_inputs);
 criterion_main!(benches);
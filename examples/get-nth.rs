@@ -1,6 +1,5 @@
-use std::collections::BTreeMap;
-
 use clojure_reader::edn::{self, Edn};
+use clojure_reader_macros::edn;
 
 fn maybe_forty_two<'a>(edn: &'a Edn<'a>) -> Option<&'a Edn<'a>> {
   // This roughly tries to match clojure's get and nth
@@ -12,10 +11,35 @@ fn maybe_forty_two<'a>(edn: &'a Edn<'a>) -> Option<&'a Edn<'a>> {
   edn
     .get(&Edn::Key("foo"))?
     .get(&Edn::Symbol("猫"))?
-    .get(&Edn::Map(BTreeMap::from([(Edn::Key("foo"), Edn::Key("bar"))])))?
+    .get(&Edn::Map(edn::Map::from([(Edn::Key("foo"), Edn::Key("bar"))])))?
     .nth(2)
 }
 
+fn get_in_forty_two<'a>(edn: &'a Edn<'a>) -> Option<&'a Edn<'a>> {
+  // (get-in (clojure.edn/read-string "{:foo {猫 {{:foo :bar} [1 2 42 3]}}}")
+  //   [:foo (symbol "猫") {:foo :bar} 2])
+  edn.get_in(&[edn!(:foo), edn!(猫), edn!({:foo :bar}), edn!(2)])
+}
+
+fn assoc_update_in() {
+  let mut edn = edn::read_string("{:foo {:bar [1 2]}}").unwrap();
+
+  // (assoc-in edn [:foo :baz] 3) -> {:foo {:bar [1 2] :baz 3}}
+  edn.assoc_in(&[Edn::Key("foo"), Edn::Key("baz")], Edn::Int(3)).unwrap();
+  // (update-in edn [:foo :bar] conj 3) -> {:foo {:bar [1 2 3] :baz 3}}
+  edn
+    .update_in(&[Edn::Key("foo"), Edn::Key("bar")], |mut bar| {
+      bar.conj(Edn::Int(3)).unwrap();
+      bar
+    })
+    .unwrap();
+  assert_eq!(edn, edn::read_string("{:foo {:bar [1 2 3] :baz 3}}").unwrap());
+
+  // (update edn :foo dissoc :bar) -> {:foo {:baz 3}}
+  edn.get_in_mut(&[Edn::Key("foo")]).unwrap().dissoc(&Edn::Key("bar")).unwrap();
+  assert_eq!(edn, edn::read_string("{:foo {:baz 3}}").unwrap());
+}
+
 fn namespace_get_contains() {
   // (def edn-data (edn/read-string "#:thingy {:foo \"bar\" :baz/bar \"qux\" 42 24}"))
   let edn_data = edn::read_string(r#"#:thingy {:foo "bar" :baz/bar "qux" 42 24}"#).unwrap();
@@ -47,8 +71,10 @@ fn main() {
   let e = edn::read_string("{:foo {猫 {{:foo :bar} [1 2 42 3]}}}").unwrap();
   let edn = maybe_forty_two(&e).unwrap();
   assert_eq!(edn, &Edn::Int(42));
+  assert_eq!(get_in_forty_two(&e), Some(edn));
 
   namespace_get_contains();
+  assoc_update_in();
 }
 
 #[test]
@@ -1,45 +1,41 @@
 use clojure_reader::edn::{self, Edn};
 
-// Recursively traverse the Edn struct and wrap quote around all quoted items.
+fn quote(edn: Edn<'_>) -> Edn<'_> {
+  Edn::List(vec![Edn::Symbol("quote"), edn])
+}
+
+// Wrap quote around all quoted items, from the inside out.
 fn wrap_quote(edn: Edn<'_>) -> Edn<'_> {
-  match edn {
-    Edn::Symbol(sym) => sym.strip_prefix('\'').map_or_else(
-      || edn::read_string(sym).unwrap(),
-      |strip| Edn::List(vec![Edn::Symbol("quote"), edn::read_string(strip).unwrap()]),
-    ),
+  edn.postwalk(|edn| match edn {
+    Edn::Symbol(sym) if sym.len() > 1 && sym.starts_with('\'') => {
+      quote(edn::read_string(&sym[1..]).unwrap())
+    }
+    // A lone ' quotes what follows it
     Edn::List(edn) => {
       let mut list = vec![];
       let mut edn = edn.into_iter();
-
       while let Some(e) = edn.next() {
-        if e == Edn::Symbol("'") {
-          if let Some(e) = edn.next() {
-            list.push(Edn::List(vec![Edn::Symbol("quote"), wrap_quote(e)]));
-          } else {
-            list.push(Edn::Symbol("quote"));
-          }
-        } else {
-          list.push(wrap_quote(e));
+        match (e, edn.len()) {
+          (Edn::Symbol("'"), 1..) => list.push(quote(edn.next().unwrap())),
+          (Edn::Symbol("'"), 0) => list.push(Edn::Symbol("quote")),
+          (e, _) => list.push(e),
         }
       }
-
       Edn::List(list)
     }
-    _ => edn,
-  }
+    edn => edn,
+  })
 }
 
 // Use `read` to handle the leading ' symbol.
 fn quotify(s: &str) -> Edn<'_> {
   let (edn, rest) = edn::read(s).unwrap();
 
-  let edn = if edn == Edn::Symbol("'") {
-    Edn::List(vec![Edn::Symbol("quote"), edn::read_string(rest).unwrap()])
+  if edn == Edn::Symbol("'") {
+    quote(wrap_quote(edn::read_string(rest).unwrap()))
   } else {
-    edn
-  };
-
-  wrap_quote(edn)
+    wrap_quote(edn)
+  }
 }
 
 fn main() {
@@ -1,3 +1,4 @@
+use clojure_reader::line_index::LineIndex;
 use clojure_reader::parse::{self, NodeKind, SourceReader};
 
 fn underline(column: usize, width: usize) -> String {
@@ -20,16 +21,17 @@ fn main() {
   };
   let cat = &inner[1];
   let span = cat.span();
-  let width = source[span.0.ptr..span.1.ptr]
-    .chars()
-    .map(|ch| if ch.is_ascii() { 1 } else { 2 })
-    .sum::<usize>();
+  let width =
+    source[span.0..span.1].chars().map(|ch| if ch.is_ascii() { 1 } else { 2 }).sum::<usize>();
+
+  let index = LineIndex::new(source);
+  let (start, end) = (index.line_col(span.0), index.line_col(span.1));
 
   println!("{source}");
-  println!("{} symbol not found", underline(span.0.column, width));
+  println!("{} symbol not found", underline(start.column, width));
   println!(
     "\nspan: start=(line {}, column {}, ptr {}) end=(line {}, column {}, ptr {})",
-    span.0.line, span.0.column, span.0.ptr, span.1.line, span.1.column, span.1.ptr,
+    start.line, start.column, span.0, end.line, end.column, span.1,
   );
 }
 
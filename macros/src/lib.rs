@@ -0,0 +1,238 @@
+//! The [`edn!`] macro, to write an [`Edn`](clojure_reader::edn::Edn) in Rust code as EDN.
+
+use clojure_reader::edn::{self, Edn};
+use proc_macro::{Delimiter, Span, TokenStream, TokenTree};
+use proc_macro2::{Literal, TokenStream as TokenStream2};
+use quote::{quote, quote_spanned};
+
+// What `~` is read as, with the index of the expression after it
+const UNQUOTE: &str = "__edn_unquote_";
+
+/// Builds an [`Edn`](clojure_reader::edn::Edn) from EDN written in Rust code, read with
+/// clojure-reader when the code is compiled. Invalid EDN is a compile error pointing at where it
+/// goes wrong.
+///
+/// `~x` or `~(expr)` puts in a Rust value, anything that's `Into<Edn>`. The expressions in a map
+/// or a set are evaluated in the order their EDN is sorted in, not the order they're written in,
+/// unless this crate's `preserve-order` feature is on. It reads the EDN with clojure-reader's
+/// `preserve-order` when the code is compiled, which is a separate build of clojure-reader from the
+/// one the code uses, so turn that one's on too for the `Edn` to keep the order.
+///
+/// EDN that Rust can't tokenize, like characters and `'` in comments, can be written in a
+/// raw string literal instead.
+///
+/// ```
+/// use clojure_reader::edn::{self, Edn};
+/// use clojure_reader_macros::edn;
+///
+/// let port = 8080;
+/// let config = edn!({:server {:host "localhost" :port ~port}
+///                    :tags #{:a :b}});
+/// assert_eq!(
+///   config,
+///   edn::read_string(r#"{:server {:host "localhost" :port 8080} :tags #{:a :b}}"#).unwrap()
+/// );
+///
+/// assert_eq!(edn!(r"[\a \b]"), Edn::Vector(vec![Edn::Char('a'), Edn::Char('b')]));
+/// ```
+///
+/// ```compile_fail
+/// let edn = clojure_reader_macros::edn!({:a 1 :a 2});
+/// ```
+#[proc_macro]
+pub fn edn(input: TokenStream) -> TokenStream {
+  let mut source = Source::default();
+  match raw_string(&input) {
+    Some((text, span)) => source.push(&text, span),
+    None => source.tokens(input),
+  }
+
+  let edn = match edn::read_string(&source.text) {
+    Ok(edn) => edn,
+    Err(error) => {
+      let span = error.ptr.map_or_else(Span::call_site, |ptr| source.span_at(ptr));
+      return compile_error(&format!("invalid EDN: {:?}", error.code), span);
+    }
+  };
+  match expand(&edn, &source.unquoted) {
+    Ok(tokens) => tokens.into(),
+    Err(message) => compile_error(&message, Span::call_site()),
+  }
+}
+
+// The EDN source the macro was given, put back together from its tokens
+#[derive(Default)]
+struct Source {
+  text: String,
+  // Where each token starts in `text`, to point errors at it
+  spans: Vec<(usize, Span)>,
+  // The expressions after `~`
+  unquoted: Vec<TokenStream2>,
+  // The line and column the last token ended at
+  end: Option<(usize, usize)>,
+}
+
+impl Source {
+  fn push(&mut self, text: &str, span: Span) {
+    self.push_spanning(text, span, span);
+  }
+
+  // Pushes `text` for the tokens from `start` to `end`, spaced from the last ones like they are in
+  // the Rust source, so `:foo-bar` stays one keyword and comments end at the end of their line
+  fn push_spanning(&mut self, text: &str, start: Span, end: Span) {
+    let start = start.start();
+    match self.end {
+      Some((line, _)) if line != start.line() => self.text.push('\n'),
+      Some(end) if end != (start.line(), start.column()) => self.text.push(' '),
+      _ => {}
+    }
+    self.spans.push((self.text.len(), start));
+    self.text.push_str(text);
+    let end = end.end();
+    self.end = Some((end.line(), end.column()));
+  }
+
+  fn tokens(&mut self, input: TokenStream) {
+    let mut tokens = input.into_iter();
+    while let Some(token) = tokens.next() {
+      match token {
+        TokenTree::Group(group) => {
+          let (open, close) = match group.delimiter() {
+            Delimiter::Parenthesis => ("(", ")"),
+            Delimiter::Brace => ("{", "}"),
+            Delimiter::Bracket => ("[", "]"),
+            Delimiter::None => {
+              self.tokens(group.stream());
+              continue;
+            }
+          };
+          self.push(open, group.span_open());
+          self.tokens(group.stream());
+          self.push(close, group.span_close());
+        }
+        TokenTree::Punct(ref punct) if punct.as_char() == '~' => match tokens.next() {
+          Some(expr) => {
+            let placeholder = format!("{UNQUOTE}{}", self.unquoted.len());
+            self.push_spanning(&placeholder, punct.span(), expr.span());
+            let expr = match expr {
+              TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis => {
+                group.stream()
+              }
+              expr => TokenStream::from(expr),
+            };
+            self.unquoted.push(expr.into());
+          }
+          None => self.push("~", punct.span()),
+        },
+        token => self.push(&token.to_string(), token.span()),
+      }
+    }
+  }
+
+  // The span of the token at `offset` in the text
+  fn span_at(&self, offset: usize) -> Span {
+    let index = self.spans.partition_point(|(start, _)| *start <= offset);
+    index
+      .checked_sub(1)
+      .and_then(|index| self.spans.get(index))
+      .map_or_else(Span::call_site, |span| span.1)
+  }
+}
+
+// The contents of the raw string literal that's all of `input`, if it is one
+fn raw_string(input: &TokenStream) -> Option<(String, Span)> {
+  let mut tokens = input.clone().into_iter();
+  let (Some(TokenTree::Literal(literal)), None) = (tokens.next(), tokens.next()) else {
+    return None;
+  };
+  let text = literal.to_string();
+  let raw = text.strip_prefix('r')?;
+  let hashes = raw.len() - raw.trim_start_matches('#').len();
+  let text = raw.get(hashes + 1..raw.len() - hashes - 1)?;
+  Some((text.to_owned(), literal.span()))
+}
+
+// The Rust expression that builds `edn`
+fn expand(edn: &Edn<'_>, unquoted: &[TokenStream2]) -> Result<TokenStream2, String> {
+  let all = |items: &mut dyn Iterator<Item = &Edn<'_>>| {
+    items.map(|item| expand(item, unquoted)).collect::<Result<Vec<_>, _>>()
+  };
+  let path = quote!(::clojure_reader::edn::Edn);
+  Ok(match edn {
+    Edn::Vector(items) => {
+      let items = all(&mut items.iter())?;
+      quote!(#path::Vector(::core::iter::IntoIterator::into_iter([#(#items),*]).collect()))
+    }
+    Edn::List(items) => {
+      let items = all(&mut items.iter())?;
+      quote!(#path::List(::core::iter::IntoIterator::into_iter([#(#items),*]).collect()))
+    }
+    Edn::Set(items) => {
+      let items = all(&mut items.iter())?;
+      quote!(#path::Set(::core::iter::IntoIterator::into_iter([#(#items),*]).collect()))
+    }
+    Edn::Map(entries) => {
+      let keys = all(&mut entries.keys())?;
+      let values = all(&mut entries.values())?;
+      quote!(#path::Map(
+        ::core::iter::IntoIterator::into_iter([#((#keys, #values)),*]).collect()
+      ))
+    }
+    Edn::Symbol(symbol) => {
+      let expr =
+        symbol.strip_prefix(UNQUOTE).and_then(|index| unquoted.get(index.parse::<usize>().ok()?));
+      expr.map_or_else(
+        || {
+          let symbol = Literal::string(symbol);
+          quote!(#path::Symbol(#symbol))
+        },
+        |expr| quote!(::core::convert::Into::<#path<'_>>::into(#expr)),
+      )
+    }
+    Edn::Key(key) => {
+      let key = Literal::string(key);
+      quote!(#path::Key(#key))
+    }
+    Edn::Str(str) => {
+      let str = Literal::string(str);
+      quote!(#path::Str(#str))
+    }
+    Edn::Int(int) => {
+      let int = Literal::i64_suffixed(*int);
+      quote!(#path::Int(#int))
+    }
+    Edn::Tagged(tag, value) => {
+      let tag = Literal::string(tag);
+      let value = expand(value, unquoted)?;
+      quote!(#path::Tagged(#tag, ::core::convert::From::from(#value)))
+    }
+    Edn::Double(double) => {
+      let double = match double.0 {
+        double if double.is_finite() => {
+          let double = Literal::f64_suffixed(double);
+          quote!(#double)
+        }
+        double if double.is_nan() => quote!(f64::NAN),
+        double if double.is_sign_positive() => quote!(f64::INFINITY),
+        _ => quote!(f64::NEG_INFINITY),
+      };
+      quote!(<#path<'_> as ::core::convert::From<f64>>::from(#double))
+    }
+    Edn::Rational((numerator, denominator)) => {
+      let numerator = Literal::i64_suffixed(*numerator);
+      let denominator = Literal::i64_suffixed(*denominator);
+      quote!(#path::Rational((#numerator, #denominator)))
+    }
+    Edn::Char(char) => {
+      let char = Literal::character(*char);
+      quote!(#path::Char(#char))
+    }
+    Edn::Bool(bool) => quote!(#path::Bool(#bool)),
+    Edn::Nil => quote!(#path::Nil),
+    edn => return Err(format!("edn! can't build {edn}")),
+  })
+}
+
+fn compile_error(message: &str, span: Span) -> TokenStream {
+  quote_spanned!(span.into() => ::core::compile_error!(#message)).into()
+}
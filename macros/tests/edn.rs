@@ -0,0 +1,75 @@
+use clojure_reader::edn::{self, Edn};
+use clojure_reader_macros::edn;
+
+#[test]
+fn reads_edn() {
+  assert_eq!(edn!(nil), Edn::Nil);
+  assert_eq!(edn!(42), Edn::Int(42));
+  assert_eq!(edn!(-42), Edn::Int(-42));
+  assert_eq!(edn!("foo bar"), Edn::Str("foo bar"));
+  assert_eq!(edn!(:foo-bar/baz?), Edn::Key("foo-bar/baz?"));
+  assert_eq!(edn!(foo.bar/baz!), Edn::Symbol("foo.bar/baz!"));
+  assert_eq!(edn!([:ratio 1/2]), edn::read_string("[:ratio 1/2]").unwrap());
+  assert_eq!(edn!(1.5), edn::read_string("1.5").unwrap());
+  assert_eq!(edn!(-1.5e3), edn::read_string("-1.5e3").unwrap());
+
+  let source = r#"{:vector [1 "two" :three]
+                   :list (+ 1 2)
+                   :set #{true false}
+                   :tagged #inst "2024-01-01"
+                   :namespaced #:person{:name "ada" :id 1}
+                   :nested {[1 2] {:a nil}}}"#;
+  let expected = edn::read_string(source).unwrap();
+  let edn = edn!({:vector [1 "two" :three]
+                  :list (+ 1 2)
+                  :set #{true false}
+                  :tagged #inst "2024-01-01"
+                  :namespaced #:person{:name "ada" :id 1}
+                  :nested {[1 2] {:a nil}}});
+  assert_eq!(edn, expected);
+}
+
+#[test]
+fn spacing_follows_source() {
+  assert_eq!(edn!([:a :b]), edn::read_string("[:a :b]").unwrap());
+  assert_eq!(edn!([a-b a - b]), edn::read_string("[a-b a - b]").unwrap());
+  assert_eq!(
+    edn!([1 ; the rest of the line is a comment
+          2 #_ 3]),
+    edn::read_string("[1 2]").unwrap()
+  );
+}
+
+#[test]
+fn raw_strings() {
+  assert_eq!(edn!(r"[\a \newline]"), Edn::Vector(vec![Edn::Char('a'), Edn::Char('\n')]));
+  assert_eq!(edn!(r#"{:a "b"}"#), edn::read_string(r#"{:a "b"}"#).unwrap());
+  assert_eq!(edn!("[\\a]"), Edn::Str("[\\\\a]"));
+}
+
+#[test]
+fn unquote() {
+  let name = "ada";
+  let ids = vec![1, 2];
+  let key = Edn::Key("k");
+  let edn =
+    edn!({:name ~name :ids ~(ids.clone()) :sum ~(ids.iter().sum::<i64>()) ~key #{~(Some(true))}});
+  assert_eq!(edn, edn::read_string(r#"{:name "ada" :ids [1 2] :sum 3 :k #{true}}"#).unwrap());
+  assert_eq!(edn!(~(None::<i64>)), Edn::Nil);
+}
+
+#[cfg(feature = "preserve-order")]
+#[test]
+fn preserve_order() {
+  let mut order = Vec::new();
+  let mut value = |name| {
+    order.push(name);
+    Edn::Str(name)
+  };
+  let edn = edn!({:b ~(value("b")) :a ~(value("a")) :c #{~(value("z")) ~(value("y"))}});
+  assert_eq!(order, ["b", "a", "z", "y"]);
+
+  let Edn::Map(map) = edn else { panic!("expected a map, got {edn:?}") };
+  let keys = map.keys().map(ToString::to_string).collect::<Vec<_>>();
+  assert_eq!(keys, [":b", ":a", ":c"]);
+}
@@ -1,21 +1,32 @@
-use alloc::collections::BTreeMap;
+use alloc::collections::{BTreeMap, BTreeSet, btree_map};
 use alloc::format;
-use alloc::string::ToString;
+use alloc::string::{String, ToString};
+use alloc::vec;
 use alloc::vec::Vec;
-use core::fmt::Display;
+use core::cmp::Ordering;
+use core::fmt::{self, Display};
+use core::hash::{Hash, Hasher};
+use core::ops::Range;
 
-use crate::edn::Edn;
-use crate::parse;
+use crate::edn::{self, Edn};
+use crate::line_index::{LineCol, LineIndex};
+use crate::parse::{self, SourceReader, Span, Token};
+use crate::ser::{EnumRepr, struct_namespace};
+use crate::types::Marker;
 
 use serde::de::{
-  self, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, VariantAccess, Visitor,
+  self, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, VariantAccess,
+  Visitor, value::BorrowedStrDeserializer,
 };
-use serde::{Deserialize, forward_to_deserialize_any};
+use serde::{Deserialize, Serialize, Serializer, forward_to_deserialize_any};
 
 use crate::error::{Code, Error, Result};
 
 /// Deserializer for a EDN formatted &str.
 ///
+/// Errors raised while deserializing carry the position of the offending form, and the message
+/// ends with the path to it, e.g. `cannot convert Str("80") to i64 at [:servers 2 :port]`.
+///
 /// # Errors
 ///
 /// See [`crate::error::Error`].
@@ -24,26 +35,129 @@ pub fn from_str<'a, T>(s: &'a str) -> Result<T>
 where
   T: Deserialize<'a>,
 {
-  let (edn, remaining) = parse::parse_as_edn(s)?;
-  let t = T::deserialize(edn)?;
+  let mut deserializer = Deserializer::from_str(s);
+  let t = T::deserialize(&mut deserializer)?;
+  deserializer.end()?;
+  Ok(t)
+}
 
-  let mut remaining = remaining;
-  loop {
-    remaining = remaining.trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+/// Like [`from_str`], but fails on anything in the source that doesn't end up in `T`, see
+/// [`Unused`].
+///
+/// ```
+/// use clojure_reader::de::from_str_strict;
+/// use serde_derive::Deserialize;
+///
+/// #[derive(Deserialize, Debug)]
+/// struct Config {
+///   port: u16,
+/// }
+///
+/// let err = from_str_strict::<Config>("{:port 80\n :prot 8080}").unwrap_err();
+/// assert_eq!(format!("{err}"), "EdnError { code: Serde(\"ignored value at [:prot]\"), line: Some(2), column: Some(8), ptr: Some(17) }");
+/// ```
+///
+/// # Errors
+///
+/// See [`crate::error::Error`].
+/// Always returns `Code::Serde`.
+pub fn from_str_strict<'a, T>(s: &'a str) -> Result<T>
+where
+  T: Deserialize<'a>,
+{
+  let mut deserializer = Deserializer::from_str(s).unused(Unused::Deny);
+  let t = T::deserialize(&mut deserializer)?;
+  deserializer.end()?;
+  Ok(t)
+}
 
-    let Some(comment) = remaining.strip_prefix(';') else {
-      break;
-    };
+/// Like [`from_str`], also returning a [`Warning`] for everything in the source that doesn't end
+/// up in `T`, see [`Unused`].
+///
+/// ```
+/// use clojure_reader::de::from_str_lenient;
+///
+/// let (pair, warnings) = from_str_lenient::<(u8, u8)>("[1 2 3]").unwrap();
+/// assert_eq!(pair, (1, 2));
+/// assert_eq!(warnings[0].to_string(), "ignored value at [2] on line 1, column 6");
+/// ```
+///
+/// # Errors
+///
+/// See [`crate::error::Error`].
+/// Always returns `Code::Serde`.
+pub fn from_str_lenient<'a, T>(s: &'a str) -> Result<(T, Vec<Warning>)>
+where
+  T: Deserialize<'a>,
+{
+  let mut deserializer = Deserializer::from_str(s).unused(Unused::Warn);
+  let t = T::deserialize(&mut deserializer)?;
+  deserializer.end()?;
+  Ok((t, deserializer.warnings))
+}
 
-    let Some(comment_end) = comment.find(['\n', '\r']) else {
-      return Ok(t);
-    };
-    remaining = &comment[comment_end..];
-  }
-  if !remaining.is_empty() {
-    return Err(de::Error::custom("trailing input"));
-  }
-  Ok(t)
+/// Deserializer for an [`Edn`], like [`from_str`] for its source.
+///
+/// ```
+/// use clojure_reader::de::from_edn;
+/// use clojure_reader::edn;
+/// use serde_derive::Deserialize;
+///
+/// #[derive(Deserialize, Debug, PartialEq)]
+/// struct Config<'a> {
+///   host: &'a str,
+///   port: u16,
+/// }
+///
+/// let mut edn = edn::read_string("{:host \"localhost\" :port 8080}").unwrap();
+/// edn.assoc(&edn::Edn::Key("port"), edn::Edn::Int(80)).unwrap();
+/// assert_eq!(from_edn::<Config<'_>>(edn).unwrap(), Config { host: "localhost", port: 80 });
+/// ```
+///
+/// # Errors
+///
+/// See [`crate::error::Error`].
+/// Always returns `Code::Serde`.
+pub fn from_edn<'de, T>(edn: Edn<'de>) -> Result<T>
+where
+  T: Deserialize<'de>,
+{
+  T::deserialize(edn)
+}
+
+/// Deserializer for an [`Edn`] like [`from_edn`], expecting enums and struct fields to be written
+/// like [`Deserializer::enums`] and [`Deserializer::namespace`] expect them.
+///
+/// ```
+/// use clojure_reader::de::from_edn_with;
+/// use clojure_reader::edn;
+/// use clojure_reader::ser::EnumRepr;
+/// use serde_derive::Deserialize;
+///
+/// #[derive(Deserialize, Debug, PartialEq)]
+/// enum Shape {
+///   Dot,
+///   Circle(u32),
+/// }
+///
+/// let edn = edn::read_string("[#geo/Dot nil #geo/Circle 2]").unwrap();
+/// let shapes = from_edn_with::<Vec<Shape>>(edn, EnumRepr::Namespaced("geo"), None).unwrap();
+/// assert_eq!(shapes, [Shape::Dot, Shape::Circle(2)]);
+/// ```
+///
+/// # Errors
+///
+/// See [`crate::error::Error`].
+/// Always returns `Code::Serde`.
+pub fn from_edn_with<'de, T>(
+  edn: Edn<'de>,
+  enums: EnumRepr,
+  namespace: Option<&'static str>,
+) -> Result<T>
+where
+  T: Deserialize<'de>,
+{
+  T::deserialize(EdnDeserializer::new(edn, Conventions { enums, namespace }))
 }
 
 impl de::Error for Error {
@@ -73,14 +187,101 @@ fn get_bytes_from_edn(edn: &Edn<'_>) -> Result<Vec<u8>> {
   }
 }
 
+macro_rules! forward_to_edn_deserializer {
+  ($($method:ident($($arg:ident: $ty:ty),*))*) => {
+    $(
+      fn $method<V>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value>
+      where
+        V: Visitor<'de>,
+      {
+        EdnDeserializer::new(self, Conventions::default()).$method($($arg,)* visitor)
+      }
+    )*
+  };
+}
+
 impl<'de> de::Deserializer<'de> for Edn<'de> {
   type Error = Error;
 
+  forward_to_edn_deserializer! {
+    deserialize_any() deserialize_bool() deserialize_i8() deserialize_i16() deserialize_i32()
+    deserialize_i64() deserialize_u8() deserialize_u16() deserialize_u32() deserialize_u64()
+    deserialize_f32() deserialize_f64() deserialize_char() deserialize_str() deserialize_string()
+    deserialize_bytes() deserialize_byte_buf() deserialize_option() deserialize_unit()
+    deserialize_unit_struct(name: &'static str) deserialize_newtype_struct(name: &'static str)
+    deserialize_seq() deserialize_tuple(len: usize)
+    deserialize_tuple_struct(name: &'static str, len: usize) deserialize_map()
+    deserialize_struct(name: &'static str, fields: &'static [&'static str])
+    deserialize_enum(name: &'static str, variants: &'static [&'static str])
+    deserialize_identifier() deserialize_ignored_any()
+  }
+}
+
+/// Deserializes an [`Edn`], telling the [`Deserializer`] that read it, if there is one, about
+/// what gets passed over in it.
+struct EdnDeserializer<'a, 'p, 'de> {
+  edn: Edn<'de>,
+  origin: Option<Origin<'a, 'p, 'de>>,
+  conventions: Conventions,
+}
+
+/// How enums and struct fields are expected to be written, see [`Deserializer::enums`] and
+/// [`Deserializer::namespace`].
+#[derive(Debug, Clone, Copy, Default)]
+struct Conventions {
+  enums: EnumRepr,
+  namespace: Option<&'static str>,
+}
+
+/// Where an [`Edn`] read by a [`Deserializer`] came from.
+struct Origin<'a, 'p, 'de> {
+  de: &'a mut Deserializer<'de>,
+  path: &'p Path<'p, 'de>,
+  // Where the form it was read from starts, the closest there is to where any part of it is
+  start: usize,
+  // Where the form ends, when the `Edn` is all of it rather than a part
+  end: Option<usize>,
+}
+
+impl<'de> Origin<'_, '_, 'de> {
+  // The origin of a part of it, at `path`
+  const fn at<'b>(&'b mut self, path: &'b Path<'b, 'de>) -> Origin<'b, 'b, 'de> {
+    Origin { de: self.de, path, start: self.start, end: None }
+  }
+
+  fn pass_over(&mut self, what: fmt::Arguments<'_>) -> Result<()> {
+    self.de.pass_over(what, self.start, self.path)
+  }
+
+  // Whether the deserializer wants to hear about what's passed over
+  fn checks(&self) -> bool {
+    self.de.unused != Unused::Skip
+  }
+}
+
+impl<'de> EdnDeserializer<'_, '_, 'de> {
+  const fn new(edn: Edn<'de>, conventions: Conventions) -> Self {
+    Self { edn, origin: None, conventions }
+  }
+
+  fn pass_over(&mut self, what: fmt::Arguments<'_>) -> Result<()> {
+    self.origin.as_mut().map_or(Ok(()), |origin| origin.pass_over(what))
+  }
+
+  fn checks(&self) -> bool {
+    self.origin.as_ref().is_some_and(Origin::checks)
+  }
+}
+
+impl<'de> de::Deserializer<'de> for EdnDeserializer<'_, '_, 'de> {
+  type Error = Error;
+
   fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
   where
     V: Visitor<'de>,
   {
-    match self {
+    let Self { edn, origin, conventions } = self;
+    match edn {
       Edn::Key(k) => visitor.visit_borrowed_str(k),
       Edn::Str(s) | Edn::Symbol(s) => visitor.visit_borrowed_str(s),
       Edn::Int(i) => visitor.visit_i64(i),
@@ -91,28 +292,51 @@ impl<'de> de::Deserializer<'de> for Edn<'de> {
       Edn::Nil => visitor.visit_unit(),
       Edn::Vector(mut list) | Edn::List(mut list) => {
         list.reverse();
-        Ok(visitor.visit_seq(SeqEdn::new(list))?)
+        SeqEdn::new(list, origin, conventions).visit(visitor)
       }
-      Edn::Map(map) => visitor.visit_map(MapEdn::new(map)),
+      Edn::Map(map) => MapEdn::new(map, origin, conventions, Keys::default())?.visit(visitor),
       Edn::Set(set) => {
         let mut s: Vec<Edn<'_>> = set.into_iter().collect();
         s.reverse();
-        Ok(visitor.visit_seq(SeqEdn::new(s))?)
+        SeqEdn::new(s, origin, conventions).visit(visitor)
       }
-      // Things like rational numbers and custom tags can't be represented in rust types
-      _ => Err(de::Error::custom(format!("Don't know how to convert {self:?} into any"))),
+      Edn::Tagged(tag, edn) => match (tag.strip_prefix(':'), *edn) {
+        (Some(namespace), Edn::Map(map)) => {
+          MapEdn::new(map, origin, conventions, Keys { map: Some(namespace), fields: None })?
+            .visit(visitor)
+        }
+        // Only `types::Tagged` asks for the tag and the value, strict mode reports it dropped
+        _ => match origin {
+          Some(Origin { de, path, start, .. }) if de.unused == Unused::Deny => {
+            Err(locate(de::Error::custom(format!("dropped #{tag} tag")), start, path))
+          }
+          _ => Err(de::Error::invalid_type(de::Unexpected::Other("tagged value"), &visitor)),
+        },
+      },
+      // Only `types::Rational` takes it apart
+      Edn::Rational(_) => Err(de::Error::invalid_type(de::Unexpected::Other("ratio"), &visitor)),
+      // Text for big numbers
+      #[cfg(feature = "arbitrary-nums")]
+      Edn::BigInt(int) => visitor.visit_string(int.to_string()),
+      #[cfg(feature = "arbitrary-nums")]
+      Edn::BigDec(dec) => visitor.visit_string(dec.to_string()),
     }
   }
 
   forward_to_deserialize_any! {
-    bool i64 f64 char str map seq tuple_struct
+    bool i64 char str map seq tuple_struct
   }
 
-  fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value>
+  fn deserialize_ignored_any<V>(mut self, visitor: V) -> Result<V::Value>
   where
     V: Visitor<'de>,
   {
-    let _ = self;
+    if let Some(origin) = &mut self.origin {
+      match &self.edn {
+        Edn::Tagged(tag, _) => origin.pass_over(format_args!("ignored #{tag} value"))?,
+        _ => origin.pass_over(format_args!("ignored value"))?,
+      }
+    }
     visitor.visit_unit()
   }
 
@@ -120,7 +344,7 @@ impl<'de> de::Deserializer<'de> for Edn<'de> {
   where
     V: Visitor<'de>,
   {
-    let int = get_int_from_edn(&self)?;
+    let int = get_int_from_edn(&self.edn)?;
     i8::try_from(int).map_or_else(
       |_| Err(de::Error::custom(format!("can't convert {int} into i8"))),
       |i| visitor.visit_i8(i),
@@ -131,7 +355,7 @@ impl<'de> de::Deserializer<'de> for Edn<'de> {
   where
     V: Visitor<'de>,
   {
-    let int = get_int_from_edn(&self)?;
+    let int = get_int_from_edn(&self.edn)?;
     i16::try_from(int).map_or_else(
       |_| Err(de::Error::custom(format!("can't convert {int} into i16"))),
       |i| visitor.visit_i16(i),
@@ -142,7 +366,7 @@ impl<'de> de::Deserializer<'de> for Edn<'de> {
   where
     V: Visitor<'de>,
   {
-    let int = get_int_from_edn(&self)?;
+    let int = get_int_from_edn(&self.edn)?;
     i32::try_from(int).map_or_else(
       |_| Err(de::Error::custom(format!("can't convert {int} into i32"))),
       |i| visitor.visit_i32(i),
@@ -153,7 +377,7 @@ impl<'de> de::Deserializer<'de> for Edn<'de> {
   where
     V: Visitor<'de>,
   {
-    let int = get_int_from_edn(&self)?;
+    let int = get_int_from_edn(&self.edn)?;
     u8::try_from(int).map_or_else(
       |_| Err(de::Error::custom(format!("can't convert {int} into u8"))),
       |i| visitor.visit_u8(i),
@@ -164,7 +388,7 @@ impl<'de> de::Deserializer<'de> for Edn<'de> {
   where
     V: Visitor<'de>,
   {
-    let int = get_int_from_edn(&self)?;
+    let int = get_int_from_edn(&self.edn)?;
     u16::try_from(int).map_or_else(
       |_| Err(de::Error::custom(format!("can't convert {int} into u16"))),
       |i| visitor.visit_u16(i),
@@ -175,7 +399,7 @@ impl<'de> de::Deserializer<'de> for Edn<'de> {
   where
     V: Visitor<'de>,
   {
-    let int = get_int_from_edn(&self)?;
+    let int = get_int_from_edn(&self.edn)?;
     u32::try_from(int).map_or_else(
       |_| Err(de::Error::custom(format!("can't convert {int} into u32"))),
       |i| visitor.visit_u32(i),
@@ -187,14 +411,14 @@ impl<'de> de::Deserializer<'de> for Edn<'de> {
     V: Visitor<'de>,
   {
     #[cfg(feature = "arbitrary-nums")]
-    if let Edn::BigInt(i) = &self {
+    if let Edn::BigInt(i) = &self.edn {
       return u64::try_from(i).map_or_else(
         |_| Err(de::Error::custom(format!("can't convert {i} into u64"))),
         |i| visitor.visit_u64(i),
       );
     }
 
-    let int = get_int_from_edn(&self)?;
+    let int = get_int_from_edn(&self.edn)?;
     u64::try_from(int).map_or_else(
       |_| Err(de::Error::custom(format!("can't convert {int} into u64"))),
       |i| visitor.visit_u64(i),
@@ -207,11 +431,36 @@ impl<'de> de::Deserializer<'de> for Edn<'de> {
   {
     let _ = visitor; // hush clippy
     #[cfg(feature = "floats")]
-    if let Edn::Double(f) = self {
+    if let Edn::Double(f) = self.edn {
       #[expect(clippy::cast_possible_truncation)]
-      return visitor.visit_f32(*f as f32);
+      let float = *f as f32;
+      // Lossy when the nearest f32 doesn't read back as the same number
+      if let Some(mut origin) = self.origin
+        && origin.checks()
+        && !f.is_nan()
+        && float.to_string().parse() != Ok(*f)
+      {
+        origin.pass_over(format_args!("lossy conversion of {f} into f32"))?;
+      }
+      return visitor.visit_f32(float);
+    }
+    Err(de::Error::custom(format!("can't convert {:?} into f32", self.edn)))
+  }
+
+  fn deserialize_f64<V>(mut self, visitor: V) -> Result<V::Value>
+  where
+    V: Visitor<'de>,
+  {
+    if let Edn::Int(int) = self.edn
+      && self.checks()
+    {
+      #[expect(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+      let lossy = i128::from(int) != int as f64 as i128;
+      if lossy {
+        self.pass_over(format_args!("lossy conversion of {int} into f64"))?;
+      }
     }
-    Err(de::Error::custom(format!("can't convert {self:?} into f32")))
+    self.deserialize_any(visitor)
   }
 
   fn deserialize_string<V>(self, visitor: V) -> Result<V::Value>
@@ -242,7 +491,7 @@ impl<'de> de::Deserializer<'de> for Edn<'de> {
   where
     V: Visitor<'de>,
   {
-    let buf = get_bytes_from_edn(&self)?;
+    let buf = get_bytes_from_edn(&self.edn)?;
     visitor.visit_byte_buf(buf)
   }
 
@@ -250,14 +499,14 @@ impl<'de> de::Deserializer<'de> for Edn<'de> {
   where
     V: Visitor<'de>,
   {
-    if self == Edn::Nil { visitor.visit_none() } else { visitor.visit_some(self) }
+    if self.edn == Edn::Nil { visitor.visit_none() } else { visitor.visit_some(self) }
   }
 
   fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value>
   where
     V: Visitor<'de>,
   {
-    match self {
+    match self.edn {
       Edn::Nil => visitor.visit_unit(),
       Edn::Map(map) if map.is_empty() => visitor.visit_unit(),
       other => Err(de::Error::custom(format!("can't convert {other:?} into unit"))),
@@ -271,11 +520,39 @@ impl<'de> de::Deserializer<'de> for Edn<'de> {
     self.deserialize_unit(visitor)
   }
 
-  fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+  fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value>
   where
     V: Visitor<'de>,
   {
-    visitor.visit_newtype_struct(self)
+    let Self { edn, origin, conventions } = self;
+    // A form read whole, like a set element, still has its source
+    if let (Some(Marker::Raw), Some(Origin { de, start, end: Some(end), .. })) =
+      (Marker::of(name), &origin)
+    {
+      let raw = de.reader.source().get(*start..*end).unwrap_or_default();
+      return visitor.visit_borrowed_str(raw);
+    }
+    match (Marker::of(name), edn) {
+      (Some(Marker::Keyword), Edn::Key(name)) | (Some(Marker::Symbol), Edn::Symbol(name)) => {
+        visitor.visit_newtype_struct(BorrowedStrDeserializer::new(name))
+      }
+      (Some(Marker::Set), edn @ Edn::Set(_)) | (None | Some(Marker::Raw), edn) => {
+        visitor.visit_newtype_struct(Self { edn, origin, conventions })
+      }
+      (Some(Marker::Tagged), Edn::Tagged(tag, edn)) => {
+        visit_tagged(tag, *edn, origin, conventions, visitor)
+      }
+      (Some(Marker::Rational), Edn::Rational((numerator, denominator))) => {
+        SeqEdn::new(vec![Edn::Int(denominator), Edn::Int(numerator)], origin, conventions)
+          .visit(visitor)
+      }
+      #[cfg(feature = "arbitrary-nums")]
+      (Some(Marker::BigInt), edn @ (Edn::BigInt(_) | Edn::Int(_)))
+      | (Some(Marker::BigDec), edn @ (Edn::BigDec(_) | Edn::BigInt(_) | Edn::Int(_))) => {
+        edn.deserialize_any(visitor)
+      }
+      (Some(marker), edn) => Err(de::Error::custom(format!("can't convert {edn:?} into {marker}"))),
+    }
   }
 
   fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value>
@@ -287,14 +564,38 @@ impl<'de> de::Deserializer<'de> for Edn<'de> {
 
   fn deserialize_struct<V>(
     self,
-    _name: &'static str,
-    _fields: &'static [&'static str],
+    name: &'static str,
+    fields: &'static [&'static str],
     visitor: V,
   ) -> Result<V::Value>
   where
     V: Visitor<'de>,
   {
-    self.deserialize_map(visitor)
+    let Self { edn, origin, conventions } = self;
+    if name == SPANNED_NAME
+      && fields == SPANNED_FIELDS
+      && let Some(Origin { start, end: Some(end), .. }) = origin
+    {
+      let value = SpannedValue::Edn(Self { edn, origin, conventions });
+      return visitor.visit_map(SpannedAccess { start, end, value, field: 0 });
+    }
+    let fields = struct_namespace(name).or(conventions.namespace);
+    match edn {
+      Edn::Map(map) => {
+        MapEdn::new(map, origin, conventions, Keys { map: None, fields })?.visit(visitor)
+      }
+      Edn::Tagged(tag, edn) => match (tag.strip_prefix(':'), *edn) {
+        (Some(namespace), Edn::Map(map)) => {
+          MapEdn::new(map, origin, conventions, Keys { map: Some(namespace), fields })?
+            .visit(visitor)
+        }
+        (_, edn) => {
+          let edn = Edn::Tagged(tag, alloc::boxed::Box::new(edn));
+          Self { edn, origin, conventions }.deserialize_any(visitor)
+        }
+      },
+      edn => Self { edn, origin, conventions }.deserialize_any(visitor),
+    }
   }
 
   fn deserialize_enum<V>(
@@ -306,79 +607,170 @@ impl<'de> de::Deserializer<'de> for Edn<'de> {
   where
     V: Visitor<'de>,
   {
-    let Edn::Tagged(tag, edn) = self else {
-      return Err(de::Error::custom(format!("can't convert {self:?} into Tagged for enum")));
-    };
-
-    let mut split = tag.split('/');
-    let (Some(tag_first), Some(tag_second)) = (split.next(), split.next()) else {
-      return Err(de::Error::custom(format!("Expected namespace in {tag} for Tagged for enum")));
-    };
-
-    if name != tag_first {
-      return Err(de::Error::custom(format!("namespace in {tag} can't be matched to {name}")));
-    }
-
-    visitor.visit_enum(EnumEdn::new(*edn, tag_second))
+    let namespace = self.conventions.enums.namespace(name);
+    visitor.visit_enum(EnumEdn::read(self.edn, namespace, self.origin, self.conventions)?)
   }
 
   fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value>
   where
     V: Visitor<'de>,
   {
-    match self {
+    match self.edn {
       Edn::Key(k) | Edn::Str(k) | Edn::Symbol(k) => visitor.visit_borrowed_str(k),
-      other => visitor.visit_string(other.to_string()),
+      // Left to the visitor, so keys that can't name a field are an error instead of unknown
+      edn => Self { edn, ..self }.deserialize_any(visitor),
     }
   }
 }
 
-struct SeqEdn<'de> {
+// What Rust has no type for is a pair for `types::Tagged`, nothing else gets it
+fn visit_tagged<'de, V>(
+  tag: &'de str,
+  edn: Edn<'de>,
+  origin: Option<Origin<'_, '_, 'de>>,
+  conventions: Conventions,
+  visitor: V,
+) -> Result<V::Value>
+where
+  V: Visitor<'de>,
+{
+  SeqEdn::new(vec![edn, Edn::Str(tag)], origin, conventions).visit(visitor)
+}
+
+struct SeqEdn<'a, 'p, 'de> {
   de: Vec<Edn<'de>>,
+  origin: Option<Origin<'a, 'p, 'de>>,
+  conventions: Conventions,
+  index: usize,
 }
 
-impl<'de> SeqEdn<'de> {
-  const fn new(de: Vec<Edn<'de>>) -> Self {
-    SeqEdn { de }
+impl<'a, 'p, 'de> SeqEdn<'a, 'p, 'de> {
+  const fn new(
+    de: Vec<Edn<'de>>,
+    origin: Option<Origin<'a, 'p, 'de>>,
+    conventions: Conventions,
+  ) -> Self {
+    SeqEdn { de, origin, conventions, index: 0 }
+  }
+
+  // Visits the elements, passing over the ones serde didn't ask for
+  fn visit<V>(mut self, visitor: V) -> Result<V::Value>
+  where
+    V: Visitor<'de>,
+  {
+    let value = visitor.visit_seq(&mut self)?;
+    while self.next_element::<de::IgnoredAny>()?.is_some() {}
+    Ok(value)
   }
 }
 
-impl<'de> SeqAccess<'de> for SeqEdn<'de> {
+impl<'de> SeqAccess<'de> for SeqEdn<'_, '_, 'de> {
   type Error = Error;
 
   fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
   where
     T: DeserializeSeed<'de>,
   {
-    let s = self.de.pop();
-    match s {
-      Some(e) => Ok(Some(seed.deserialize(e)?)),
-      None => Ok(None),
+    let Some(edn) = self.de.pop() else { return Ok(None) };
+    let path = self.origin.as_ref().map(|origin| Path::Index(origin.path, self.index));
+    self.index += 1;
+
+    let origin = self.origin.as_mut().zip(path.as_ref()).map(|(origin, path)| origin.at(path));
+    Ok(Some(seed.deserialize(EdnDeserializer { edn, origin, conventions: self.conventions })?))
+  }
+}
+
+/// How the keywords and symbols keying a map are read, when it's written `#:namespace{}` or read
+/// into a struct whose fields are in a namespace.
+#[derive(Debug, Clone, Copy, Default)]
+struct Keys<'de> {
+  // The namespace of a `#:namespace{}` map, for the keys in it written without one
+  map: Option<&'de str>,
+  // The namespace of the struct's fields, which they're read without
+  fields: Option<&'static str>,
+}
+
+impl<'de> Keys<'de> {
+  fn deserialize<K>(self, seed: K, key: Edn<'de>) -> Result<K::Value>
+  where
+    K: DeserializeSeed<'de>,
+  {
+    let (Edn::Key(name) | Edn::Symbol(name)) = key else { return seed.deserialize(key) };
+    let (namespace, bare) = match name.split_once('/') {
+      // `:_/name` opts out of the namespace of the map
+      Some(("_", bare)) if self.map.is_some() => (None, bare),
+      Some((namespace, bare)) => (Some(namespace), bare),
+      None => (self.map, name),
+    };
+    match namespace {
+      Some(namespace) if self.fields != Some(namespace) => {
+        if name.contains('/') {
+          seed.deserialize(key)
+        } else {
+          // Only the map knows the namespace of the key, which has to be put together
+          seed.deserialize(format!("{namespace}/{bare}").into_deserializer())
+        }
+      }
+      _ if matches!(key, Edn::Key(_)) => seed.deserialize(Edn::Key(bare)),
+      _ => seed.deserialize(Edn::Symbol(bare)),
     }
   }
 }
 
-struct MapEdn<'de> {
-  de: BTreeMap<Edn<'de>, Edn<'de>>,
-  pending_value: Option<Edn<'de>>,
+struct MapEdn<'a, 'p, 'de> {
+  de: <edn::Map<Edn<'de>, Edn<'de>> as IntoIterator>::IntoIter,
+  // The next value, and its key when it's needed for the path to it
+  pending_value: Option<(Option<Edn<'de>>, Edn<'de>)>,
+  origin: Option<Origin<'a, 'p, 'de>>,
+  conventions: Conventions,
+  keys: Keys<'de>,
 }
 
-impl<'de> MapEdn<'de> {
-  const fn new(de: BTreeMap<Edn<'de>, Edn<'de>>) -> Self {
-    MapEdn { de, pending_value: None }
+impl<'a, 'p, 'de> MapEdn<'a, 'p, 'de> {
+  fn new(
+    de: edn::Map<Edn<'de>, Edn<'de>>,
+    origin: Option<Origin<'a, 'p, 'de>>,
+    conventions: Conventions,
+    keys: Keys<'de>,
+  ) -> Result<Self> {
+    // `:b` and `:a/b` are the same key in `#:a{}`, which an `Edn` doesn't have to have checked
+    if let Some(namespace) = keys.map
+      && !edn::unique_keys(namespace, &de)
+    {
+      return Err(origin.as_ref().map_or(Error::new(Code::HashMapDuplicateKey), |origin| {
+        Error::from_offset(Code::HashMapDuplicateKey, origin.start)
+      }));
+    }
+    Ok(MapEdn { de: de.into_iter(), pending_value: None, origin, conventions, keys })
+  }
+
+  // Visits the entries, passing over the ones serde didn't ask for
+  fn visit<V>(mut self, visitor: V) -> Result<V::Value>
+  where
+    V: Visitor<'de>,
+  {
+    let value = visitor.visit_map(&mut self)?;
+    self.finish()?;
+    Ok(value)
+  }
+
+  // Passes over the entries serde didn't ask for
+  fn finish(&mut self) -> Result<()> {
+    while self.next_entry::<de::IgnoredAny, de::IgnoredAny>()?.is_some() {}
+    Ok(())
   }
 }
 
-impl<'de> MapAccess<'de> for MapEdn<'de> {
+impl<'de> MapAccess<'de> for MapEdn<'_, '_, 'de> {
   type Error = Error;
 
   fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
   where
     K: DeserializeSeed<'de>,
   {
-    if let Some((k, v)) = self.de.pop_first() {
-      self.pending_value = Some(v);
-      return Ok(Some(seed.deserialize(k)?));
+    if let Some((k, v)) = self.de.next() {
+      self.pending_value = Some((self.origin.is_some().then(|| k.clone()), v));
+      return Ok(Some(self.keys.deserialize(seed, k)?));
     }
     Ok(None)
   }
@@ -388,42 +780,84 @@ impl<'de> MapAccess<'de> for MapEdn<'de> {
     V: DeserializeSeed<'de>,
   {
     // Infallible: serde always calls next_key_seed before next_value_seed.
-    let v = self.pending_value.take().ok_or_else(|| {
+    let (key, edn) = self.pending_value.take().ok_or_else(|| {
       de::Error::custom("value missing: next_value_seed called without next_key_seed")
     })?;
-    seed.deserialize(v)
+    let path =
+      self.origin.as_ref().zip(key.as_ref()).map(|(origin, key)| Path::Key(origin.path, key));
+
+    let origin = self.origin.as_mut().zip(path.as_ref()).map(|(origin, path)| origin.at(path));
+    seed.deserialize(EdnDeserializer { edn, origin, conventions: self.conventions })
   }
 }
 
-#[derive(Debug)]
-struct EnumEdn<'de> {
+struct EnumEdn<'a, 'p, 'de> {
   de: Edn<'de>,
   variant: &'de str,
+  origin: Option<Origin<'a, 'p, 'de>>,
+  conventions: Conventions,
+}
+
+impl<'a, 'p, 'de> EnumEdn<'a, 'p, 'de> {
+  /// Reads a variant written in any of the forms of [`EnumRepr`], with tags in `namespace`.
+  fn read(
+    edn: Edn<'de>,
+    namespace: &str,
+    origin: Option<Origin<'a, 'p, 'de>>,
+    conventions: Conventions,
+  ) -> Result<Self> {
+    let (de, variant) = match edn {
+      Edn::Key(variant) => (Edn::Nil, variant),
+      Edn::Map(mut map) if map.len() == 1 => match map.pop_first() {
+        Some((Edn::Key(variant), edn)) => (edn, variant),
+        _ => return Err(de::Error::custom("expected a keyword for the variant of an enum")),
+      },
+      Edn::Vector(items) if items.len() == 2 => match <[Edn<'de>; 2]>::try_from(items) {
+        Ok([Edn::Key(variant), edn]) => (edn, variant),
+        _ => return Err(de::Error::custom("expected a keyword for the variant of an enum")),
+      },
+      Edn::Tagged(tag, edn) => (*edn, tag_variant(tag, namespace)?),
+      edn => return Err(de::Error::custom(format!("can't convert {edn:?} into an enum"))),
+    };
+    // The payload is only a part of the form
+    let origin = origin.map(|origin| Origin { end: None, ..origin });
+    Ok(EnumEdn { de, variant, origin, conventions })
+  }
 }
 
-impl<'de> EnumEdn<'de> {
-  const fn new(de: Edn<'de>, variant: &'de str) -> Self {
-    EnumEdn { de, variant }
+// The variant a tag is for, if it's in `namespace`
+fn tag_variant<'de>(tag: &'de str, namespace: &str) -> Result<&'de str> {
+  let mut split = tag.split('/');
+  let (Some(tag_first), Some(tag_second)) = (split.next(), split.next()) else {
+    return Err(de::Error::custom(format!("Expected namespace in {tag} for Tagged for enum")));
+  };
+
+  if namespace != tag_first {
+    return Err(de::Error::custom(format!("namespace in {tag} can't be matched to {namespace}")));
   }
+  Ok(tag_second)
 }
 
-impl<'de> EnumAccess<'de> for EnumEdn<'de> {
+impl<'a, 'p, 'de> EnumAccess<'de> for EnumEdn<'a, 'p, 'de> {
   type Error = Error;
-  type Variant = Self;
+  type Variant = EdnDeserializer<'a, 'p, 'de>;
 
   fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant)>
   where
     V: DeserializeSeed<'de>,
   {
     let val = seed.deserialize(self.variant.into_deserializer())?;
-    Ok((val, self))
+    Ok((val, EdnDeserializer { edn: self.de, origin: self.origin, conventions: self.conventions }))
   }
 }
 
-impl<'de> VariantAccess<'de> for EnumEdn<'de> {
+impl<'de> VariantAccess<'de> for EdnDeserializer<'_, '_, 'de> {
   type Error = Error;
 
-  fn unit_variant(self) -> Result<()> {
+  fn unit_variant(mut self) -> Result<()> {
+    if self.edn != Edn::Nil {
+      self.pass_over(format_args!("ignored value"))?;
+    }
     Ok(())
   }
 
@@ -431,14 +865,14 @@ impl<'de> VariantAccess<'de> for EnumEdn<'de> {
   where
     T: DeserializeSeed<'de>,
   {
-    seed.deserialize(self.de)
+    seed.deserialize(self)
   }
 
   fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value>
   where
     V: Visitor<'de>,
   {
-    de::Deserializer::deserialize_seq(self.de, visitor)
+    de::Deserializer::deserialize_seq(self, visitor)
   }
 
   fn struct_variant<V>(
@@ -449,6 +883,1045 @@ impl<'de> VariantAccess<'de> for EnumEdn<'de> {
   where
     V: Visitor<'de>,
   {
-    de::Deserializer::deserialize_map(self.de, visitor)
+    de::Deserializer::deserialize_map(self, visitor)
+  }
+}
+
+/// Where a value sits inside the document being deserialized, used to annotate errors.
+enum Path<'p, 'de> {
+  Root,
+  Index(&'p Self, usize),
+  Key(&'p Self, &'p Edn<'de>),
+}
+
+impl Path<'_, '_> {
+  fn write_segments(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let (parent, segment): (&Self, &dyn Display) = match self {
+      Self::Root => return Ok(()),
+      Self::Index(parent, index) => (parent, index),
+      Self::Key(parent, key) => (parent, key),
+    };
+    parent.write_segments(f)?;
+    if !matches!(parent, Self::Root) {
+      f.write_str(" ")?;
+    }
+    write!(f, "{segment}")
+  }
+}
+
+impl Display for Path<'_, '_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("[")?;
+    self.write_segments(f)?;
+    f.write_str("]")
+  }
+}
+
+/// Attaches the position `ptr` and the `path` to an error that doesn't know where it happened.
+///
+/// Errors bubble up through every enclosing form, so only the innermost one gets to locate it.
+fn locate(mut err: Error, ptr: usize, path: &Path<'_, '_>) -> Error {
+  if err.ptr.is_some() {
+    return err;
+  }
+  if let Code::Serde(msg) = &mut err.code
+    && !matches!(path, Path::Root)
+  {
+    *msg = format!("{msg} at {path}");
+  }
+  err.ptr = Some(ptr);
+  err
+}
+
+/// What a [`Deserializer`] does with what it reads but can't put anywhere.
+///
+/// That's values under keys that aren't fields, elements past the end of a tuple, the value after
+/// a unit variant, and numbers that don't convert exactly.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Unused {
+  /// Passes over it, like serde does.
+  #[default]
+  Skip,
+  /// Fails with an error pointing at it, see [`from_str_strict`].
+  Deny,
+  /// Passes over it and keeps a [`Warning`] about it, see [`from_str_lenient`].
+  Warn,
+}
+
+/// Something a [`Deserializer`] passed over with [`Unused::Warn`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+  /// What was passed over, like `ignored value`.
+  pub message: String,
+  /// The path to it, like `[:servers 2 :port]`.
+  pub path: String,
+  /// Line number, counting from 1.
+  pub line: usize,
+  /// Column number, counting from 1. The count is utf-8 chars.
+  pub column: usize,
+  /// Byte offset of the form it's in.
+  pub ptr: usize,
+}
+
+impl Display for Warning {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} at {} on line {}, column {}", self.message, self.path, self.line, self.column)
+  }
+}
+
+/// Deserializes EDN straight from its source, without building an [`Edn`] first.
+///
+/// Collections are handed to serde while they are read. Map keys and set elements are read into
+/// [`Edn`] so that duplicates are caught, and so are values serde skips, which keeps them
+/// validated.
+///
+/// ```
+/// use clojure_reader::de::Deserializer;
+/// use serde::Deserialize;
+///
+/// let mut de = Deserializer::from_str("[1 2 3] ; the end");
+/// let numbers = Vec::<u8>::deserialize(&mut de).unwrap();
+/// de.end().unwrap();
+///
+/// assert_eq!(numbers, [1, 2, 3]);
+/// ```
+#[derive(Debug)]
+pub struct Deserializer<'de> {
+  reader: SourceReader<'de>,
+  // A token that was read ahead, and where it starts and ends
+  peeked: Option<(Span, Token<'de>)>,
+  // Where the last token that was taken ends
+  end: usize,
+  conventions: Conventions,
+  unused: Unused,
+  warnings: Vec<Warning>,
+  // Turns offsets into lines and columns for warnings, made for the first one
+  lines: Option<LineIndex<'de>>,
+}
+
+impl<'de> Deserializer<'de> {
+  /// Creates a deserializer reading from `source`.
+  pub const fn from_str(source: &'de str) -> Self {
+    Self {
+      reader: SourceReader::new(source),
+      peeked: None,
+      end: 0,
+      conventions: Conventions { enums: EnumRepr::Tagged, namespace: None },
+      unused: Unused::Skip,
+      warnings: Vec::new(),
+      lines: None,
+    }
+  }
+
+  /// Sets the namespace enum variants are tagged with, from how they're written.
+  ///
+  /// Variants are read in any of the forms of [`EnumRepr`], but tags have to be in the enum's name
+  /// unless it's [`EnumRepr::Namespaced`].
+  ///
+  /// ```
+  /// use clojure_reader::de::Deserializer;
+  /// use clojure_reader::ser::EnumRepr;
+  /// use serde::Deserialize;
+  /// use serde_derive::Deserialize;
+  ///
+  /// #[derive(Deserialize, Debug, PartialEq)]
+  /// #[serde(rename_all = "kebab-case")]
+  /// enum Shape {
+  ///   Dot,
+  ///   Circle(u32),
+  /// }
+  ///
+  /// let mut de = Deserializer::from_str("[:dot {:circle 2} [:circle 3] #geo/circle 4]")
+  ///   .enums(EnumRepr::Namespaced("geo"));
+  /// let shapes = Vec::<Shape>::deserialize(&mut de).unwrap();
+  /// assert_eq!(shapes, [Shape::Dot, Shape::Circle(2), Shape::Circle(3), Shape::Circle(4)]);
+  /// ```
+  #[must_use]
+  pub const fn enums(mut self, enums: EnumRepr) -> Self {
+    self.conventions.enums = enums;
+    self
+  }
+
+  /// Sets the namespace of the fields of structs, for the ones not renamed to `#:namespace`.
+  ///
+  /// Keys are read with or without the namespace, and `#:namespace{}` maps are read like any
+  /// other.
+  ///
+  /// ```
+  /// use clojure_reader::de::Deserializer;
+  /// use serde::Deserialize;
+  /// use serde_derive::Deserialize;
+  ///
+  /// #[derive(Deserialize, Debug, PartialEq)]
+  /// struct User {
+  ///   name: String,
+  ///   #[serde(rename = "account/id")]
+  ///   id: u32,
+  /// }
+  ///
+  /// let mut de = Deserializer::from_str("[{:user/name \"ada\", :account/id 1} #:user{:name \"bob\", :account/id 2}]")
+  ///   .namespace("user");
+  /// let users = Vec::<User>::deserialize(&mut de).unwrap();
+  /// assert_eq!(users, [User { name: "ada".into(), id: 1 }, User { name: "bob".into(), id: 2 }]);
+  /// ```
+  #[must_use]
+  pub const fn namespace(mut self, namespace: &'static str) -> Self {
+    self.conventions.namespace = Some(namespace);
+    self
+  }
+
+  /// Sets what happens to what's read but doesn't end up in the deserialized value, see
+  /// [`Unused`].
+  #[must_use]
+  pub const fn unused(mut self, unused: Unused) -> Self {
+    self.unused = unused;
+    self
+  }
+
+  /// What's been passed over so far with [`Unused::Warn`], in the order it was read.
+  pub fn warnings(&self) -> &[Warning] {
+    &self.warnings
+  }
+
+  /// Checks that nothing but whitespace and comments follow the deserialized value.
+  ///
+  /// # Errors
+  ///
+  /// Returns `Code::Serde` if there is anything else, positioned where it starts.
+  pub fn end(&mut self) -> Result<()> {
+    let start = if let Some((span, _)) = self.peeked {
+      span.0
+    } else {
+      self.reader.nibble_trivia();
+      if self.reader.remaining().is_empty() {
+        return Ok(());
+      }
+      self.reader.span_from(0).1
+    };
+    let err = Error::from_offset(Code::Serde("trailing input".to_string()), start);
+    Err(err.with_source(self.reader.source()))
+  }
+
+  fn peek(&mut self) -> Result<Option<&(Span, Token<'de>)>> {
+    if self.peeked.is_none() {
+      while let Some((start, token)) = self.reader.next_token()? {
+        if matches!(token, Token::Discard) {
+          parse::discard_form(&mut self.reader)?;
+          continue;
+        }
+        self.peeked = Some((self.reader.span_from(start), token));
+        break;
+      }
+    }
+    Ok(self.peeked.as_ref())
+  }
+
+  // Offset where the next token starts, or the end of the source
+  fn peek_start(&mut self) -> Result<usize> {
+    let len = self.reader.source().len();
+    Ok(self.peek()?.map_or(len, |(span, _)| span.0))
+  }
+
+  fn next(&mut self) -> Result<Option<(Span, Token<'de>)>> {
+    self.peek()?;
+    let next = self.peeked.take();
+    if let Some((span, _)) = &next {
+      self.end = span.1;
+    }
+    Ok(next)
+  }
+
+  // Whether the next token closes the collection with `close`, consuming it if so
+  fn close(&mut self, close: char) -> Result<bool> {
+    match self.peek()? {
+      None => Err(self.eof()),
+      Some(&(span, Token::Close(found))) if found != close => {
+        Err(Error::from_offset(Code::UnmatchedDelimiter(found), span.0))
+      }
+      Some((_, Token::Close(_))) => {
+        let _ = self.next()?;
+        Ok(true)
+      }
+      Some(_) => Ok(false),
+    }
+  }
+
+  const fn eof(&self) -> Error {
+    Error::from_offset(Code::UnexpectedEOF, self.reader.source().len())
+  }
+
+  /// Passes over `what`, which is at `ptr`, or fails there if that isn't allowed.
+  fn pass_over(
+    &mut self,
+    what: fmt::Arguments<'_>,
+    ptr: usize,
+    path: &Path<'_, 'de>,
+  ) -> Result<()> {
+    match self.unused {
+      Unused::Skip => Ok(()),
+      Unused::Deny => Err(locate(de::Error::custom(what), ptr, path)),
+      Unused::Warn => {
+        let source = self.reader.source();
+        let lines = self.lines.get_or_insert_with(|| LineIndex::new(source));
+        let LineCol { line, column } = lines.line_col(ptr);
+        let (message, path) = (what.to_string(), path.to_string());
+        self.warnings.push(Warning { message, path, line, column, ptr });
+        Ok(())
+      }
+    }
+  }
+
+  /// Reads the next value into an [`Edn`], for when serde needs more than one look at it.
+  fn read_edn(&mut self) -> Result<Edn<'de>> {
+    match self.next()? {
+      Some((_, Token::Atom(atom))) => Ok(atom.into()),
+      Some((span, _)) => {
+        self.reader.rewind(span.0);
+        let edn = parse::read_form(&mut self.reader)?;
+        self.end = self.reader.span_from(0).1;
+        Ok(edn)
+      }
+      None => Err(self.eof()),
+    }
+  }
+}
+
+macro_rules! forward_to_root {
+  ($($method:ident($($arg:ident: $ty:ty),*))*) => {
+    $(
+      fn $method<V>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value>
+      where
+        V: Visitor<'de>,
+      {
+        let source = self.reader.source();
+        let value = match self.peek().map(|peeked| peeked.map(|(span, _)| span.0)) {
+          Ok(Some(start)) => ValueDeserializer::new(self, &Path::Root)
+            .$method($($arg,)* visitor)
+            .map_err(|err| locate(err, start, &Path::Root)),
+          // An empty source reads as nil, like it does for `read_string`
+          Ok(None) => Edn::Nil.$method($($arg,)* visitor).map_err(|err| locate(err, 0, &Path::Root)),
+          Err(err) => Err(err),
+        };
+        value.map_err(|err| err.with_source(source))
+      }
+    )*
+  };
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+  type Error = Error;
+
+  forward_to_root! {
+    deserialize_any() deserialize_bool() deserialize_i8() deserialize_i16() deserialize_i32()
+    deserialize_i64() deserialize_u8() deserialize_u16() deserialize_u32() deserialize_u64()
+    deserialize_f32() deserialize_f64() deserialize_char() deserialize_str() deserialize_string()
+    deserialize_bytes() deserialize_byte_buf() deserialize_option() deserialize_unit()
+    deserialize_unit_struct(name: &'static str) deserialize_newtype_struct(name: &'static str)
+    deserialize_seq() deserialize_tuple(len: usize)
+    deserialize_tuple_struct(name: &'static str, len: usize) deserialize_map()
+    deserialize_struct(name: &'static str, fields: &'static [&'static str])
+    deserialize_enum(name: &'static str, variants: &'static [&'static str])
+    deserialize_identifier() deserialize_ignored_any()
+  }
+}
+
+/// Deserializes the next value of a [`Deserializer`], knowing where it sits in the document.
+struct ValueDeserializer<'a, 'de, 'p> {
+  de: &'a mut Deserializer<'de>,
+  path: &'p Path<'p, 'de>,
+}
+
+impl<'a, 'de, 'p> ValueDeserializer<'a, 'de, 'p> {
+  const fn new(de: &'a mut Deserializer<'de>, path: &'p Path<'p, 'de>) -> Self {
+    Self { de, path }
+  }
+
+  /// Reads the value into an [`Edn`], for when serde needs more than one look at it.
+  fn read_edn(self) -> Result<EdnDeserializer<'a, 'p, 'de>> {
+    let start = self.de.peek_start()?;
+    let edn = self.de.read_edn()?;
+    let end = Some(self.de.end);
+    let conventions = self.de.conventions;
+    let origin = Some(Origin { de: self.de, path: self.path, start, end });
+    Ok(EdnDeserializer { edn, origin, conventions })
+  }
+
+  /// Streams the value if it's a map or a `#:namespace{}` map, with `keys` read in its namespace.
+  fn visit_map<V>(self, visitor: V, mut keys: Keys<'de>) -> Result<V::Value>
+  where
+    V: Visitor<'de>,
+  {
+    match self.de.peek()? {
+      Some(&(_, Token::Open(parse::Open::Map))) => {
+        let _ = self.de.next()?;
+        MapStream::new(self.de, self.path, keys).visit(visitor)
+      }
+      Some(&(span, Token::Tag(tag, tag_span))) if tag.starts_with(':') => {
+        let _ = self.de.next()?;
+        crate::edn::validate_tag(tag, tag_span)?;
+        if let Some((_, Token::Open(parse::Open::Map))) = self.de.peek()? {
+          let _ = self.de.next()?;
+          keys.map = tag.strip_prefix(':');
+          return MapStream::new(self.de, self.path, keys).visit(visitor);
+        }
+        // Not a map after all, read it again for the error
+        self.de.peeked = None;
+        self.de.reader.rewind(span.0);
+        de::Deserializer::deserialize_any(self.read_edn()?, visitor)
+      }
+      Some(&(_, Token::Tag(..))) => de::Deserializer::deserialize_any(self.read_edn()?, visitor),
+      _ => de::Deserializer::deserialize_any(self, visitor),
+    }
+  }
+}
+
+macro_rules! via_edn {
+  ($($method:ident)*) => {
+    $(
+      fn $method<V>(self, visitor: V) -> Result<V::Value>
+      where
+        V: Visitor<'de>,
+      {
+        self.read_edn()?.$method(visitor)
+      }
+    )*
+  };
+}
+
+impl<'de> de::Deserializer<'de> for ValueDeserializer<'_, 'de, '_> {
+  type Error = Error;
+
+  fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+  where
+    V: Visitor<'de>,
+  {
+    let (span, token) = self.de.next()?.ok_or_else(|| self.de.eof())?;
+    match token {
+      Token::Atom(atom) => Edn::from(atom).deserialize_any(visitor),
+      Token::Open(open @ (parse::Open::Vector | parse::Open::List)) => {
+        let close = if open == parse::Open::Vector { ']' } else { ')' };
+        let mut seq = SeqStream::new(self.de, close, self.path);
+        let value = visitor.visit_seq(&mut seq)?;
+        seq.finish()?;
+        Ok(value)
+      }
+      Token::Open(parse::Open::Set) => {
+        let mut set = SetElements::read(self.de, self.path)?;
+        let value = visitor.visit_seq(&mut set)?;
+        set.finish()?;
+        Ok(value)
+      }
+      Token::Open(parse::Open::Map) => {
+        MapStream::new(self.de, self.path, Keys::default()).visit(visitor)
+      }
+      Token::Close(close) => Err(Error::from_offset(Code::UnmatchedDelimiter(close), span.0)),
+      Token::Tag(..) | Token::Discard => {
+        self.de.peeked = Some((span, token));
+        self.visit_map(visitor, Keys::default())
+      }
+    }
+  }
+
+  forward_to_deserialize_any! {
+    bool i64 char str string map seq tuple_struct
+  }
+
+  via_edn! {
+    deserialize_i8 deserialize_i16 deserialize_i32 deserialize_u8 deserialize_u16 deserialize_u32
+    deserialize_u64 deserialize_f32 deserialize_f64 deserialize_bytes deserialize_byte_buf deserialize_unit
+    deserialize_identifier deserialize_ignored_any
+  }
+
+  fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+  where
+    V: Visitor<'de>,
+  {
+    if let Some((_, Token::Atom(parse::Atom::Nil))) = self.de.peek()? {
+      let _ = self.de.next()?;
+      visitor.visit_none()
+    } else {
+      visitor.visit_some(self)
+    }
+  }
+
+  fn deserialize_unit_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+  where
+    V: Visitor<'de>,
+  {
+    self.deserialize_unit(visitor)
+  }
+
+  fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value>
+  where
+    V: Visitor<'de>,
+  {
+    match Marker::of(name) {
+      Some(Marker::Raw) => {
+        let start = self.de.peek_start()?;
+        // Read to be validated, only the source is kept
+        let _ = self.de.read_edn()?;
+        let raw = self.de.reader.source().get(start..self.de.end).unwrap_or_default();
+        visitor.visit_borrowed_str(raw)
+      }
+      Some(Marker::Set) if matches!(self.de.peek()?, Some((_, Token::Open(parse::Open::Set)))) => {
+        visitor.visit_newtype_struct(self)
+      }
+      Some(_) => self.read_edn()?.deserialize_newtype_struct(name, visitor),
+      None => visitor.visit_newtype_struct(self),
+    }
+  }
+
+  fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+  where
+    V: Visitor<'de>,
+  {
+    self.deserialize_seq(visitor)
+  }
+
+  fn deserialize_struct<V>(
+    self,
+    name: &'static str,
+    fields: &'static [&'static str],
+    visitor: V,
+  ) -> Result<V::Value>
+  where
+    V: Visitor<'de>,
+  {
+    if name == SPANNED_NAME && fields == SPANNED_FIELDS {
+      let start = self.de.peek_start()?;
+      let value = SpannedValue::Stream(self);
+      return visitor.visit_map(SpannedAccess { start, end: start, value, field: 0 });
+    }
+    let fields = struct_namespace(name).or(self.de.conventions.namespace);
+    self.visit_map(visitor, Keys { map: None, fields })
+  }
+
+  fn deserialize_enum<V>(
+    self,
+    name: &'static str,
+    variants: &'static [&'static str],
+    visitor: V,
+  ) -> Result<V::Value>
+  where
+    V: Visitor<'de>,
+  {
+    let namespace = self.de.conventions.enums.namespace(name);
+    let Some(&(_, Token::Tag(tag, tag_span))) = self.de.peek()? else {
+      // Keywords, maps and vectors are small enough to be read first
+      let edn = self.read_edn()?;
+      return visitor.visit_enum(EnumEdn::read(edn.edn, namespace, edn.origin, edn.conventions)?);
+    };
+    let _ = self.de.next()?;
+    crate::edn::validate_tag(tag, tag_span)?;
+    if tag.starts_with(':') {
+      // Namespaced maps can't be matched to enums anyway, read it for the error message
+      let edn = Edn::Tagged(tag, alloc::boxed::Box::new(self.de.read_edn()?));
+      return edn.deserialize_enum(name, variants, visitor);
+    }
+
+    let variant = tag_variant(tag, namespace)?;
+    visitor.visit_enum(EnumStream { de: self, variant })
+  }
+}
+
+/// Streams the elements of a vector or a list.
+struct SeqStream<'a, 'de, 'p> {
+  de: &'a mut Deserializer<'de>,
+  close: char,
+  done: bool,
+  index: usize,
+  path: &'p Path<'p, 'de>,
+}
+
+impl<'a, 'de, 'p> SeqStream<'a, 'de, 'p> {
+  const fn new(de: &'a mut Deserializer<'de>, close: char, path: &'p Path<'p, 'de>) -> Self {
+    Self { de, close, done: false, index: 0, path }
+  }
+
+  // Reads the elements serde didn't ask for, which still have to be valid
+  fn finish(&mut self) -> Result<()> {
+    while self.next_element::<de::IgnoredAny>()?.is_some() {}
+    Ok(())
+  }
+}
+
+impl<'de> SeqAccess<'de> for SeqStream<'_, 'de, '_> {
+  type Error = Error;
+
+  fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+  where
+    T: DeserializeSeed<'de>,
+  {
+    self.done = self.done || self.de.close(self.close)?;
+    if self.done {
+      return Ok(None);
+    }
+    let path = Path::Index(self.path, self.index);
+    self.index += 1;
+
+    let start = self.de.peek_start()?;
+    let value = seed.deserialize(ValueDeserializer::new(self.de, &path));
+    value.map(Some).map_err(|e| locate(e, start, &path))
+  }
+}
+
+/// Hands out the elements of a set in order. They have to be read up front anyway, to check that
+/// each is unique.
+struct SetElements<'a, 'de, 'p> {
+  de: &'a mut Deserializer<'de>,
+  // Each element along with where it starts and ends
+  elements: btree_map::IntoIter<Edn<'de>, Span>,
+  index: usize,
+  path: &'p Path<'p, 'de>,
+}
+
+impl<'a, 'de, 'p> SetElements<'a, 'de, 'p> {
+  fn read(de: &'a mut Deserializer<'de>, path: &'p Path<'p, 'de>) -> Result<Self> {
+    let mut elements = BTreeMap::new();
+    while !de.close('}')? {
+      let start = de.peek_start()?;
+      let edn = de.read_edn()?;
+      if elements.insert(edn, Span(start, de.end)).is_some() {
+        return Err(Error::from_offset(Code::SetDuplicateKey, start));
+      }
+    }
+    Ok(Self { de, elements: elements.into_iter(), index: 0, path })
+  }
+
+  // Passes over the elements serde didn't ask for
+  fn finish(&mut self) -> Result<()> {
+    while self.next_element::<de::IgnoredAny>()?.is_some() {}
+    Ok(())
+  }
+}
+
+impl<'de> SeqAccess<'de> for SetElements<'_, 'de, '_> {
+  type Error = Error;
+
+  fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+  where
+    T: DeserializeSeed<'de>,
+  {
+    let Some((edn, Span(start, end))) = self.elements.next() else { return Ok(None) };
+    let path = Path::Index(self.path, self.index);
+    self.index += 1;
+
+    let conventions = self.de.conventions;
+    let origin = Some(Origin { de: self.de, path: &path, start, end: Some(end) });
+    let value = seed.deserialize(EdnDeserializer { edn, origin, conventions });
+    value.map(Some).map_err(|e| locate(e, start, &path))
+  }
+
+  fn size_hint(&self) -> Option<usize> {
+    Some(self.elements.len())
+  }
+}
+
+/// Streams the entries of a map. Keys are read into an [`Edn`] to check that they are unique.
+struct MapStream<'a, 'de, 'p> {
+  de: &'a mut Deserializer<'de>,
+  done: bool,
+  path: &'p Path<'p, 'de>,
+  seen: BTreeSet<Edn<'de>>,
+  // The keywords and symbols of a `#:namespace{}` map, by the namespace and name they stand for
+  names: BTreeSet<(bool, (Option<&'de str>, &'de str))>,
+  // The key whose value is next
+  key: Option<Edn<'de>>,
+  keys: Keys<'de>,
+}
+
+impl<'a, 'de, 'p> MapStream<'a, 'de, 'p> {
+  const fn new(de: &'a mut Deserializer<'de>, path: &'p Path<'p, 'de>, keys: Keys<'de>) -> Self {
+    Self { de, done: false, path, seen: BTreeSet::new(), names: BTreeSet::new(), key: None, keys }
+  }
+
+  // Visits the entries, reading the ones serde didn't ask for
+  fn visit<V>(mut self, visitor: V) -> Result<V::Value>
+  where
+    V: Visitor<'de>,
+  {
+    let value = visitor.visit_map(&mut self)?;
+    self.finish()?;
+    Ok(value)
+  }
+
+  // Reads the entries serde didn't ask for, which still have to be valid
+  fn finish(&mut self) -> Result<()> {
+    while self.next_entry::<de::IgnoredAny, de::IgnoredAny>()?.is_some() {}
+    Ok(())
+  }
+}
+
+impl<'de> MapAccess<'de> for MapStream<'_, 'de, '_> {
+  type Error = Error;
+
+  fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+  where
+    K: DeserializeSeed<'de>,
+  {
+    self.done = self.done || self.de.close('}')?;
+    if self.done {
+      return Ok(None);
+    }
+
+    let start = self.de.peek_start()?;
+    let key = self.de.read_edn()?;
+    // `:b` and `:a/b` are the same key in `#:a{}`
+    let name = self.keys.map.and_then(|namespace| edn::qualified_key(namespace, &key));
+    if name.map_or_else(|| self.seen.contains(&key), |name| self.names.contains(&name)) {
+      return Err(Error::from_offset(Code::HashMapDuplicateKey, start));
+    }
+    let path = Path::Key(self.path, &key);
+    let value = self.keys.deserialize(seed, key.clone()).map_err(|e| locate(e, start, &path))?;
+
+    match name {
+      Some(name) => self.names.insert(name),
+      None => self.seen.insert(key.clone()),
+    };
+    self.key = Some(key);
+    Ok(Some(value))
+  }
+
+  fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+  where
+    V: DeserializeSeed<'de>,
+  {
+    // Infallible: serde always calls next_key_seed before next_value_seed.
+    let key = self.key.take().ok_or_else(|| {
+      de::Error::custom("value missing: next_value_seed called without next_key_seed")
+    })?;
+    let path = Path::Key(self.path, &key);
+
+    let start = match self.de.peek()? {
+      None => return Err(self.de.eof()),
+      // A key without a value
+      Some(&(span, Token::Close('}'))) => {
+        return Err(Error::from_offset(Code::UnexpectedEOF, span.0));
+      }
+      Some((span, _)) => span.0,
+    };
+    seed.deserialize(ValueDeserializer::new(self.de, &path)).map_err(|e| locate(e, start, &path))
+  }
+}
+
+struct EnumStream<'a, 'de, 'p> {
+  de: ValueDeserializer<'a, 'de, 'p>,
+  variant: &'de str,
+}
+
+impl<'a, 'de, 'p> EnumAccess<'de> for EnumStream<'a, 'de, 'p> {
+  type Error = Error;
+  type Variant = ValueDeserializer<'a, 'de, 'p>;
+
+  fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant)>
+  where
+    V: DeserializeSeed<'de>,
+  {
+    let val = seed.deserialize(self.variant.into_deserializer())?;
+    Ok((val, self.de))
+  }
+}
+
+impl<'de> VariantAccess<'de> for ValueDeserializer<'_, 'de, '_> {
+  type Error = Error;
+
+  fn unit_variant(self) -> Result<()> {
+    // The tagged value is still there, and still has to be valid
+    self.read_edn()?.unit_variant()
+  }
+
+  fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value>
+  where
+    T: DeserializeSeed<'de>,
+  {
+    seed.deserialize(self)
+  }
+
+  fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+  where
+    V: Visitor<'de>,
+  {
+    de::Deserializer::deserialize_seq(self, visitor)
+  }
+
+  fn struct_variant<V>(
+    self,
+    _fields: &'static [&'static str],
+    visitor: V,
+  ) -> core::result::Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    de::Deserializer::deserialize_map(self, visitor)
+  }
+}
+
+/// Hands a [`Spanned`] the offset its value starts at, the `value` itself, and then where it ended.
+struct SpannedAccess<'a, 'de, 'p> {
+  start: usize,
+  // Where the value ends, once it's known
+  end: usize,
+  value: SpannedValue<'a, 'de, 'p>,
+  field: usize,
+}
+
+enum SpannedValue<'a, 'de, 'p> {
+  Stream(ValueDeserializer<'a, 'de, 'p>),
+  Edn(EdnDeserializer<'a, 'p, 'de>),
+  Taken,
+}
+
+impl<'de> MapAccess<'de> for SpannedAccess<'_, 'de, '_> {
+  type Error = Error;
+
+  fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+  where
+    K: DeserializeSeed<'de>,
+  {
+    let Some(key) = SPANNED_FIELDS.get(self.field) else { return Ok(None) };
+    seed.deserialize(de::value::BorrowedStrDeserializer::new(key)).map(Some)
+  }
+
+  fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+  where
+    V: DeserializeSeed<'de>,
+  {
+    self.field += 1;
+    match self.field {
+      1 => seed.deserialize(self.start.into_deserializer()),
+      2 => match core::mem::replace(&mut self.value, SpannedValue::Taken) {
+        SpannedValue::Stream(de) => {
+          let value = seed.deserialize(ValueDeserializer::new(de.de, de.path));
+          self.end = de.de.end;
+          value
+        }
+        SpannedValue::Edn(edn) => seed.deserialize(edn),
+        SpannedValue::Taken => Err(de::Error::custom("spanned value was already deserialized")),
+      },
+      _ => seed.deserialize(self.end.into_deserializer()),
+    }
+  }
+}
+
+const SPANNED_NAME: &str = "$__clojure_reader_private_Spanned";
+const SPANNED_START: &str = "$__clojure_reader_private_start";
+const SPANNED_END: &str = "$__clojure_reader_private_end";
+const SPANNED_VALUE: &str = "$__clojure_reader_private_value";
+// The end is only known once the value has been read
+const SPANNED_FIELDS: &[&str] = &[SPANNED_START, SPANNED_VALUE, SPANNED_END];
+
+/// A value together with the span of the source it was deserialized from.
+///
+/// Only [`from_str`] and a [`Deserializer`] know where values come from, deserializing a
+/// `Spanned` from anything else, like [`from_edn`], is an error. So is one that's part of a form
+/// read whole, like a map key or the payload of an enum written as a map or a vector, as only the
+/// span of the whole form is known. Comparisons and hashing only look at the value, and it
+/// serializes as the value.
+///
+/// ```
+/// use clojure_reader::de::{Spanned, from_str};
+/// use clojure_reader::line_index::{LineCol, LineIndex};
+/// use serde_derive::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Config {
+///   port: Spanned<u32>,
+/// }
+///
+/// let source = "{:name \"cat\"\n :port 70000}";
+/// let config: Config = from_str(source).unwrap();
+/// assert_eq!(*config.port.get_ref(), 70000);
+/// assert_eq!(&source[config.port.span()], "70000");
+///
+/// let index = LineIndex::new(source);
+/// assert_eq!(index.line_col(config.port.span().start), LineCol { line: 2, column: 8 });
+/// ```
+#[derive(Debug, Clone)]
+pub struct Spanned<T> {
+  span: Span,
+  value: T,
+}
+
+impl<T> Spanned<T> {
+  /// Byte range of the source the value was read from.
+  ///
+  /// Use a [`LineIndex`] to turn it into lines and columns.
+  pub const fn span(&self) -> Range<usize> {
+    self.span.0..self.span.1
+  }
+
+  /// The value.
+  pub const fn get_ref(&self) -> &T {
+    &self.value
+  }
+
+  /// The value, to change it without changing its span.
+  pub const fn get_mut(&mut self) -> &mut T {
+    &mut self.value
+  }
+
+  /// The value, without its span.
+  pub fn into_inner(self) -> T {
+    self.value
+  }
+}
+
+impl<T: PartialEq> PartialEq for Spanned<T> {
+  fn eq(&self, other: &Self) -> bool {
+    self.value == other.value
+  }
+}
+
+impl<T: Eq> Eq for Spanned<T> {}
+
+impl<T: PartialOrd> PartialOrd for Spanned<T> {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    self.value.partial_cmp(&other.value)
+  }
+}
+
+impl<T: Ord> Ord for Spanned<T> {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.value.cmp(&other.value)
+  }
+}
+
+impl<T: Hash> Hash for Spanned<T> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.value.hash(state);
+  }
+}
+
+impl<'de, T> Deserialize<'de> for Spanned<T>
+where
+  T: Deserialize<'de>,
+{
+  fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+  where
+    D: de::Deserializer<'de>,
+  {
+    struct SpannedVisitor<T>(core::marker::PhantomData<T>);
+
+    impl<'de, T> Visitor<'de> for SpannedVisitor<T>
+    where
+      T: Deserialize<'de>,
+    {
+      type Value = Spanned<T>;
+
+      fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a spanned value")
+      }
+
+      fn visit_map<A>(self, mut map: A) -> core::result::Result<Self::Value, A::Error>
+      where
+        A: MapAccess<'de>,
+      {
+        if map.next_key::<&str>()? != Some(SPANNED_START) {
+          return Err(de::Error::custom("spanned value is missing its span"));
+        }
+        let start = map.next_value()?;
+
+        if map.next_key::<&str>()? != Some(SPANNED_VALUE) {
+          return Err(de::Error::custom("spanned value is missing its value"));
+        }
+        let value = map.next_value()?;
+
+        if map.next_key::<&str>()? != Some(SPANNED_END) {
+          return Err(de::Error::custom("spanned value is missing its span"));
+        }
+        Ok(Spanned { span: Span(start, map.next_value()?), value })
+      }
+    }
+
+    deserializer.deserialize_struct(
+      SPANNED_NAME,
+      SPANNED_FIELDS,
+      SpannedVisitor(core::marker::PhantomData),
+    )
+  }
+}
+
+impl<T: Serialize> Serialize for Spanned<T> {
+  fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    self.value.serialize(serializer)
+  }
+}
+
+pub(crate) const RAW_EDN_NAME: &str = "$__clojure_reader_private_RawEdn";
+
+/// A form kept as the exact slice of source it was deserialized from, without building it.
+///
+/// The form is still read to check that it's valid EDN. It serializes as that source, so it can
+/// be passed along untouched, formatting and all. Like [`Spanned`], it can only be deserialized
+/// with [`from_str`] or a [`Deserializer`], and not as part of a form read whole, like a map key
+/// or the payload of an enum written as a map or a vector.
+///
+/// ```
+/// use clojure_reader::de::{RawEdn, from_str};
+/// use clojure_reader::ser::to_string;
+/// use serde_derive::{Deserialize, Serialize};
+///
+/// #[derive(Deserialize, Serialize)]
+/// struct Envelope<'a> {
+///   to: &'a str,
+///   #[serde(borrow)]
+///   payload: RawEdn<'a>,
+/// }
+///
+/// let message: Envelope = from_str(r#"{:to "cat", :payload [1 #_2 {:a  :b}]}"#).unwrap();
+/// assert_eq!(message.payload.get(), "[1 #_2 {:a  :b}]");
+/// assert_eq!(to_string(&message).unwrap(), r#"{:to "cat", :payload [1 #_2 {:a  :b}]}"#);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RawEdn<'e>(&'e str);
+
+impl<'e> RawEdn<'e> {
+  /// The source of the form, from its first to its last char
+  pub const fn get(&self) -> &'e str {
+    self.0
+  }
+}
+
+impl Display for RawEdn<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.0)
+  }
+}
+
+impl<'de: 'e, 'e> Deserialize<'de> for RawEdn<'e> {
+  fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+  where
+    D: de::Deserializer<'de>,
+  {
+    struct RawEdnVisitor;
+
+    impl<'de> Visitor<'de> for RawEdnVisitor {
+      type Value = RawEdn<'de>;
+
+      fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("raw EDN")
+      }
+
+      fn visit_borrowed_str<E>(self, v: &'de str) -> core::result::Result<Self::Value, E>
+      where
+        E: de::Error,
+      {
+        Ok(RawEdn(v))
+      }
+    }
+
+    deserializer.deserialize_newtype_struct(RAW_EDN_NAME, RawEdnVisitor)
+  }
+}
+
+impl Serialize for RawEdn<'_> {
+  fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    serializer.serialize_newtype_struct(RAW_EDN_NAME, self.0)
   }
 }
@@ -4,11 +4,24 @@
 //! -  [`core::fmt::Display`] will output valid EDN for any Edn object
 //! -  With the `unstable` feature enabled, [`TryFrom`]<[`parse::Node`]> implemented for [`Edn`]
 //!    will convert the Node into an Edn
+//! -  With the `unstable` feature enabled, [`From`]<[`parse::SpannedEdn`]> implemented for [`Edn`]
+//!    will drop the spans of a `SpannedEdn`
+//! -  [`From`] Rust values and collections, and [`TryFrom`] an [`Edn`] back into them
+//! -  [`core::ops::Index`] by key or position, giving nil where there's nothing, like `get`
+//! -  [`IntoIterator`] over what's in a collection, like `seq`
+//!
+//! ## Ordering
+//! Maps and sets are sorted, unless the `preserve-order` feature keeps them in the order they're
+//! read or built in. Either way they're equal to, and hash like, any other with the same entries.
+//!
+//! `preserve-order` isn't additive: it changes [`Map`] and [`Set`] from `BTreeMap` and `BTreeSet`
+//! to types with fewer methods and their own iterators, see `edn::ordered`.
 //!
 //! ## Differences from Clojure
 //! -  Escape characters are not escaped.
 
 use alloc::boxed::Box;
+#[cfg(not(feature = "preserve-order"))]
 use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::vec::Vec;
 use core::fmt;
@@ -22,12 +35,36 @@ use ordered_float::OrderedFloat;
 
 use crate::{error, parse};
 
+mod access;
+mod convert;
+mod edit;
+mod namespaces;
+#[cfg(feature = "preserve-order")]
+pub mod ordered;
+mod walk;
+mod zip;
+pub use namespaces::Namespaces;
+pub(crate) use namespaces::qualified_key;
+#[cfg(feature = "serde")]
+pub(crate) use namespaces::unique_keys;
+pub use walk::Visitor;
+pub use zip::Zipper;
+
+/// The map of an [`Edn::Map`], sorted by key.
+#[cfg(not(feature = "preserve-order"))]
+pub type Map<K, V> = BTreeMap<K, V>;
+/// The set of an [`Edn::Set`], sorted.
+#[cfg(not(feature = "preserve-order"))]
+pub type Set<T> = BTreeSet<T>;
+#[cfg(feature = "preserve-order")]
+pub use ordered::{Map, Set};
+
 #[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
 #[non_exhaustive]
 pub enum Edn<'e> {
   Vector(Vec<Self>),
-  Set(BTreeSet<Self>),
-  Map(BTreeMap<Self, Self>),
+  Set(Set<Self>),
+  Map(Map<Self, Self>),
   List(Vec<Self>),
   Key(&'e str),
   Symbol(&'e str),
@@ -76,7 +113,7 @@ pub(crate) fn validate_tag(tag: &str, tag_span: parse::Span) -> error::Result<()
       None => valid_symbol_part(tag),
     };
 
-  if valid { Ok(()) } else { Err(error::Error::from_position(error::Code::InvalidTag, tag_span.0)) }
+  if valid { Ok(()) } else { Err(error::Error::from_offset(error::Code::InvalidTag, tag_span.0)) }
 }
 
 impl<'e> TryFrom<parse::Node<'e>> for Edn<'e> {
@@ -112,21 +149,21 @@ impl<'e> TryFrom<parse::Node<'e>> for Edn<'e> {
         Edn::Vector(items.into_iter().map(TryInto::try_into).collect::<Result<_>>()?)
       }
       NodeKind::Set(items, _) => {
-        let mut set = BTreeSet::new();
+        let mut set = Set::new();
         for node in items {
           let position = node.span().1;
           if !set.insert(node.try_into()?) {
-            return Err(Error::from_position(Code::SetDuplicateKey, position));
+            return Err(Error::from_offset(Code::SetDuplicateKey, position));
           }
         }
         Edn::Set(set)
       }
       NodeKind::Map(entries, _) => {
-        let mut map = BTreeMap::new();
+        let mut map = Map::new();
         for (key, value) in entries {
           let position = value.span().1;
           if map.insert(key.try_into()?, value.try_into()?).is_some() {
-            return Err(Error::from_position(Code::HashMapDuplicateKey, position));
+            return Err(Error::from_offset(Code::HashMapDuplicateKey, position));
           }
         }
         Edn::Map(map)
@@ -141,7 +178,7 @@ impl<'e> TryFrom<parse::Node<'e>> for Edn<'e> {
       NodeKind::Tagged(tag, tag_span, node) => {
         validate_tag(tag, tag_span)?;
         if tag.starts_with(':') && !matches!(&node.kind, NodeKind::Map(..)) {
-          return Err(Error::from_position(Code::InvalidTag, tag_span.0));
+          return Err(Error::from_offset(Code::InvalidTag, tag_span.0));
         }
         Edn::Tagged(tag, Box::new((*node).try_into()?))
       }
@@ -161,6 +198,11 @@ impl<'e> TryFrom<parse::Node<'e>> for Edn<'e> {
 
 /// Reads one object from the &str.
 ///
+/// Namespaced maps aren't expanded, `#:thingy{:foo 1}` is read as a map tagged `:thingy`, which
+/// [`Edn::get`] still looks into with `:thingy/foo`. Use [`read_string_with`] to read it as
+/// `{:thingy/foo 1}`. `#::{}` and `#::alias{}` maps need the namespaces it's given, so here they're
+/// `Code::InvalidTag`.
+///
 /// # Errors
 ///
 /// See [`crate::error::Error`].
@@ -168,6 +210,37 @@ pub fn read_string(edn: &str) -> Result<Edn<'_>, error::Error> {
   Ok(parse::parse_as_edn(edn)?.0)
 }
 
+/// Reads one object from the &str like [`read_string`], with namespaced maps expanded.
+///
+/// `read_string` keeps `#:thingy{:foo 1}` as a tagged map, as its keys are borrowed from the
+/// source. Here it's read as `{:thingy/foo 1}`, with the keys that aren't in the source written
+/// into `buffer`. Keys with a namespace keep it, and `:_/foo` is read as `:foo`. `#::{}` and
+/// `#::alias{}` maps are read in the `namespaces` given.
+///
+/// ```
+/// use clojure_reader::edn::{self, Namespaces};
+///
+/// let namespaces = Namespaces::new().current("user").aliases(&[("str", "clojure.string")]);
+/// let mut buffer = String::new();
+/// let edn = edn::read_string_with("[#::{:a 1} #::str{:b 2 :_/c 3}]", &namespaces, &mut buffer);
+/// assert_eq!(edn.unwrap(), edn::read_string("[{:user/a 1} {:clojure.string/b 2 :c 3}]").unwrap());
+/// ```
+///
+/// # Errors
+///
+/// See [`crate::error::Error`]. `#::{}` maps without a current namespace, and `#::alias{}` maps
+/// with an alias that isn't in `namespaces`, are `Code::InvalidTag`. Keys that end up the same
+/// are `Code::HashMapDuplicateKey`, and set items `Code::SetDuplicateKey`, like in
+/// `#{#:a{:b 1} {:a/b 1}}`, at the second one.
+pub fn read_string_with<'e>(
+  edn: &'e str,
+  namespaces: &Namespaces<'_>,
+  buffer: &'e mut alloc::string::String,
+) -> Result<Edn<'e>, error::Error> {
+  let edn = parse::parse_as_namespaced_edn(edn, namespaces)?;
+  namespaces.expand(edn, buffer)
+}
+
 /// Reads the first object from the &str and the remaining unread &str.
 ///
 /// # Errors
@@ -189,7 +262,7 @@ pub fn read(edn: &str) -> Result<(Edn<'_>, &str), error::Error> {
   Ok((edn, remaining))
 }
 
-fn get_tag<'a>(tag: &'a str, key: &'a str) -> Option<&'a str> {
+pub(crate) fn get_tag<'a>(tag: &'a str, key: &str) -> Option<&'a str> {
   // Break out early if there's no namespaces
   if !key.contains('/') {
     return None;
@@ -203,7 +276,7 @@ fn get_tag<'a>(tag: &'a str, key: &'a str) -> Option<&'a str> {
   Some(tag)
 }
 
-fn check_key<'a>(tag: &'a str, key: &'a str) -> &'a str {
+pub(crate) fn check_key<'a>(tag: &str, key: &'a str) -> &'a str {
   // check if the Key starts with the saved Tag
   if key.starts_with(tag) {
     let (_, key) = key.rsplit_once(tag).expect("Tag must exist, because it starts with it.");
@@ -0,0 +1,159 @@
+//! Getting at what's in an [`Edn`] without matching on it.
+
+use alloc::vec::{self, Vec};
+use core::ops::Index;
+
+use super::{Edn, Map, Set};
+
+// What indexing gives back where there's nothing
+static NIL: Edn<'static> = Edn::Nil;
+
+impl<'e> Edn<'e> {
+  /// The string, if this is one.
+  pub const fn as_str(&self) -> Option<&'e str> {
+    if let Edn::Str(str) = self { Some(str) } else { None }
+  }
+
+  /// The keyword without its `:`, if this is one.
+  pub const fn as_keyword(&self) -> Option<&'e str> {
+    if let Edn::Key(key) = self { Some(key) } else { None }
+  }
+
+  /// The symbol, if this is one.
+  pub const fn as_symbol(&self) -> Option<&'e str> {
+    if let Edn::Symbol(symbol) = self { Some(symbol) } else { None }
+  }
+
+  /// The tag and the value it's on, if this is a tagged value.
+  pub fn as_tagged(&self) -> Option<(&'e str, &Self)> {
+    if let Edn::Tagged(tag, value) = self { Some((tag, value)) } else { None }
+  }
+
+  /// The integer, if this is one.
+  pub const fn as_i64(&self) -> Option<i64> {
+    if let Edn::Int(int) = self { Some(*int) } else { None }
+  }
+
+  /// The double, if this is one.
+  #[cfg(feature = "floats")]
+  pub const fn as_f64(&self) -> Option<f64> {
+    if let Edn::Double(double) = self { Some(double.0) } else { None }
+  }
+
+  /// The numerator and denominator, if this is a ratio.
+  pub const fn as_rational(&self) -> Option<(i64, i64)> {
+    if let Edn::Rational(rational) = self { Some(*rational) } else { None }
+  }
+
+  /// The boolean, if this is one.
+  pub const fn as_bool(&self) -> Option<bool> {
+    if let Edn::Bool(bool) = self { Some(*bool) } else { None }
+  }
+
+  /// The character, if this is one.
+  pub const fn as_char(&self) -> Option<char> {
+    if let Edn::Char(char) = self { Some(*char) } else { None }
+  }
+
+  /// The entries, if this is a map.
+  pub const fn as_map(&self) -> Option<&Map<Self, Self>> {
+    if let Edn::Map(map) = self { Some(map) } else { None }
+  }
+
+  /// The entries to edit in place, if this is a map.
+  pub const fn as_map_mut(&mut self) -> Option<&mut Map<Self, Self>> {
+    if let Edn::Map(map) = self { Some(map) } else { None }
+  }
+
+  /// The items, if this is a vector.
+  pub const fn as_vec(&self) -> Option<&Vec<Self>> {
+    if let Edn::Vector(vec) = self { Some(vec) } else { None }
+  }
+
+  /// The items to edit in place, if this is a vector.
+  pub const fn as_vec_mut(&mut self) -> Option<&mut Vec<Self>> {
+    if let Edn::Vector(vec) = self { Some(vec) } else { None }
+  }
+
+  /// The items, if this is a list.
+  pub const fn as_list(&self) -> Option<&Vec<Self>> {
+    if let Edn::List(list) = self { Some(list) } else { None }
+  }
+
+  /// The items to edit in place, if this is a list.
+  pub const fn as_list_mut(&mut self) -> Option<&mut Vec<Self>> {
+    if let Edn::List(list) = self { Some(list) } else { None }
+  }
+
+  /// The elements, if this is a set.
+  pub const fn as_set(&self) -> Option<&Set<Self>> {
+    if let Edn::Set(set) = self { Some(set) } else { None }
+  }
+
+  /// The elements to edit in place, if this is a set.
+  pub const fn as_set_mut(&mut self) -> Option<&mut Set<Self>> {
+    if let Edn::Set(set) = self { Some(set) } else { None }
+  }
+
+  /// Whether this is nil.
+  pub const fn is_nil(&self) -> bool {
+    matches!(self, Edn::Nil)
+  }
+
+  /// Whether this is a vector, list, set or map.
+  pub const fn is_collection(&self) -> bool {
+    matches!(self, Edn::Vector(_) | Edn::List(_) | Edn::Set(_) | Edn::Map(_))
+  }
+}
+
+/// Looks up a key like [`Edn::get`], giving nil where there's nothing.
+///
+/// ```
+/// use clojure_reader::edn::{self, Edn};
+///
+/// let edn = edn::read_string("{:foo [1 2 3]}").unwrap();
+/// assert_eq!(edn[&Edn::Key("foo")][2], Edn::Int(3));
+/// assert_eq!(edn[&Edn::Key("bar")][2], Edn::Nil);
+/// ```
+impl Index<&Self> for Edn<'_> {
+  type Output = Self;
+
+  fn index(&self, key: &Self) -> &Self {
+    self.get(key).unwrap_or(&NIL)
+  }
+}
+
+/// Looks up an item like [`Edn::nth`], giving nil where there's nothing.
+impl Index<usize> for Edn<'_> {
+  type Output = Self;
+
+  fn index(&self, index: usize) -> &Self {
+    self.nth(index).unwrap_or(&NIL)
+  }
+}
+
+/// Goes through the values in a collection, like Clojure's `seq`. Map entries come as
+/// `[key value]` vectors, and anything but a collection has nothing in it.
+///
+/// ```
+/// use clojure_reader::edn::{self, Edn};
+///
+/// let keys: Vec<_> = edn::read_string("{:a 1 :b 2}").unwrap().into_iter().map(|entry| entry[0].clone()).collect();
+/// assert_eq!(keys, [Edn::Key("a"), Edn::Key("b")]);
+/// ```
+impl IntoIterator for Edn<'_> {
+  type Item = Self;
+  type IntoIter = vec::IntoIter<Self>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    match self {
+      Edn::Vector(items) | Edn::List(items) => items,
+      Edn::Set(items) => items.into_iter().collect(),
+      Edn::Map(entries) => {
+        entries.into_iter().map(|entry| Edn::Vector(<[Self; 2]>::from(entry).into())).collect()
+      }
+      _ => Vec::new(),
+    }
+    .into_iter()
+  }
+}
@@ -0,0 +1,204 @@
+//! Conversions between [`Edn`] and Rust types.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+#[cfg(feature = "floats")]
+use ordered_float::OrderedFloat;
+
+use crate::error::{Code, Error, Result};
+
+use super::Edn;
+
+impl<'e> From<&'e str> for Edn<'e> {
+  fn from(str: &'e str) -> Self {
+    Edn::Str(str)
+  }
+}
+
+impl From<bool> for Edn<'_> {
+  fn from(bool: bool) -> Self {
+    Edn::Bool(bool)
+  }
+}
+
+impl From<char> for Edn<'_> {
+  fn from(char: char) -> Self {
+    Edn::Char(char)
+  }
+}
+
+macro_rules! from_int {
+  ($($int:ty),*) => {
+    $(
+      impl From<$int> for Edn<'_> {
+        fn from(int: $int) -> Self {
+          Edn::Int(i64::from(int))
+        }
+      }
+    )*
+  };
+}
+
+from_int!(i8, i16, i32, i64, u8, u16, u32);
+
+#[cfg(feature = "floats")]
+impl From<f64> for Edn<'_> {
+  fn from(double: f64) -> Self {
+    Edn::Double(OrderedFloat(double))
+  }
+}
+
+/// A vector
+impl<T: Into<Self>> From<Vec<T>> for Edn<'_> {
+  fn from(items: Vec<T>) -> Self {
+    Edn::Vector(items.into_iter().map(Into::into).collect())
+  }
+}
+
+impl<T: Into<Self>> From<BTreeSet<T>> for Edn<'_> {
+  fn from(items: BTreeSet<T>) -> Self {
+    Edn::Set(items.into_iter().map(Into::into).collect())
+  }
+}
+
+impl<K: Into<Self>, V: Into<Self>> From<BTreeMap<K, V>> for Edn<'_> {
+  fn from(entries: BTreeMap<K, V>) -> Self {
+    Edn::Map(entries.into_iter().map(|(key, value)| (key.into(), value.into())).collect())
+  }
+}
+
+#[cfg(feature = "std")]
+impl<K: Into<Self>, V: Into<Self>, S> From<HashMap<K, V, S>> for Edn<'_> {
+  fn from(entries: HashMap<K, V, S>) -> Self {
+    Edn::Map(entries.into_iter().map(|(key, value)| (key.into(), value.into())).collect())
+  }
+}
+
+/// Nil for `None`
+impl<T: Into<Self>> From<Option<T>> for Edn<'_> {
+  fn from(option: Option<T>) -> Self {
+    option.map_or(Edn::Nil, Into::into)
+  }
+}
+
+// For values that aren't the type they're converted to
+const fn unexpected<T>() -> Result<T> {
+  Err(Error::new(Code::UnexpectedType))
+}
+
+impl<'e> TryFrom<Edn<'e>> for &'e str {
+  type Error = Error;
+
+  fn try_from(edn: Edn<'e>) -> Result<Self> {
+    edn.as_str().map_or_else(unexpected, Ok)
+  }
+}
+
+impl TryFrom<Edn<'_>> for bool {
+  type Error = Error;
+
+  fn try_from(edn: Edn<'_>) -> Result<Self> {
+    edn.as_bool().map_or_else(unexpected, Ok)
+  }
+}
+
+impl TryFrom<Edn<'_>> for char {
+  type Error = Error;
+
+  fn try_from(edn: Edn<'_>) -> Result<Self> {
+    edn.as_char().map_or_else(unexpected, Ok)
+  }
+}
+
+impl TryFrom<Edn<'_>> for i64 {
+  type Error = Error;
+
+  fn try_from(edn: Edn<'_>) -> Result<Self> {
+    edn.as_i64().map_or_else(unexpected, Ok)
+  }
+}
+
+#[cfg(feature = "floats")]
+impl TryFrom<Edn<'_>> for f64 {
+  type Error = Error;
+
+  fn try_from(edn: Edn<'_>) -> Result<Self> {
+    edn.as_f64().map_or_else(unexpected, Ok)
+  }
+}
+
+/// From a vector or a list
+impl<'e, T: TryFrom<Edn<'e>, Error = Error>> TryFrom<Edn<'e>> for Vec<T> {
+  type Error = Error;
+
+  fn try_from(edn: Edn<'e>) -> Result<Self> {
+    match edn {
+      Edn::Vector(items) | Edn::List(items) => items.into_iter().map(T::try_from).collect(),
+      _ => unexpected(),
+    }
+  }
+}
+
+impl<'e, T: TryFrom<Edn<'e>, Error = Error> + Ord> TryFrom<Edn<'e>> for BTreeSet<T> {
+  type Error = Error;
+
+  fn try_from(edn: Edn<'e>) -> Result<Self> {
+    match edn {
+      Edn::Set(items) => items.into_iter().map(T::try_from).collect(),
+      _ => unexpected(),
+    }
+  }
+}
+
+impl<'e, K, V> TryFrom<Edn<'e>> for BTreeMap<K, V>
+where
+  K: TryFrom<Edn<'e>, Error = Error> + Ord,
+  V: TryFrom<Edn<'e>, Error = Error>,
+{
+  type Error = Error;
+
+  fn try_from(edn: Edn<'e>) -> Result<Self> {
+    match edn {
+      Edn::Map(entries) => {
+        entries.into_iter().map(|(key, value)| Ok((key.try_into()?, value.try_into()?))).collect()
+      }
+      _ => unexpected(),
+    }
+  }
+}
+
+#[cfg(feature = "std")]
+impl<'e, K, V, S> TryFrom<Edn<'e>> for HashMap<K, V, S>
+where
+  K: TryFrom<Edn<'e>, Error = Error> + Eq + Hash,
+  V: TryFrom<Edn<'e>, Error = Error>,
+  S: BuildHasher + Default,
+{
+  type Error = Error;
+
+  fn try_from(edn: Edn<'e>) -> Result<Self> {
+    match edn {
+      Edn::Map(entries) => {
+        entries.into_iter().map(|(key, value)| Ok((key.try_into()?, value.try_into()?))).collect()
+      }
+      _ => unexpected(),
+    }
+  }
+}
+
+/// `None` from nil
+impl<'e, T: TryFrom<Edn<'e>, Error = Error>> TryFrom<Edn<'e>> for Option<T> {
+  type Error = Error;
+
+  fn try_from(edn: Edn<'e>) -> Result<Self> {
+    match edn {
+      Edn::Nil => Ok(None),
+      edn => edn.try_into().map(Some),
+    }
+  }
+}
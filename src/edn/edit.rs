@@ -0,0 +1,227 @@
+//! Looking up and editing values deep inside an [`Edn`], like Clojure's `get-in` and `assoc-in`.
+
+use alloc::vec;
+use core::mem;
+
+use crate::error::{Code, Error, Result};
+
+use super::{Edn, Map, check_key, get_tag};
+
+impl Edn<'_> {
+  /// Looks up a value by the keys leading to it, like Clojure's `get-in`: maps by key, vectors
+  /// by index and sets by element.
+  ///
+  /// ```
+  /// use clojure_reader::edn::{self, Edn};
+  ///
+  /// let edn = edn::read_string("{:users [{:name \"ada\"}]}").unwrap();
+  /// let path = [Edn::Key("users"), Edn::Int(0), Edn::Key("name")];
+  /// assert_eq!(edn.get_in(&path), Some(&Edn::Str("ada")));
+  /// ```
+  pub fn get_in(&self, path: &[Self]) -> Option<&Self> {
+    path.iter().try_fold(self, |edn, key| match (edn, key) {
+      (Edn::Vector(items), key) => items.get(index(key)?),
+      (Edn::Set(items), key) => items.get(key),
+      (edn, key) => edn.get(key),
+    })
+  }
+
+  /// Looks up a value by the keys leading to it like [`Edn::get_in`], to edit in place. Set
+  /// elements can't be edited, so they aren't found.
+  pub fn get_in_mut(&mut self, path: &[Self]) -> Option<&mut Self> {
+    path.iter().try_fold(self, |edn, key| match edn {
+      Edn::Vector(items) => items.get_mut(index(key)?),
+      Edn::Map(entries) => entries.get_mut(key),
+      Edn::Tagged(tag, value) => value.get_in_mut(&[namespaced_key(tag, key)?]),
+      _ => None,
+    })
+  }
+
+  /// Sets `key` to `value`, like Clojure's `assoc`. Vectors take an index up to their length,
+  /// which appends, and nil turns into a map.
+  ///
+  /// # Errors
+  ///
+  /// [`Code::NotAssociative`] for values other than maps, vectors and nil, and
+  /// [`Code::InvalidIndex`] for an index out of a vector. In a `#:namespace{}` map that
+  /// [`read_string`](super::read_string) kept tagged, `:key` stands for `:namespace/key`, so a
+  /// keyword without a namespace is [`Code::NamespacedMapKey`]. Read it with
+  /// [`read_string_with`](super::read_string_with) for a map that takes any key.
+  pub fn assoc(&mut self, key: &Self, value: Self) -> Result<()> {
+    *self.entry(key)? = value;
+    Ok(())
+  }
+
+  /// Sets the value at the end of `path`, like Clojure's `assoc-in`. Maps are made for the keys
+  /// that aren't there, and an empty path replaces the whole value.
+  ///
+  /// ```
+  /// use clojure_reader::edn::{self, Edn};
+  ///
+  /// let mut edn = edn::read_string("{:deps {}}").unwrap();
+  /// let path = [Edn::Key("aliases"), Edn::Key("test"), Edn::Key("main-opts")];
+  /// edn.assoc_in(&path, Edn::Vector(vec![Edn::Str("-m")])).unwrap();
+  /// assert_eq!(edn, edn::read_string("{:deps {} :aliases {:test {:main-opts [\"-m\"]}}}").unwrap());
+  /// ```
+  ///
+  /// # Errors
+  ///
+  /// See [`Edn::assoc`].
+  pub fn assoc_in(&mut self, path: &[Self], value: Self) -> Result<()> {
+    self.update_in(path, |_| value)
+  }
+
+  /// Replaces the value at the end of `path` with what `f` makes of it, like Clojure's
+  /// `update-in`. `f` gets nil where there's nothing, and maps are made for the keys that aren't
+  /// there.
+  ///
+  /// ```
+  /// use clojure_reader::edn::{self, Edn};
+  ///
+  /// let mut edn = edn::read_string("{:counts [1 2]}").unwrap();
+  /// let inc = |edn| match edn {
+  ///   Edn::Int(i) => Edn::Int(i + 1),
+  ///   _ => Edn::Int(1),
+  /// };
+  /// edn.update_in(&[Edn::Key("counts"), Edn::Int(1)], inc).unwrap();
+  /// edn.update_in(&[Edn::Key("misses")], inc).unwrap();
+  /// assert_eq!(edn.to_string(), "{:counts [1 3], :misses 1}");
+  /// ```
+  ///
+  /// # Errors
+  ///
+  /// See [`Edn::assoc`].
+  pub fn update_in(&mut self, path: &[Self], f: impl FnOnce(Self) -> Self) -> Result<()> {
+    let edn = path.iter().try_fold(self, |edn, key| edn.entry(key))?;
+    *edn = f(mem::replace(edn, Edn::Nil));
+    Ok(())
+  }
+
+  /// Removes `key` from a map and gives back its value, like Clojure's `dissoc`. There's nothing
+  /// to remove from nil.
+  ///
+  /// # Errors
+  ///
+  /// [`Code::NotAssociative`] for values other than maps and nil.
+  pub fn dissoc(&mut self, key: &Self) -> Result<Option<Self>> {
+    match self {
+      Edn::Map(entries) => Ok(entries.remove(key)),
+      Edn::Nil => Ok(None),
+      Edn::Tagged(tag, value) => {
+        namespaced_key(tag, key).map_or(Ok(None), |key| value.dissoc(&key))
+      }
+      _ => Err(Error::new(Code::NotAssociative)),
+    }
+  }
+
+  /// Adds `value` where it goes in a collection, like Clojure's `conj`: at the end of vectors,
+  /// the start of lists and in sets. Maps take `[key value]` vectors and other maps, and nil
+  /// turns into a list.
+  ///
+  /// # Errors
+  ///
+  /// [`Code::NotCollection`] for values other than collections and nil, and
+  /// [`Code::InvalidMapEntry`] for anything but a `[key value]` vector, a map or nil added to a
+  /// map.
+  pub fn conj(&mut self, value: Self) -> Result<()> {
+    match self {
+      Edn::Vector(items) => items.push(value),
+      Edn::List(items) => items.insert(0, value),
+      Edn::Set(items) => {
+        items.insert(value);
+      }
+      Edn::Map(entries) => match value {
+        Edn::Vector(entry) => {
+          let [key, value] =
+            <[Self; 2]>::try_from(entry).map_err(|_| Error::new(Code::InvalidMapEntry))?;
+          entries.insert(key, value);
+        }
+        Edn::Map(other) => entries.extend(other),
+        Edn::Nil => {}
+        _ => return Err(Error::new(Code::InvalidMapEntry)),
+      },
+      Edn::Nil => *self = Edn::List(vec![value]),
+      _ => return Err(Error::new(Code::NotCollection)),
+    }
+    Ok(())
+  }
+
+  /// Adds the entries of `other` to this map, replacing the values of keys they share, like
+  /// Clojure's `merge`. Nil merged into is an empty map, nil merged is nothing.
+  ///
+  /// # Errors
+  ///
+  /// See [`Edn::conj`].
+  pub fn merge(&mut self, other: Self) -> Result<()> {
+    if other == Edn::Nil {
+      return Ok(());
+    }
+    if *self == Edn::Nil {
+      *self = Edn::Map(Map::new());
+    }
+    self.conj(other)
+  }
+
+  /// Adds everything in the collection `from` with [`Edn::conj`], like Clojure's `into`, and gives
+  /// back the collection. Map entries are added as `[key value]` vectors.
+  ///
+  /// ```
+  /// use clojure_reader::edn::{self, Edn};
+  ///
+  /// let edn = Edn::Nil.into_coll(edn::read_string("[1 2 3]").unwrap()).unwrap();
+  /// assert_eq!(edn.to_string(), "(3 2 1)");
+  /// ```
+  ///
+  /// # Errors
+  ///
+  /// [`Code::NotCollection`] if `from` isn't a collection or nil, or see [`Edn::conj`].
+  pub fn into_coll(mut self, from: Self) -> Result<Self> {
+    if !from.is_collection() && !from.is_nil() {
+      return Err(Error::new(Code::NotCollection));
+    }
+    from.into_iter().try_for_each(|item| self.conj(item))?;
+    Ok(self)
+  }
+
+  // The value at `key`, put in as nil if it isn't there
+  fn entry(&mut self, key: &Self) -> Result<&mut Self> {
+    if *self == Edn::Nil {
+      *self = Edn::Map(Map::new());
+    }
+    match self {
+      Edn::Map(entries) => Ok(entries.entry(key.clone()).or_insert(Edn::Nil)),
+      Edn::Vector(items) => {
+        let index = index(key).filter(|index| *index <= items.len());
+        let index = index.ok_or_else(|| Error::new(Code::InvalidIndex))?;
+        if index == items.len() {
+          items.push(Edn::Nil);
+        }
+        items.get_mut(index).ok_or_else(|| Error::new(Code::InvalidIndex))
+      }
+      Edn::Tagged(tag, value) => {
+        let Some(key) = namespaced_key(tag, key) else {
+          // A keyword without a namespace would have to be written `:_/key`
+          let code =
+            if tag.starts_with(':') { Code::NamespacedMapKey } else { Code::NotAssociative };
+          return Err(Error::new(code));
+        };
+        value.entry(&key)
+      }
+      _ => Err(Error::new(Code::NotAssociative)),
+    }
+  }
+}
+
+fn index(key: &Edn<'_>) -> Option<usize> {
+  let Edn::Int(index) = key else { return None };
+  usize::try_from(*index).ok()
+}
+
+// What a key is called inside a tagged map, like `Edn::get` looks it up: without the namespace
+// of a namespaced map
+fn namespaced_key<'k>(tag: &str, key: &Edn<'k>) -> Option<Edn<'k>> {
+  match key {
+    Edn::Key(key) => Some(Edn::Key(check_key(get_tag(tag, key)?, key))),
+    key => Some(key.clone()),
+  }
+}
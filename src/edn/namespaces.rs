@@ -0,0 +1,310 @@
+//! Expanding `#:namespace{}` maps into maps keyed by qualified keywords and symbols.
+
+use alloc::boxed::Box;
+#[cfg(feature = "serde")]
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::{Edn, Map, Set};
+use crate::error::{Code, Error};
+
+/// The namespaces `#::{}` and `#::alias{}` maps are read in, see [`read_string_with`].
+///
+/// [`read_string_with`]: super::read_string_with
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Namespaces<'n> {
+  current: Option<&'n str>,
+  aliases: &'n [(&'n str, &'n str)],
+}
+
+impl<'n> Namespaces<'n> {
+  /// No namespaces, so only `#:namespace{}` maps can be read.
+  pub const fn new() -> Self {
+    Self { current: None, aliases: &[] }
+  }
+
+  /// Sets the namespace of `#::{}` maps, the one being read in.
+  #[must_use]
+  pub const fn current(mut self, namespace: &'n str) -> Self {
+    self.current = Some(namespace);
+    self
+  }
+
+  /// Sets the namespaces of `#::alias{}` maps, as pairs of alias and namespace.
+  #[must_use]
+  pub const fn aliases(mut self, aliases: &'n [(&'n str, &'n str)]) -> Self {
+    self.aliases = aliases;
+    self
+  }
+
+  /// The namespace of a `#:namespace`, `#::` or `#::alias` tag.
+  pub(crate) fn resolve<'t>(&self, tag: &'t str) -> Option<&'t str>
+  where
+    'n: 't,
+  {
+    match tag.strip_prefix("::") {
+      Some("") => self.current,
+      Some(alias) => self.aliases.iter().find(|(name, _)| *name == alias).map(|(_, ns)| *ns),
+      None => tag.strip_prefix(':'),
+    }
+  }
+
+  /// Where the first of `items`, given with where they start, that's the same as one before it
+  /// once namespaced maps are expanded starts, like the second key of `{#:a{:b 1} 1, {:a/b 1} 2}`.
+  pub(crate) fn first_duplicate(&self, items: &[(&Edn<'_>, usize)]) -> Option<usize> {
+    // Items without namespaced maps in them are only the same when they're equal
+    if !items.iter().any(|(item, _)| self.has_namespaced(item)) {
+      return None;
+    }
+    items.iter().enumerate().find_map(|(index, &(item, start))| {
+      let duplicate = items.iter().take(index).any(|(other, _)| self.same(other, item));
+      duplicate.then_some(start)
+    })
+  }
+
+  // Whether there's a namespaced map in `edn`
+  fn has_namespaced(&self, edn: &Edn<'_>) -> bool {
+    match edn {
+      Edn::Vector(items) | Edn::List(items) => items.iter().any(|item| self.has_namespaced(item)),
+      Edn::Set(set) => set.iter().any(|item| self.has_namespaced(item)),
+      Edn::Map(map) => {
+        map.iter().any(|(key, value)| self.has_namespaced(key) || self.has_namespaced(value))
+      }
+      Edn::Tagged(tag, edn) => {
+        (self.resolve(tag).is_some() && matches!(**edn, Edn::Map(_))) || self.has_namespaced(edn)
+      }
+      _ => false,
+    }
+  }
+
+  // The entries of a namespaced map, with its namespace, or of a map
+  fn entries<'a, 'e>(
+    &'a self,
+    edn: &'a Edn<'e>,
+  ) -> Option<(Option<&'a str>, &'a Map<Edn<'e>, Edn<'e>>)> {
+    match edn {
+      Edn::Map(map) => Some((None, map)),
+      Edn::Tagged(tag, edn) => match (self.resolve(tag), &**edn) {
+        (Some(namespace), Edn::Map(map)) => Some((Some(namespace), map)),
+        _ => None,
+      },
+      _ => None,
+    }
+  }
+
+  // Whether `a` and `b` are equal once namespaced maps are expanded
+  fn same<'e>(&self, a: &Edn<'e>, b: &Edn<'e>) -> bool {
+    if let (Some((a_namespace, a)), Some((b_namespace, b))) = (self.entries(a), self.entries(b)) {
+      return a.len() == b.len()
+        && a.iter().all(|(a_key, a_value)| {
+          b.iter().any(|(b_key, b_value)| {
+            let same_key = match (map_key(a_namespace, a_key), map_key(b_namespace, b_key)) {
+              (Some(a_key), Some(b_key)) => a_key == b_key,
+              (None, None) => self.same(a_key, b_key),
+              _ => false,
+            };
+            same_key && self.same(a_value, b_value)
+          })
+        });
+    }
+    match (a, b) {
+      (Edn::Vector(a), Edn::Vector(b)) | (Edn::List(a), Edn::List(b)) => {
+        a.len() == b.len() && a.iter().zip(b).all(|(a, b)| self.same(a, b))
+      }
+      (Edn::Set(a), Edn::Set(b)) => {
+        a.len() == b.len() && a.iter().all(|a| b.iter().any(|b| self.same(a, b)))
+      }
+      (Edn::Tagged(a_tag, a), Edn::Tagged(b_tag, b)) => a_tag == b_tag && self.same(a, b),
+      _ => a == b,
+    }
+  }
+
+  /// Turns the namespaced maps in `edn` into maps with qualified keys, which are written into
+  /// `buffer`.
+  ///
+  /// Maps and sets that now have equal keys or items are [`Code::HashMapDuplicateKey`] and
+  /// [`Code::SetDuplicateKey`], like `{#:a{:b 1} 1, {:a/b 1} 2}`. Reading checks for those where
+  /// it still knows their position, see [`Self::first_duplicate`], so here they're only a
+  /// safeguard without one.
+  pub(crate) fn expand<'e>(&self, edn: Edn<'e>, buffer: &'e mut String) -> Result<Edn<'e>, Error> {
+    let start = buffer.len();
+    let mut ends = Vec::new();
+    self.write_keys(&edn, buffer, &mut ends);
+
+    let buffer: &'e String = buffer;
+    let mut keys = ends.into_iter().scan(start, |start, end| {
+      let key = &buffer[*start..end];
+      *start = end;
+      Some(key)
+    });
+    self.qualify(edn, &mut keys)
+  }
+
+  // Writes the keys namespaced maps put in their namespace, in the order `qualify` takes them
+  fn write_keys(&self, edn: &Edn<'_>, buffer: &mut String, ends: &mut Vec<usize>) {
+    match edn {
+      Edn::Vector(items) | Edn::List(items) => {
+        for item in items {
+          self.write_keys(item, buffer, ends);
+        }
+      }
+      Edn::Set(set) => {
+        for item in set {
+          self.write_keys(item, buffer, ends);
+        }
+      }
+      Edn::Map(map) => {
+        for (key, value) in map {
+          self.write_keys(key, buffer, ends);
+          self.write_keys(value, buffer, ends);
+        }
+      }
+      Edn::Tagged(tag, edn) => match (self.resolve(tag), &**edn) {
+        (Some(namespace), Edn::Map(map)) => {
+          for (key, value) in map {
+            match key {
+              Edn::Key(name) | Edn::Symbol(name) => {
+                if let MapKey::Qualified(name) = MapKey::of(name) {
+                  *buffer += namespace;
+                  *buffer += "/";
+                  *buffer += name;
+                  ends.push(buffer.len());
+                }
+              }
+              key => self.write_keys(key, buffer, ends),
+            }
+            self.write_keys(value, buffer, ends);
+          }
+        }
+        (_, edn) => self.write_keys(edn, buffer, ends),
+      },
+      _ => {}
+    }
+  }
+
+  fn qualify<'e>(
+    &self,
+    edn: Edn<'e>,
+    keys: &mut impl Iterator<Item = &'e str>,
+  ) -> Result<Edn<'e>, Error> {
+    Ok(match edn {
+      Edn::Vector(items) => Edn::Vector(
+        items.into_iter().map(|item| self.qualify(item, keys)).collect::<Result<_, _>>()?,
+      ),
+      Edn::List(items) => {
+        Edn::List(items.into_iter().map(|item| self.qualify(item, keys)).collect::<Result<_, _>>()?)
+      }
+      Edn::Set(items) => {
+        let mut set = Set::new();
+        for item in items {
+          if !set.insert(self.qualify(item, keys)?) {
+            return Err(Error::new(Code::SetDuplicateKey));
+          }
+        }
+        Edn::Set(set)
+      }
+      Edn::Map(entries) => {
+        let mut map = Map::new();
+        for (key, value) in entries {
+          let key = self.qualify(key, keys)?;
+          if map.insert(key, self.qualify(value, keys)?).is_some() {
+            return Err(Error::new(Code::HashMapDuplicateKey));
+          }
+        }
+        Edn::Map(map)
+      }
+      Edn::Tagged(tag, edn) => match (self.resolve(tag), *edn) {
+        (Some(_), Edn::Map(entries)) => {
+          let mut map = Map::new();
+          for (key, value) in entries {
+            let key = match key {
+              Edn::Key(name) => Edn::Key(MapKey::of(name).name(keys)),
+              Edn::Symbol(name) => Edn::Symbol(MapKey::of(name).name(keys)),
+              key => self.qualify(key, keys)?,
+            };
+            if map.insert(key, self.qualify(value, keys)?).is_some() {
+              return Err(Error::new(Code::HashMapDuplicateKey));
+            }
+          }
+          Edn::Map(map)
+        }
+        (_, edn) => Edn::Tagged(tag, Box::new(self.qualify(edn, keys)?)),
+      },
+      edn => edn,
+    })
+  }
+}
+
+/// What a keyword or symbol keying a `#:namespace{}` map stands for.
+enum MapKey<'a> {
+  /// Itself when it has a namespace, or its name when the namespace is `_`
+  Kept(&'a str),
+  /// Its name in the namespace of the map, `/` too, which becomes `namespace//` like in Clojure
+  Qualified(&'a str),
+}
+
+impl<'a> MapKey<'a> {
+  fn of(key: &'a str) -> Self {
+    match key.split_once('/') {
+      Some(("_", name)) => MapKey::Kept(name),
+      Some((namespace, _)) if !namespace.is_empty() => MapKey::Kept(key),
+      _ => MapKey::Qualified(key),
+    }
+  }
+
+  // The name it's read as, taking the next of the keys written by `write_keys` if it's qualified
+  fn name(self, keys: &mut impl Iterator<Item = &'a str>) -> &'a str {
+    match self {
+      MapKey::Kept(name) => name,
+      // Infallible: `write_keys` wrote one for every qualified key
+      MapKey::Qualified(name) => keys.next().unwrap_or(name),
+    }
+  }
+
+  // Its namespace and name
+  fn parts(&self, namespace: &'a str) -> (Option<&'a str>, &'a str) {
+    match *self {
+      MapKey::Kept(key) => match key.split_once('/') {
+        Some((namespace, name)) if !namespace.is_empty() => (Some(namespace), name),
+        _ => (None, key),
+      },
+      MapKey::Qualified(name) => (Some(namespace), name),
+    }
+  }
+}
+
+/// Whether no two keys of a map in `namespace` stand for the same key.
+#[cfg(feature = "serde")]
+pub fn unique_keys(namespace: &str, map: &Map<Edn<'_>, Edn<'_>>) -> bool {
+  let mut seen = BTreeSet::new();
+  map.keys().all(|key| qualified_key(namespace, key).is_none_or(|key| seen.insert(key)))
+}
+
+/// Whether a keyword or symbol keying a map in `namespace` is a keyword, and its namespace and
+/// name, which are the same for keys that stand for the same one.
+pub fn qualified_key<'a>(
+  namespace: &'a str,
+  key: &Edn<'a>,
+) -> Option<(bool, (Option<&'a str>, &'a str))> {
+  match *key {
+    Edn::Key(name) => Some((true, MapKey::of(name).parts(namespace))),
+    Edn::Symbol(name) => Some((false, MapKey::of(name).parts(namespace))),
+    _ => None,
+  }
+}
+
+// A key of a map like `qualified_key` has it, for a map in `namespace` or, without one, any map
+fn map_key<'a>(
+  namespace: Option<&'a str>,
+  key: &Edn<'a>,
+) -> Option<(bool, (Option<&'a str>, &'a str))> {
+  namespace.map_or_else(
+    || match *key {
+      Edn::Key(name) => Some((true, MapKey::Kept(name).parts(""))),
+      Edn::Symbol(name) => Some((false, MapKey::Kept(name).parts(""))),
+      _ => None,
+    },
+    |namespace| qualified_key(namespace, key),
+  )
+}
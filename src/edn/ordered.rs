@@ -0,0 +1,585 @@
+//! Maps and sets that keep their entries in the order they were inserted, for `preserve-order`.
+//!
+//! They compare and hash like the sorted ones they replace, by what's in them and not by its
+//! order, so `{:a 1 :b 2}` is still `{:b 2 :a 1}`. That makes [`Ord`] and [`Hash`] slower than
+//! the sorted ones': each call collects the entries into a `Vec` and sorts it.
+//!
+//! # Not an additive feature
+//!
+//! Turning `preserve-order` on changes the types of [`Edn::Map`](super::Edn::Map) and
+//! [`Edn::Set`](super::Edn::Set) from [`BTreeMap`](alloc::collections::BTreeMap) and
+//! [`BTreeSet`](alloc::collections::BTreeSet) to the [`Map`] and [`Set`] here. They have the
+//! methods of those that [`Edn`](super::Edn) needs, but not all of them, and their iterators and
+//! [`Entry`] are their own types, so code written against the sorted ones may not build with the
+//! feature on. Every crate in a build gets it once any of them turns it on.
+
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::FusedIterator;
+
+use indexmap::{IndexMap, IndexSet};
+
+#[cfg(feature = "std")]
+type State = std::hash::RandomState;
+#[cfg(not(feature = "std"))]
+type State = core::hash::BuildHasherDefault<Fnv>;
+
+// FNV-1a, for when there's no std to make random hashers
+#[cfg(not(feature = "std"))]
+struct Fnv(u64);
+
+#[cfg(not(feature = "std"))]
+impl Default for Fnv {
+  fn default() -> Self {
+    Self(0xcbf2_9ce4_8422_2325)
+  }
+}
+
+#[cfg(not(feature = "std"))]
+impl Hasher for Fnv {
+  fn finish(&self) -> u64 {
+    self.0
+  }
+
+  fn write(&mut self, bytes: &[u8]) {
+    for byte in bytes {
+      self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+    }
+  }
+}
+
+// Wraps an iterator of indexmap's, so that its types don't show up in this crate's
+macro_rules! iterator {
+  ($(#[$doc:meta] $name:ident<$($param:tt),*>($inner:ty) -> $item:ty;)*) => {
+    $(
+      #[$doc]
+      pub struct $name<$($param),*>($inner);
+
+      impl<$($param),*> Iterator for $name<$($param),*> {
+        type Item = $item;
+
+        fn next(&mut self) -> Option<Self::Item> {
+          self.0.next()
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+          self.0.size_hint()
+        }
+      }
+
+      impl<$($param),*> DoubleEndedIterator for $name<$($param),*> {
+        fn next_back(&mut self) -> Option<Self::Item> {
+          self.0.next_back()
+        }
+      }
+
+      impl<$($param),*> ExactSizeIterator for $name<$($param),*> {}
+
+      impl<$($param),*> FusedIterator for $name<$($param),*> {}
+    )*
+  };
+}
+
+iterator! {
+  /// The entries of a [`Map`], in order.
+  Iter<'a, K, V>(indexmap::map::Iter<'a, K, V>) -> (&'a K, &'a V);
+  /// The entries of a [`Map`] in order, with mutable values.
+  IterMut<'a, K, V>(indexmap::map::IterMut<'a, K, V>) -> (&'a K, &'a mut V);
+  /// The entries of a [`Map`] it's made from, in order.
+  IntoIter<K, V>(indexmap::map::IntoIter<K, V>) -> (K, V);
+  /// The values of a [`Set`], in order.
+  SetIter<'a, T>(indexmap::set::Iter<'a, T>) -> &'a T;
+  /// The values of a [`Set`] it's made from, in order.
+  SetIntoIter<T>(indexmap::set::IntoIter<T>) -> T;
+}
+
+/// A map in insertion order, with the methods of a [`BTreeMap`](alloc::collections::BTreeMap)
+/// that [`Edn`](super::Edn) needs.
+#[derive(Clone)]
+pub struct Map<K, V>(IndexMap<K, V, State>);
+
+impl<K, V> Map<K, V> {
+  /// An empty map.
+  pub fn new() -> Self {
+    Self(IndexMap::with_hasher(State::default()))
+  }
+
+  /// The number of entries.
+  pub fn len(&self) -> usize {
+    self.0.len()
+  }
+
+  /// Whether there are no entries.
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  /// The entries, in order.
+  pub fn iter(&self) -> Iter<'_, K, V> {
+    Iter(self.0.iter())
+  }
+
+  /// The entries in order, with mutable values.
+  pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+    IterMut(self.0.iter_mut())
+  }
+
+  /// The keys, in order.
+  pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> + ExactSizeIterator + FusedIterator {
+    self.0.keys()
+  }
+
+  /// The values, in the order of their keys.
+  pub fn values(&self) -> impl DoubleEndedIterator<Item = &V> + ExactSizeIterator + FusedIterator {
+    self.0.values()
+  }
+
+  /// The values, mutable, in the order of their keys.
+  pub fn values_mut(
+    &mut self,
+  ) -> impl DoubleEndedIterator<Item = &mut V> + ExactSizeIterator + FusedIterator {
+    self.0.values_mut()
+  }
+
+  /// The keys of the map, in order.
+  pub fn into_keys(self) -> impl DoubleEndedIterator<Item = K> + ExactSizeIterator + FusedIterator {
+    self.0.into_keys()
+  }
+
+  /// The values of the map, in the order of their keys.
+  pub fn into_values(
+    self,
+  ) -> impl DoubleEndedIterator<Item = V> + ExactSizeIterator + FusedIterator {
+    self.0.into_values()
+  }
+
+  /// The first entry inserted that's still there.
+  pub fn first_key_value(&self) -> Option<(&K, &V)> {
+    self.0.first()
+  }
+
+  /// The last entry inserted.
+  pub fn last_key_value(&self) -> Option<(&K, &V)> {
+    self.0.last()
+  }
+
+  /// Removes the first entry, moving the rest down, which takes as long as there are entries.
+  pub fn pop_first(&mut self) -> Option<(K, V)> {
+    self.0.shift_remove_index(0)
+  }
+
+  /// Removes the last entry.
+  pub fn pop_last(&mut self) -> Option<(K, V)> {
+    self.0.pop()
+  }
+
+  /// Removes every entry.
+  pub fn clear(&mut self) {
+    self.0.clear();
+  }
+
+  /// Keeps only the entries `keep` returns true for, in their order.
+  pub fn retain(&mut self, keep: impl FnMut(&K, &mut V) -> bool) {
+    self.0.retain(keep);
+  }
+}
+
+impl<K: Hash + Eq, V> Map<K, V> {
+  /// Inserts `value` at `key`, which keeps its place if it's already in the map.
+  pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+    self.0.insert(key, value)
+  }
+
+  /// The value at `key`.
+  pub fn get<Q>(&self, key: &Q) -> Option<&V>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+  {
+    self.0.get(key)
+  }
+
+  /// The key equal to `key`, and its value.
+  pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+  {
+    self.0.get_key_value(key)
+  }
+
+  /// The value at `key`, mutable.
+  pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+  {
+    self.0.get_mut(key)
+  }
+
+  /// Whether there's a value at `key`.
+  pub fn contains_key<Q>(&self, key: &Q) -> bool
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+  {
+    self.0.contains_key(key)
+  }
+
+  /// Removes `key`, keeping the order of the rest.
+  pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+  {
+    self.0.shift_remove(key)
+  }
+
+  /// Removes `key`, keeping the order of the rest.
+  pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+  where
+    K: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+  {
+    self.0.shift_remove_entry(key)
+  }
+
+  /// The entry at `key`, to change or insert in place. New keys go at the end.
+  pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+    Entry(self.0.entry(key))
+  }
+
+  /// Moves the entries of `other` to the end of this map, replacing the values of keys in both.
+  pub fn append(&mut self, other: &mut Self) {
+    self.0.extend(other.0.drain(..));
+  }
+
+  // The entries sorted by key, to compare and hash maps without their order
+  fn sorted(&self) -> Vec<(&K, &V)>
+  where
+    K: Ord,
+  {
+    let mut entries: Vec<_> = self.0.iter().collect();
+    entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+    entries
+  }
+}
+
+/// The entry of a [`Map`] at a key, from [`Map::entry`].
+pub struct Entry<'a, K, V>(indexmap::map::Entry<'a, K, V>);
+
+impl<'a, K, V> Entry<'a, K, V> {
+  /// The key it's at.
+  pub fn key(&self) -> &K {
+    self.0.key()
+  }
+
+  /// Calls `f` with the value, if there is one.
+  #[must_use]
+  pub fn and_modify(self, f: impl FnOnce(&mut V)) -> Self {
+    Self(self.0.and_modify(f))
+  }
+
+  /// The value, after inserting `default` if there wasn't one.
+  pub fn or_insert(self, default: V) -> &'a mut V {
+    self.0.or_insert(default)
+  }
+
+  /// The value, after inserting what `default` returns if there wasn't one.
+  pub fn or_insert_with(self, default: impl FnOnce() -> V) -> &'a mut V {
+    self.0.or_insert_with(default)
+  }
+
+  /// The value, after inserting [`V::default`](Default::default) if there wasn't one.
+  pub fn or_default(self) -> &'a mut V
+  where
+    V: Default,
+  {
+    self.0.or_default()
+  }
+}
+
+impl<K, V> Default for Map<K, V> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for Map<K, V> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_map().entries(self.0.iter()).finish()
+  }
+}
+
+impl<K: Hash + Eq, V: PartialEq> PartialEq for Map<K, V> {
+  fn eq(&self, other: &Self) -> bool {
+    self.0 == other.0
+  }
+}
+
+impl<K: Hash + Eq, V: Eq> Eq for Map<K, V> {}
+
+impl<K: Hash + Ord, V: Ord> PartialOrd for Map<K, V> {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl<K: Hash + Ord, V: Ord> Ord for Map<K, V> {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.sorted().cmp(&other.sorted())
+  }
+}
+
+impl<K: Hash + Ord, V: Hash> Hash for Map<K, V> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.len().hash(state);
+    for entry in self.sorted() {
+      entry.hash(state);
+    }
+  }
+}
+
+impl<K: Hash + Eq, V> FromIterator<(K, V)> for Map<K, V> {
+  fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+    let mut map = Self::new();
+    map.extend(iter);
+    map
+  }
+}
+
+impl<K: Hash + Eq, V> Extend<(K, V)> for Map<K, V> {
+  fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+    self.0.extend(iter);
+  }
+}
+
+impl<K: Hash + Eq, V, const N: usize> From<[(K, V); N]> for Map<K, V> {
+  fn from(entries: [(K, V); N]) -> Self {
+    entries.into_iter().collect()
+  }
+}
+
+impl<K, V> IntoIterator for Map<K, V> {
+  type Item = (K, V);
+  type IntoIter = IntoIter<K, V>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    IntoIter(self.0.into_iter())
+  }
+}
+
+impl<'a, K, V> IntoIterator for &'a Map<K, V> {
+  type Item = (&'a K, &'a V);
+  type IntoIter = Iter<'a, K, V>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl<'a, K, V> IntoIterator for &'a mut Map<K, V> {
+  type Item = (&'a K, &'a mut V);
+  type IntoIter = IterMut<'a, K, V>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter_mut()
+  }
+}
+
+/// A set in insertion order, with the methods of a [`BTreeSet`](alloc::collections::BTreeSet)
+/// that [`Edn`](super::Edn) needs.
+#[derive(Clone)]
+pub struct Set<T>(IndexSet<T, State>);
+
+impl<T> Set<T> {
+  /// An empty set.
+  pub fn new() -> Self {
+    Self(IndexSet::with_hasher(State::default()))
+  }
+
+  /// The number of values.
+  pub fn len(&self) -> usize {
+    self.0.len()
+  }
+
+  /// Whether there are no values.
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  /// The values, in order.
+  pub fn iter(&self) -> SetIter<'_, T> {
+    SetIter(self.0.iter())
+  }
+
+  /// The first value inserted that's still there.
+  pub fn first(&self) -> Option<&T> {
+    self.0.first()
+  }
+
+  /// The last value inserted.
+  pub fn last(&self) -> Option<&T> {
+    self.0.last()
+  }
+
+  /// Removes the first value, moving the rest down, which takes as long as there are values.
+  pub fn pop_first(&mut self) -> Option<T> {
+    self.0.shift_remove_index(0)
+  }
+
+  /// Removes the last value.
+  pub fn pop_last(&mut self) -> Option<T> {
+    self.0.pop()
+  }
+
+  /// Removes every value.
+  pub fn clear(&mut self) {
+    self.0.clear();
+  }
+
+  /// Keeps only the values `keep` returns true for, in their order.
+  pub fn retain(&mut self, keep: impl FnMut(&T) -> bool) {
+    self.0.retain(keep);
+  }
+}
+
+impl<T: Hash + Eq> Set<T> {
+  /// Adds `value` at the end, if it isn't already in the set.
+  pub fn insert(&mut self, value: T) -> bool {
+    self.0.insert(value)
+  }
+
+  /// Whether `value` is in the set.
+  pub fn contains<Q>(&self, value: &Q) -> bool
+  where
+    T: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+  {
+    self.0.contains(value)
+  }
+
+  /// The value in the set equal to `value`.
+  pub fn get<Q>(&self, value: &Q) -> Option<&T>
+  where
+    T: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+  {
+    self.0.get(value)
+  }
+
+  /// Removes `value`, keeping the order of the rest.
+  pub fn remove<Q>(&mut self, value: &Q) -> bool
+  where
+    T: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+  {
+    self.0.shift_remove(value)
+  }
+
+  /// Removes `value`, keeping the order of the rest.
+  pub fn take<Q>(&mut self, value: &Q) -> Option<T>
+  where
+    T: Borrow<Q>,
+    Q: ?Sized + Hash + Eq,
+  {
+    self.0.shift_take(value)
+  }
+
+  /// Moves the values of `other` that aren't in this set to its end.
+  pub fn append(&mut self, other: &mut Self) {
+    self.0.extend(other.0.drain(..));
+  }
+
+  // The values sorted, to compare and hash sets without their order
+  fn sorted(&self) -> Vec<&T>
+  where
+    T: Ord,
+  {
+    let mut values: Vec<_> = self.0.iter().collect();
+    values.sort_unstable();
+    values
+  }
+}
+
+impl<T> Default for Set<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Set<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_set().entries(self.0.iter()).finish()
+  }
+}
+
+impl<T: Hash + Eq> PartialEq for Set<T> {
+  fn eq(&self, other: &Self) -> bool {
+    self.0 == other.0
+  }
+}
+
+impl<T: Hash + Eq> Eq for Set<T> {}
+
+impl<T: Hash + Ord> PartialOrd for Set<T> {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl<T: Hash + Ord> Ord for Set<T> {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.sorted().cmp(&other.sorted())
+  }
+}
+
+impl<T: Hash + Ord> Hash for Set<T> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.len().hash(state);
+    for value in self.sorted() {
+      value.hash(state);
+    }
+  }
+}
+
+impl<T: Hash + Eq> FromIterator<T> for Set<T> {
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    let mut set = Self::new();
+    set.extend(iter);
+    set
+  }
+}
+
+impl<T: Hash + Eq> Extend<T> for Set<T> {
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    self.0.extend(iter);
+  }
+}
+
+impl<T: Hash + Eq, const N: usize> From<[T; N]> for Set<T> {
+  fn from(values: [T; N]) -> Self {
+    values.into_iter().collect()
+  }
+}
+
+impl<T> IntoIterator for Set<T> {
+  type Item = T;
+  type IntoIter = SetIntoIter<T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    SetIntoIter(self.0.into_iter())
+  }
+}
+
+impl<'a, T> IntoIterator for &'a Set<T> {
+  type Item = &'a T;
+  type IntoIter = SetIter<'a, T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
@@ -0,0 +1,151 @@
+//! Walking an [`Edn`] and the values inside it, like `clojure.walk`.
+
+use alloc::vec::Vec;
+use core::mem;
+
+use super::Edn;
+
+/// Looks at an [`Edn`] and every value inside it, see [`Edn::visit`].
+///
+/// Closures taking an `&Edn` are visitors that see every value as it's entered.
+pub trait Visitor<'e> {
+  /// Called on a value before the ones inside it, which are skipped if this returns `false`
+  fn enter(&mut self, _edn: &Edn<'e>) -> bool {
+    true
+  }
+
+  /// Called on a value after the ones inside it, even if they were skipped
+  fn leave(&mut self, _edn: &Edn<'e>) {}
+}
+
+impl<'e, F: FnMut(&Edn<'e>)> Visitor<'e> for F {
+  fn enter(&mut self, edn: &Edn<'e>) -> bool {
+    self(edn);
+    true
+  }
+}
+
+impl<'e> Edn<'e> {
+  /// Transforms this value and every value inside it with `f`, outside in: `f` gets collections
+  /// before what's inside them, and what it returns is walked into.
+  ///
+  /// Inside maps, `f` is applied to keys and values alike. Sets and maps are collected again from
+  /// what `f` returns, so elements and keys that have become equal are merged.
+  ///
+  /// ```
+  /// use clojure_reader::edn::{self, Edn};
+  ///
+  /// // Expands `(inc x)` before walking into what it expanded to
+  /// let edn = edn::read_string("[(inc (inc 1))]").unwrap().prewalk(|edn| match edn {
+  ///   Edn::List(list) if list.first() == Some(&Edn::Symbol("inc")) => {
+  ///     Edn::List([Edn::Symbol("+"), Edn::Int(1)].into_iter().chain(list.into_iter().skip(1)).collect())
+  ///   }
+  ///   edn => edn,
+  /// });
+  /// assert_eq!(edn.to_string(), "[(+ 1 (+ 1 1))]");
+  /// ```
+  #[must_use]
+  pub fn prewalk(self, mut f: impl FnMut(Self) -> Self) -> Self {
+    prewalk(self, &mut f)
+  }
+
+  /// Transforms this value and every value inside it with `f`, inside out: `f` gets collections
+  /// once what's inside them has been transformed.
+  ///
+  /// Inside maps, `f` is applied to keys and values alike. Sets and maps are collected again from
+  /// what `f` returns, so elements and keys that have become equal are merged.
+  ///
+  /// ```
+  /// use clojure_reader::edn::{self, Edn};
+  ///
+  /// let edn = edn::read_string("{:a [1 2] :b #{3}}").unwrap().postwalk(|edn| match edn {
+  ///   Edn::Int(i) => Edn::Int(i * 10),
+  ///   edn => edn,
+  /// });
+  /// assert_eq!(edn, edn::read_string("{:a [10 20] :b #{30}}").unwrap());
+  /// ```
+  #[must_use]
+  pub fn postwalk(self, mut f: impl FnMut(Self) -> Self) -> Self {
+    postwalk(self, &mut f)
+  }
+
+  /// Visits this value and every value inside it, depth first. Keys come right before their
+  /// values.
+  ///
+  /// ```
+  /// use clojure_reader::edn::{self, Edn};
+  ///
+  /// let mut keywords = vec![];
+  /// edn::read_string("{:a [:b 1] :c #{:d}}").unwrap().visit(&mut |edn: &Edn<'_>| {
+  ///   if let Edn::Key(key) = edn {
+  ///     keywords.push(key.to_string());
+  ///   }
+  /// });
+  /// assert_eq!(keywords, ["a", "b", "c", "d"]);
+  /// ```
+  pub fn visit(&self, visitor: &mut impl Visitor<'e>) {
+    if visitor.enter(self) {
+      match self {
+        Edn::Vector(items) | Edn::List(items) => items.iter().for_each(|item| item.visit(visitor)),
+        Edn::Set(items) => items.iter().for_each(|item| item.visit(visitor)),
+        Edn::Map(entries) => {
+          for (key, value) in entries {
+            key.visit(visitor);
+            value.visit(visitor);
+          }
+        }
+        Edn::Tagged(_, value) => value.visit(visitor),
+        _ => {}
+      }
+    }
+    visitor.leave(self);
+  }
+}
+
+fn prewalk<'e>(edn: Edn<'e>, f: &mut impl FnMut(Edn<'e>) -> Edn<'e>) -> Edn<'e> {
+  let mut edn = f(edn);
+  let children = take_children(&mut edn).into_iter().map(|child| prewalk(child, f)).collect();
+  put_children(&mut edn, children);
+  edn
+}
+
+fn postwalk<'e>(mut edn: Edn<'e>, f: &mut impl FnMut(Edn<'e>) -> Edn<'e>) -> Edn<'e> {
+  let children = take_children(&mut edn).into_iter().map(|child| postwalk(child, f)).collect();
+  put_children(&mut edn, children);
+  f(edn)
+}
+
+// Takes the values out of a collection or a tagged value, keys and values of maps in order
+pub(super) fn take_children<'e>(edn: &mut Edn<'e>) -> Vec<Edn<'e>> {
+  match edn {
+    Edn::Vector(items) | Edn::List(items) => mem::take(items),
+    Edn::Set(items) => mem::take(items).into_iter().collect(),
+    Edn::Map(entries) => mem::take(entries).into_iter().flat_map(<[Edn<'e>; 2]>::from).collect(),
+    Edn::Tagged(_, value) => Vec::from([mem::replace(value.as_mut(), Edn::Nil)]),
+    _ => Vec::new(),
+  }
+}
+
+// Puts values back into what `take_children` took them out of
+pub(super) fn put_children<'e>(edn: &mut Edn<'e>, children: Vec<Edn<'e>>) {
+  match edn {
+    Edn::Vector(items) | Edn::List(items) => *items = children,
+    Edn::Set(items) => *items = children.into_iter().collect(),
+    Edn::Map(entries) => {
+      let mut children = children.into_iter();
+      *entries = iter_pairs(&mut children).collect();
+    }
+    Edn::Tagged(_, value) => {
+      if let Some(child) = children.into_iter().next() {
+        **value = child;
+      }
+    }
+    _ => {}
+  }
+}
+
+fn iter_pairs<'a, 'e>(
+  children: &'a mut impl Iterator<Item = Edn<'e>>,
+) -> impl Iterator<Item = (Edn<'e>, Edn<'e>)> + 'a {
+  core::iter::from_fn(move || Some((children.next()?, children.next()?)))
+}
@@ -0,0 +1,205 @@
+//! Moving around and editing an [`Edn`], like `clojure.zip`.
+
+use alloc::vec::Vec;
+use core::mem;
+
+use super::Edn;
+use super::walk::{put_children, take_children};
+
+/// A cursor over an [`Edn`] and the values inside it, to move around them and edit them.
+///
+/// Going [`down`](Zipper::down) leads to the first value in a collection, the first key of a map
+/// or the value a tag is on, then [`right`](Zipper::right) goes through the rest of them, keys
+/// and values alike. Moving where nothing is gives back `None` and leaves the zipper where it was.
+/// Collections are put back together on the way [`up`](Zipper::up), so sets and maps are sorted
+/// again then.
+///
+/// # Examples
+///
+/// ```
+/// use clojure_reader::edn::{self, Edn, Zipper};
+///
+/// let edn = edn::read_string("[:users [{:name \"ada\"} {:name \"grace\"}]]").unwrap();
+/// let mut zipper = Zipper::new(edn);
+/// zipper.find_next_value(&Edn::Str("grace")).unwrap().replace(Edn::Str("hopper"));
+/// zipper.up().unwrap().insert_right(Edn::Map([(Edn::Key("name"), Edn::Str("alan"))].into()));
+///
+/// assert_eq!(
+///   zipper.root(),
+///   edn::read_string("[:users [{:name \"ada\"} {:name \"hopper\"} {:name \"alan\"}]]").unwrap()
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Zipper<'e> {
+  node: Edn<'e>,
+  // The collections the node is in, innermost last
+  parents: Vec<Parent<'e>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Parent<'e> {
+  // With what's inside taken out
+  edn: Edn<'e>,
+  left: Vec<Edn<'e>>,
+  // In reverse, so the one right of the node is last
+  right: Vec<Edn<'e>>,
+}
+
+impl<'e> Zipper<'e> {
+  pub const fn new(edn: Edn<'e>) -> Self {
+    Self { node: edn, parents: Vec::new() }
+  }
+
+  /// The value the zipper is at
+  pub const fn node(&self) -> &Edn<'e> {
+    &self.node
+  }
+
+  /// The value the zipper is at, to edit in place
+  pub const fn node_mut(&mut self) -> &mut Edn<'e> {
+    &mut self.node
+  }
+
+  /// Goes back up to the top, and gives back the whole value with the edits.
+  #[must_use]
+  pub fn root(mut self) -> Edn<'e> {
+    while self.up().is_some() {}
+    self.node
+  }
+
+  /// Goes to the first value inside this one.
+  pub fn down(&mut self) -> Option<&mut Self> {
+    let mut right = take_children(&mut self.node);
+    right.reverse();
+    let node = right.pop()?;
+    let edn = mem::replace(&mut self.node, node);
+    self.parents.push(Parent { edn, left: Vec::new(), right });
+    Some(self)
+  }
+
+  /// Goes to the value this one is in.
+  pub fn up(&mut self) -> Option<&mut Self> {
+    let Parent { mut edn, mut left, right } = self.parents.pop()?;
+    left.push(mem::replace(&mut self.node, Edn::Nil));
+    left.extend(right.into_iter().rev());
+    put_children(&mut edn, left);
+    self.node = edn;
+    Some(self)
+  }
+
+  /// Goes to the next value over.
+  pub fn right(&mut self) -> Option<&mut Self> {
+    let parent = self.parents.last_mut()?;
+    let node = parent.right.pop()?;
+    parent.left.push(mem::replace(&mut self.node, node));
+    Some(self)
+  }
+
+  /// Goes to the previous value over.
+  pub fn left(&mut self) -> Option<&mut Self> {
+    let parent = self.parents.last_mut()?;
+    let node = parent.left.pop()?;
+    parent.right.push(mem::replace(&mut self.node, node));
+    Some(self)
+  }
+
+  /// Goes to the next value after this one that `predicate` is true for, looking inside values
+  /// before past them. The value the zipper is at isn't tried, so calling it again goes on to the
+  /// next one.
+  pub fn find_next(&mut self, mut predicate: impl FnMut(&Edn<'e>) -> bool) -> Option<&mut Self> {
+    let path: Vec<usize> = self.parents.iter().map(|parent| parent.left.len()).collect();
+    while self.next_value() {
+      if predicate(&self.node) {
+        return Some(self);
+      }
+    }
+    // Back where it was, from the top
+    for index in path {
+      self.down();
+      for _ in 0..index {
+        self.right();
+      }
+    }
+    None
+  }
+
+  /// Goes to the next value after this one equal to `value`, like [`Self::find_next`].
+  pub fn find_next_value(&mut self, value: &Edn<'_>) -> Option<&mut Self> {
+    self.find_next(|edn| edn == value)
+  }
+
+  /// Replaces the value the zipper is at.
+  pub fn replace(&mut self, edn: Edn<'e>) -> &mut Self {
+    self.node = edn;
+    self
+  }
+
+  /// Replaces the value the zipper is at with what `f` makes of it.
+  pub fn edit(&mut self, f: impl FnOnce(Edn<'e>) -> Edn<'e>) -> &mut Self {
+    self.node = f(mem::replace(&mut self.node, Edn::Nil));
+    self
+  }
+
+  /// Inserts `edn` after the value the zipper is at, and stays put.
+  ///
+  /// Returns `None` at the top, in maps, where values come in pairs, and in tagged values.
+  pub fn insert_right(&mut self, edn: Edn<'e>) -> Option<&mut Self> {
+    self.pairless_parent()?.right.push(edn);
+    Some(self)
+  }
+
+  /// Inserts `edn` before the value the zipper is at, and stays put.
+  ///
+  /// Returns `None` at the top, in maps, where values come in pairs, and in tagged values.
+  pub fn insert_left(&mut self, edn: Edn<'e>) -> Option<&mut Self> {
+    self.pairless_parent()?.left.push(edn);
+    Some(self)
+  }
+
+  /// Removes the value the zipper is at, and goes to the value before it, or the one it was in
+  /// if it was the first.
+  ///
+  /// In a map the whole entry goes, key and value. Returns `None` at the top and in tagged values.
+  pub fn remove(&mut self) -> Option<&mut Self> {
+    let parent = self.parents.last_mut()?;
+    match parent.edn {
+      Edn::Tagged(..) => return None,
+      // A key goes with its value, and a value with its key
+      Edn::Map(_) if parent.left.len().is_multiple_of(2) => {
+        parent.right.pop();
+      }
+      Edn::Map(_) => {
+        parent.left.pop();
+      }
+      _ => {}
+    }
+    if let Some(node) = parent.left.pop() {
+      self.node = node;
+      return Some(self);
+    }
+    let Parent { mut edn, right, .. } = self.parents.pop()?;
+    put_children(&mut edn, right.into_iter().rev().collect());
+    self.node = edn;
+    Some(self)
+  }
+
+  // The parent, if values can be put in it one at a time
+  fn pairless_parent(&mut self) -> Option<&mut Parent<'e>> {
+    self.parents.last_mut().filter(|parent| !matches!(parent.edn, Edn::Map(_) | Edn::Tagged(..)))
+  }
+
+  // Goes to the next value depth first, or back to the top if there's none
+  fn next_value(&mut self) -> bool {
+    if self.down().is_some() {
+      return true;
+    }
+    loop {
+      if self.right().is_some() {
+        return true;
+      }
+      if self.up().is_none() {
+        return false;
+      }
+    }
+  }
+}
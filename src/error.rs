@@ -1,6 +1,8 @@
 use core::error;
 use core::fmt::{self, Debug};
 
+use crate::line_index::{LineCol, LineIndex};
+
 pub type Result<T> = core::result::Result<T, Error>;
 
 pub struct Error {
@@ -10,7 +12,8 @@ pub struct Error {
   pub line: Option<usize>,
   /// Column number, counting from 1. The count is utf-8 chars.
   pub column: Option<usize>,
-  /// This is a pointer offset of the str trying to be parsed, not a utf-8 char offset
+  /// This is a pointer offset of the str trying to be parsed, not a utf-8 char offset.
+  /// See [`LineIndex`] to turn it into a line and column.
   pub ptr: Option<usize>,
 }
 
@@ -31,6 +34,18 @@ pub enum Code {
   UnexpectedEOF,
   UnmatchedDelimiter(char),
 
+  /// Edit errors
+  InvalidIndex,
+  InvalidMapEntry,
+  NotAssociative,
+  NotCollection,
+  /// A key without a namespace put in a `#:namespace{}` map read as a tagged one, where it'd be
+  /// taken to be in the map's namespace
+  NamespacedMapKey,
+
+  /// Conversion errors
+  UnexpectedType,
+
   /// Feature errors
   NoFloatFeature,
 
@@ -40,8 +55,26 @@ pub enum Code {
 }
 
 impl Error {
-  pub(crate) const fn from_position(code: Code, position: crate::parse::Position) -> Self {
-    Self { code, line: Some(position.line), column: Some(position.column), ptr: Some(position.ptr) }
+  /// An error that isn't about any source, like one editing an [`Edn`](crate::edn::Edn).
+  pub(crate) const fn new(code: Code) -> Self {
+    Self { code, line: None, column: None, ptr: None }
+  }
+
+  /// An error at a byte offset, the line and column get filled in by [`Error::with_source`].
+  pub(crate) const fn from_offset(code: Code, ptr: usize) -> Self {
+    Self { code, line: None, column: None, ptr: Some(ptr) }
+  }
+
+  /// Fills in the line and column from the source that `ptr` points into.
+  pub(crate) fn with_source(mut self, source: &str) -> Self {
+    if let Some(ptr) = self.ptr
+      && self.line.is_none()
+    {
+      let LineCol { line, column } = LineIndex::new(source).line_col(ptr);
+      self.line = Some(line);
+      self.column = Some(column);
+    }
+    self
   }
 }
 
@@ -9,16 +9,28 @@ extern crate std;
 
 pub mod edn;
 pub mod error;
+pub mod line_index;
+
+pub mod builder {
+  //! Parsing straight into your own types with a [`Builder`], without building an
+  //! [`Edn`](crate::edn::Edn) first.
+  //!
+  //! These are the same items as in the `parse` module of the `unstable` feature, and stable.
+
+  pub use crate::parse::{Atom, Builder, Parsed, SourceReader, Span, parse_with};
+}
 
 #[cfg(feature = "serde")]
-pub use de::from_str;
+pub use de::{from_edn, from_str};
 #[cfg(feature = "serde")]
-pub use ser::to_string;
+pub use ser::{to_edn, to_string};
 
 #[cfg(feature = "serde")]
 pub mod de;
 #[cfg(feature = "serde")]
 pub mod ser;
+#[cfg(feature = "serde")]
+pub mod types;
 
 #[cfg(feature = "unstable")]
 pub mod parse;
@@ -0,0 +1,113 @@
+//! Maps byte offsets, like the ones in spans and [`Error::ptr`](crate::error::Error::ptr),
+//! to lines and columns.
+//!
+//! Spans only record byte offsets, which keeps them small and parsing fast. Build a
+//! [`LineIndex`] once per source when positions are needed, then look up as many offsets as
+//! required.
+//!
+//! ```
+//! use clojure_reader::line_index::{LineCol, LineIndex};
+//!
+//! let index = LineIndex::new("{:cat \"猫\"\n :gato 𝄞}");
+//!
+//! assert_eq!(index.line_col(7), LineCol { line: 1, column: 8 });
+//! // Columns count chars, or UTF-16 code units for editors that speak LSP
+//! assert_eq!(index.line_col(23), LineCol { line: 2, column: 9 });
+//! assert_eq!(index.line_col_utf16(23), LineCol { line: 2, column: 10 });
+//! assert_eq!(index.offset(LineCol { line: 2, column: 9 }), Some(23));
+//! ```
+
+use alloc::vec::Vec;
+
+/// A line and column, both counting from 1.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LineCol {
+  pub line: usize,
+  pub column: usize,
+}
+
+/// Index of where every line of a source string starts.
+#[derive(Debug, Clone)]
+pub struct LineIndex<'s> {
+  source: &'s str,
+  // Byte offset of the start of each line, the first one is always 0
+  line_starts: Vec<usize>,
+}
+
+// Continuation bytes don't start a char
+const fn is_char_start(byte: u8) -> bool {
+  byte & 0xC0 != 0x80
+}
+
+// Chars outside the basic multilingual plane, which take 4 bytes in UTF-8, are surrogate pairs
+const fn utf16_len(byte: u8) -> usize {
+  if byte >= 0xF0 { 2 } else { 1 }
+}
+
+impl<'s> LineIndex<'s> {
+  pub fn new(source: &'s str) -> Self {
+    let newlines =
+      source.bytes().enumerate().filter(|&(_, byte)| byte == b'\n').map(|(ptr, _)| ptr + 1);
+    let line_starts = core::iter::once(0).chain(newlines).collect();
+    Self { source, line_starts }
+  }
+
+  /// Number of lines in the source. An empty source, or a trailing newline, still counts a line.
+  pub const fn line_count(&self) -> usize {
+    self.line_starts.len()
+  }
+
+  /// Line and column of a byte offset, with the column counted in chars.
+  ///
+  /// This is how [`Error`](crate::error::Error) counts. Offsets past the end of the source are
+  /// clamped to the end.
+  pub fn line_col(&self, offset: usize) -> LineCol {
+    let (line, line_bytes) = self.line_bytes(offset);
+    let column = line_bytes.iter().copied().filter(|&byte| is_char_start(byte)).count() + 1;
+    LineCol { line, column }
+  }
+
+  /// Line and column of a byte offset, with the column counted in UTF-16 code units.
+  ///
+  /// LSP clients count from 0, so subtract 1 from both before handing them over.
+  pub fn line_col_utf16(&self, offset: usize) -> LineCol {
+    let (line, line_bytes) = self.line_bytes(offset);
+    let column =
+      line_bytes.iter().copied().filter(|&byte| is_char_start(byte)).map(utf16_len).sum::<usize>()
+        + 1;
+    LineCol { line, column }
+  }
+
+  /// Byte offset of a line and column counted in chars, if it falls inside the source.
+  pub fn offset(&self, line_col: LineCol) -> Option<usize> {
+    self.offset_by(line_col, |_| 1)
+  }
+
+  /// Byte offset of a line and column counted in UTF-16 code units, if it falls inside the source.
+  pub fn offset_utf16(&self, line_col: LineCol) -> Option<usize> {
+    self.offset_by(line_col, utf16_len)
+  }
+
+  fn line_bytes(&self, offset: usize) -> (usize, &'s [u8]) {
+    let offset = offset.min(self.source.len());
+    // Infallible: the first line starts at 0, so at least one line starts at or before `offset`
+    let line = self.line_starts.partition_point(|&start| start <= offset);
+    let start = self.line_starts.get(line - 1).copied().unwrap_or_default();
+    (line, self.source.as_bytes().get(start..offset).unwrap_or_default())
+  }
+
+  fn offset_by(&self, line_col: LineCol, width: impl Fn(u8) -> usize) -> Option<usize> {
+    let start = *self.line_starts.get(line_col.line.checked_sub(1)?)?;
+    let end = self.line_starts.get(line_col.line).map_or(self.source.len(), |next| next - 1);
+    let line = self.source.as_bytes().get(start..end)?;
+
+    let mut remaining = line_col.column.checked_sub(1)?;
+    for (ptr, byte) in line.iter().copied().enumerate().filter(|&(_, byte)| is_char_start(byte)) {
+      if remaining == 0 {
+        return Some(start + ptr);
+      }
+      remaining = remaining.checked_sub(width(byte))?;
+    }
+    (remaining == 0).then_some(end)
+  }
+}
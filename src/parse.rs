@@ -2,13 +2,14 @@
 #![expect(clippy::inline_always)]
 
 use alloc::boxed::Box;
-use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::collections::BTreeSet;
 use alloc::vec::Vec;
 use core::mem::replace;
 use core::primitive::str;
 
-use crate::edn::Edn;
+use crate::edn::{self, Edn, Namespaces};
 use crate::error::{Code, Error};
+use scan::{DELIMITER, QUOTE, SPACE};
 
 #[cfg(feature = "arbitrary-nums")]
 use bigdecimal::BigDecimal;
@@ -17,6 +18,30 @@ use num_bigint::BigInt;
 #[cfg(feature = "floats")]
 use ordered_float::OrderedFloat;
 
+#[cfg(feature = "unstable")]
+mod events;
+#[cfg(feature = "unstable")]
+mod format;
+#[cfg(feature = "unstable")]
+mod print;
+mod scan;
+#[cfg(feature = "unstable")]
+mod spanned;
+#[cfg(feature = "unstable")]
+mod trivia;
+#[cfg(feature = "unstable")]
+mod zipper;
+#[cfg(feature = "unstable")]
+pub use events::{Event, Events};
+#[cfg(feature = "unstable")]
+pub use format::{Formatter, Indent};
+#[cfg(feature = "unstable")]
+pub use spanned::{SpannedEdn, SpannedKind, SpannedMap, SpannedSet, parse_spanned};
+#[cfg(feature = "unstable")]
+pub use trivia::parse_with_trivia;
+#[cfg(feature = "unstable")]
+pub use zipper::Zipper;
+
 /// Possible kinds of an EDN node
 ///
 /// **NOTE:** The vector of items in [`NodeKind::Set`] may contain duplicate items.
@@ -58,24 +83,53 @@ pub enum NodeKind<'e> {
   Nil,
 }
 
-/// A **discarded** form containing the node that was discarded
+/// A **discarded** form containing the node that was discarded, and the trivia before its `#_`
 #[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Discard<'e>(pub Node<'e>, pub Span);
+pub struct Discard<'e>(pub Node<'e>, pub Span, pub Vec<Trivia<'e>>);
+
+/// Text between forms that doesn't change what they read as
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Trivia<'e> {
+  /// Whitespace, commas included
+  Whitespace(&'e str),
+  /// A `;` or `#!` comment, without the line break that ends it
+  Comment(&'e str),
+}
+
+impl<'e> Trivia<'e> {
+  /// The source of the trivia
+  pub const fn as_str(&self) -> &'e str {
+    match self {
+      Trivia::Whitespace(trivia) | Trivia::Comment(trivia) => trivia,
+    }
+  }
+}
 
 /// Concrete EDN syntax tree.
 ///
-/// Parse one with [`parse`], then convert it to an [`Edn`] with [`Edn::try_from`].
+/// Parse one with [`parse`], then convert it to an [`Edn`] with [`Edn::try_from`]. The trivia is
+/// only recorded by [`parse_with_trivia`].
 #[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Node<'e> {
   pub kind: NodeKind<'e>,
   pub span: Span,
   pub leading_discards: Vec<Discard<'e>>,
+  /// Whitespace and comments between the leading discards and the node
+  pub leading_trivia: Vec<Trivia<'e>>,
+  /// Whitespace and comments at the end of a collection, before its closing delimiter
+  pub trailing_trivia: Vec<Trivia<'e>>,
 }
 
 impl<'e> Node<'e> {
   /// Construct a `Node` with the given kind and span and no leading discards.
   pub const fn no_discards(kind: NodeKind<'e>, span: Span) -> Self {
-    Self { kind, span, leading_discards: Vec::new() }
+    Self {
+      kind,
+      span,
+      leading_discards: Vec::new(),
+      leading_trivia: Vec::new(),
+      trailing_trivia: Vec::new(),
+    }
   }
 
   #[inline]
@@ -131,12 +185,26 @@ impl<'e> Node<'e> {
 /// See [`crate::error::Error`].
 #[cfg_attr(not(feature = "unstable"), expect(dead_code))]
 pub fn parse<'r, 'e: 'r>(reader: &'r mut SourceReader<'e>) -> Result<Node<'e>, Error> {
+  parse_with(reader, &NodeBuilder)
+}
+
+/// Parse a single form from a [`SourceReader`] with your own [`Builder`], consuming that form.
+///
+/// An input without any forms is built with [`Builder::nil`].
+///
+/// # Errors
+///
+/// See [`crate::error::Error`], and whatever the builder returns.
+pub fn parse_with<'r, 'e: 'r, B: Builder<'e>>(
+  reader: &'r mut SourceReader<'e>,
+  builder: &B,
+) -> Result<B::Item, Error> {
   let start_pos = reader.read_pos;
-  let builder = NodeBuilder;
   let parsed = {
     let mut walker = Walker::new(reader);
-    parse_internal(&mut walker, &builder)?
+    parse_internal(&mut walker, builder)
   };
+  let parsed = parsed.map_err(|err| err.with_source(reader.slice))?;
   Ok(parsed.unwrap_or_else(|| builder.nil(reader.span_from(start_pos))))
 }
 
@@ -147,52 +215,53 @@ pub fn parse<'r, 'e: 'r>(reader: &'r mut SourceReader<'e>) -> Result<Node<'e>, E
 /// See [`crate::error::Error`].
 pub fn parse_as_edn(edn: &str) -> Result<(Edn<'_>, &str), Error> {
   let mut source_reader = SourceReader::new(edn);
-  let start_pos = source_reader.read_pos;
-  let builder = EdnBuilder;
-  let parsed = {
-    let mut walker = Walker::new(&mut source_reader);
-    parse_internal(&mut walker, &builder)?
-  };
-  let parsed = parsed.unwrap_or_else(|| builder.nil(source_reader.span_from(start_pos)));
+  let parsed = parse_with(&mut source_reader, &EdnBuilder)?;
   Ok((parsed, source_reader.remaining()))
 }
 
+/// Parse the first EDN form from a string, checking its namespaced maps can be expanded in
+/// `namespaces`.
+#[cfg_attr(not(feature = "unstable"), expect(clippy::redundant_pub_crate))]
+pub(crate) fn parse_as_namespaced_edn<'e>(
+  edn: &'e str,
+  namespaces: &Namespaces<'_>,
+) -> Result<Edn<'e>, Error> {
+  let mut source_reader = SourceReader::new(edn);
+  Ok(parse_with(&mut source_reader, &NamespacedBuilder(namespaces))?.0)
+}
+
 #[cfg_attr(not(feature = "unstable"), expect(clippy::redundant_pub_crate))]
 pub(crate) fn parse_optional_edn(edn: &str) -> Result<(Option<Edn<'_>>, &str), Error> {
   let mut source_reader = SourceReader::new(edn);
   let parsed = {
     let mut walker = Walker::new(&mut source_reader);
-    parse_internal(&mut walker, &EdnBuilder)?
+    parse_internal(&mut walker, &EdnBuilder).map_err(|err| err.with_source(edn))?
   };
   Ok((parsed, source_reader.remaining()))
 }
 
-const DELIMITERS: [char; 8] = [',', ']', '}', ')', ';', '(', '[', '{'];
-
-fn is_token_boundary(c: char) -> bool {
-  c.is_whitespace() || DELIMITERS.contains(&c) || c == '"'
-}
+/// Start and end byte offsets of a form in the source, the end being exclusive
+///
+/// Use a [`LineIndex`](crate::line_index::LineIndex) to find out which line and column an offset
+/// is at.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Span(pub usize, pub usize);
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Position {
-  pub line: usize,
-  pub column: usize,
-  pub ptr: usize,
-}
+impl Span {
+  /// Whether the span is empty
+  pub const fn is_empty(&self) -> bool {
+    self.0 == self.1
+  }
 
-impl Default for Position {
-  fn default() -> Self {
-    Self { line: 1, column: 1, ptr: 0 }
+  /// Length of the span in bytes
+  pub const fn len(&self) -> usize {
+    self.1 - self.0
   }
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Span(pub Position, pub Position);
-
-impl Span {
-  /// Whether the span is empty
-  pub const fn is_empty(&self) -> bool {
-    self.0.ptr == self.1.ptr
+impl From<Span> for core::ops::Range<usize> {
+  fn from(Span(start, end): Span) -> Self {
+    start..end
   }
 }
 
@@ -200,18 +269,19 @@ impl Span {
 #[derive(Debug)]
 pub struct SourceReader<'s> {
   slice: &'s str,
-  // Position till where this string has been read
-  read_pos: Position,
+  // Byte offset till where this string has been read
+  read_pos: usize,
 }
 
 impl<'e> SourceReader<'e> {
-  pub fn new(source: &'e str) -> Self {
-    Self { slice: source, read_pos: Position::default() }
+  /// A reader at the start of `source`
+  pub const fn new(source: &'e str) -> Self {
+    Self { slice: source, read_pos: 0 }
   }
 
   /// Span from some previously marked position to the current position of the reader
   #[inline(always)]
-  pub const fn span_from(&self, marker: Position) -> Span {
+  pub const fn span_from(&self, marker: usize) -> Span {
     Span(marker, self.read_pos)
   }
 
@@ -228,149 +298,116 @@ impl<'e> SourceReader<'e> {
   /// }
   /// ```
   pub fn remaining(&self) -> &'e str {
-    &self.slice[self.read_pos.ptr..]
+    &self.slice[self.read_pos..]
   }
 
   /// Finishes the source-reader, returning:
-  /// 1. the current reader position, and
+  /// 1. the byte offset the reader got to, and
   /// 2. the original source-string.
   ///
   /// ```
   /// #[cfg(feature = "unstable")]
   /// {
-  ///   use clojure_reader::parse::{Position, SourceReader, parse};
+  ///   use clojure_reader::parse::{SourceReader, parse};
   ///
   ///   let mut s = SourceReader::new("() []");
   ///   let _ = parse(&mut s).expect("failed to parse");
   ///
-  ///   let (pos, slice) = s.finish();
-  ///   assert_eq!(
-  ///       pos,
-  ///       Position {
-  ///           line: 1,
-  ///           column: 3,
-  ///           ptr: 2
-  ///       }
-  ///   );
+  ///   let (ptr, slice) = s.finish();
+  ///   assert_eq!(ptr, 2);
   ///   assert_eq!(slice, "() []");
-  ///   assert_eq!(&slice[pos.ptr..], " []");
+  ///   assert_eq!(&slice[ptr..], " []");
   /// }
   /// ```
-  #[cfg_attr(not(feature = "unstable"), expect(dead_code))]
-  pub const fn finish(self) -> (Position, &'e str) {
+  pub const fn finish(self) -> (usize, &'e str) {
     (self.read_pos, self.slice)
   }
 
   // Slurps until whitespace or delimiter, returning the slice.
   #[inline(always)]
   fn slurp_literal(&mut self) -> &'e str {
-    let token = self.slice[self.read_pos.ptr..]
-      .split(is_token_boundary)
-      .next()
-      .expect("Expected at least an empty slice");
-
-    self.read_pos.ptr += token.len();
-    self.read_pos.column += token.chars().count();
+    let rest = self.remaining();
+    let token = &rest[..scan::len_until(rest, SPACE | DELIMITER | QUOTE)];
+    self.read_pos += token.len();
     token
   }
 
   // Slurps a char. Special handling for chars that happen to be delimiters
   #[inline(always)]
   fn slurp_char(&mut self) -> &'e str {
-    let starting_ptr = self.read_pos.ptr;
-
-    let mut ptr = 0;
-    while let Some(c) = self.peek_next() {
-      // first is always \\, second is always a char we want.
-      // Handles edge cases of having a valid "\\[" but also "\\c[lolthisisvalidedn"
-      if ptr > 1 && (c.is_whitespace() || DELIMITERS.contains(&c)) {
-        break;
-      }
-
-      let _ = self.nibble_next();
-      ptr += c.len_utf8();
-    }
-    &self.slice[starting_ptr..starting_ptr + ptr]
+    let rest = self.remaining();
+    // first is always \\, second is always a char we want.
+    // Handles edge cases of having a valid "\\[" but also "\\c[lolthisisvalidedn"
+    let mut chars = rest.chars();
+    let head = chars.next().map_or(0, char::len_utf8) + chars.next().map_or(0, char::len_utf8);
+    let token = &rest[..head + scan::len_until(&rest[head..], SPACE | DELIMITER)];
+    self.read_pos += token.len();
+    token
   }
 
   #[inline(always)]
   fn slurp_str(&mut self) -> Result<&'e str, Error> {
     let _ = self.nibble_next(); // Consume the leading '"' char
-    let starting_ptr = self.read_pos.ptr;
-    let mut escape = false;
+    let starting_ptr = self.read_pos;
     loop {
-      if let Some(c) = self.nibble_next() {
-        if escape {
-          match c {
-            't' | 'r' | 'n' | '\\' | '"' => (),
-            _ => {
-              return Err(Error::from_position(Code::InvalidEscape, self.read_pos));
-            }
-          }
-          escape = false;
-        } else if c == '"' {
-          return Ok(&self.slice[starting_ptr..self.read_pos.ptr - 1]);
-        } else {
-          escape = c == '\\';
-        }
-      } else {
-        return Err(Error::from_position(Code::UnexpectedEOF, self.read_pos));
+      let Some(found) = scan::find_quote_or_escape(self.remaining()) else {
+        self.read_pos = self.slice.len();
+        return Err(Error::from_offset(Code::UnexpectedEOF, self.read_pos));
+      };
+      self.read_pos += found;
+      if self.nibble_next() == Some('"') {
+        return Ok(&self.slice[starting_ptr..self.read_pos - 1]);
+      }
+      match self.nibble_next() {
+        Some('t' | 'r' | 'n' | '\\' | '"') => (),
+        Some(_) => return Err(Error::from_offset(Code::InvalidEscape, self.read_pos)),
+        None => return Err(Error::from_offset(Code::UnexpectedEOF, self.read_pos)),
       }
     }
   }
 
   #[inline(always)]
   fn slurp_tag(&mut self) -> Result<&'e str, Error> {
-    let starting_ptr = self.read_pos.ptr;
-
-    loop {
-      if let Some(c) = self.peek_next() {
-        if is_token_boundary(c) {
-          return Ok(&self.slice[starting_ptr..self.read_pos.ptr]);
-        }
-        let _ = self.nibble_next();
-      } else {
-        return Err(Error::from_position(Code::UnexpectedEOF, self.read_pos));
-      }
+    let rest = self.remaining();
+    let tag = &rest[..scan::len_until(rest, SPACE | DELIMITER | QUOTE)];
+    self.read_pos += tag.len();
+    if tag.len() == rest.len() {
+      return Err(Error::from_offset(Code::UnexpectedEOF, self.read_pos));
     }
+    Ok(tag)
   }
 
-  // Nibbles away until the next new line
-  #[inline(always)]
-  fn nibble_newline(&mut self) {
-    while let Some(c) = self.peek_next() {
-      if matches!(c, '\n' | '\r') {
-        break;
-      }
-      let _ = self.nibble_next();
-    }
-    self.nibble_whitespace();
+  /// Reads the whitespace or the comment at the reader, if there is any
+  #[cfg(feature = "unstable")]
+  fn next_trivia(&mut self) -> Option<Trivia<'e>> {
+    let rest = self.remaining();
+    let trivia = match scan::comment_len(rest) {
+      0 => Trivia::Whitespace(&rest[..scan::len_while(rest, SPACE)]),
+      len => Trivia::Comment(&rest[..len]),
+    };
+    let len = trivia.as_str().len();
+    self.read_pos += len;
+    (len > 0).then_some(trivia)
   }
 
-  // Nibbles away until the start of the next form
+  // Nibbles away whitespace and comments until the start of the next form
   #[inline(always)]
-  fn nibble_whitespace(&mut self) {
-    while let Some(n) = self.peek_next() {
-      if n == ',' || n.is_whitespace() {
-        let _ = self.nibble_next();
-        continue;
+  pub(crate) fn nibble_trivia(&mut self) {
+    loop {
+      self.read_pos += scan::len_while(self.remaining(), SPACE);
+      match scan::comment_len(self.remaining()) {
+        0 => break,
+        len => self.read_pos += len,
       }
-      break;
     }
   }
 
   // Consumes next
   #[inline(always)]
   fn nibble_next(&mut self) -> Option<char> {
-    let char = self.slice[self.read_pos.ptr..].chars().next();
+    let char = self.peek_next();
     if let Some(c) = char {
-      self.read_pos.ptr += c.len_utf8();
-      if c == '\n' {
-        self.read_pos.line += 1;
-        self.read_pos.column = 1;
-      } else {
-        self.read_pos.column += 1;
-      }
+      self.read_pos += c.len_utf8();
     }
     char
   }
@@ -378,27 +415,118 @@ impl<'e> SourceReader<'e> {
   // Peek into the next char
   #[inline(always)]
   fn peek_next(&self) -> Option<char> {
-    self.slice[self.read_pos.ptr..].chars().next()
+    match self.slice.as_bytes().get(self.read_pos) {
+      Some(&byte) if byte.is_ascii() => Some(char::from(byte)),
+      Some(_) => self.remaining().chars().next(),
+      None => None,
+    }
   }
+
+  /// Skips whitespace and comments, then reads the next token along with the offset it starts
+  /// at. Returns `None` at the end of the source.
+  pub(crate) fn next_token(&mut self) -> Result<Option<(usize, Token<'e>)>, Error> {
+    self.nibble_trivia();
+    let pos_start = self.read_pos;
+    let Some(c) = self.nibble_next() else { return Ok(None) };
+    let token = match c {
+      '[' => Token::Open(Open::Vector),
+      '(' => Token::Open(Open::List),
+      '{' => Token::Open(Open::Map),
+      ']' | ')' | '}' => Token::Close(c),
+      '#' => self.hash_dispatch()?,
+      _ => {
+        self.read_pos = pos_start;
+        let atom = match c {
+          '\\' => parse_char(self.slurp_char()).map(Atom::Char),
+          '"' => Ok(Atom::Str(self.slurp_str()?)),
+          _ => edn_literal(self.slurp_literal()),
+        };
+        Token::Atom(atom.map_err(|code| Error::from_offset(code, pos_start))?)
+      }
+    };
+    Ok(Some((pos_start, token)))
+  }
+
+  // Reads what follows a '#'
+  fn hash_dispatch(&mut self) -> Result<Token<'e>, Error> {
+    match self.peek_next() {
+      Some('{') => {
+        let _ = self.nibble_next();
+        Ok(Token::Open(Open::Set))
+      }
+      Some('_') => {
+        let _ = self.nibble_next();
+        Ok(Token::Discard)
+      }
+      Some(c) if scan::char_class(c) & (SPACE | DELIMITER) == 0 => {
+        let tag_pos_start = self.read_pos;
+        let tag = self.slurp_tag()?;
+        if tag.is_empty() {
+          return Err(Error::from_offset(Code::InvalidTag, self.read_pos));
+        }
+        Ok(Token::Tag(tag, self.span_from(tag_pos_start)))
+      }
+      Some(_) => Err(Error::from_offset(Code::InvalidTag, self.read_pos)),
+      None => Err(Error::from_offset(Code::UnexpectedEOF, self.read_pos)),
+    }
+  }
+
+  /// Moves the reader back to an offset it has already been at
+  #[cfg(feature = "serde")]
+  pub(crate) const fn rewind(&mut self, ptr: usize) {
+    self.read_pos = ptr;
+  }
+
+  /// The whole source being read
+  #[cfg(feature = "serde")]
+  pub(crate) const fn source(&self) -> &'e str {
+    self.slice
+  }
+}
+
+#[cfg_attr(not(feature = "unstable"), expect(clippy::redundant_pub_crate))]
+/// What starts a collection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Open {
+  Vector,
+  List,
+  Map,
+  Set,
 }
 
-struct Parsed<I> {
-  item: I,
-  span: Span,
+#[cfg_attr(not(feature = "unstable"), expect(clippy::redundant_pub_crate))]
+/// A piece of EDN as the reader sees it, before it is assembled into forms
+#[derive(Debug)]
+pub(crate) enum Token<'e> {
+  Open(Open),
+  Close(char),
+  Tag(&'e str, /* Span of the tag string */ Span),
+  Discard,
+  Atom(Atom<'e>),
+}
+
+/// An item a [`Builder`] made, along with the span of the form it was made from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parsed<I> {
+  /// What the form was built into
+  pub item: I,
+  /// Where the form is in the source
+  pub span: Span,
 }
 
 impl<I> Parsed<I> {
-  const fn new(item: I, span: Span) -> Self {
+  /// An `item` made from the form at `span`
+  pub const fn new(item: I, span: Span) -> Self {
     Self { item, span }
   }
 }
 
-struct Walker<'e, 'r, B: InternalParser<'e>> {
+struct Walker<'e, 'r, B: Builder<'e>> {
   reader: &'r mut SourceReader<'e>,
   stack: Vec<ParseContext<'e, B>>,
 }
 
-impl<'e, 'r, B: InternalParser<'e>> Walker<'e, 'r, B> {
+impl<'e, 'r, B: Builder<'e>> Walker<'e, 'r, B> {
   fn new(reader: &'r mut SourceReader<'e>) -> Self {
     Self {
       reader,
@@ -407,13 +535,13 @@ impl<'e, 'r, B: InternalParser<'e>> Walker<'e, 'r, B> {
   }
 
   #[inline(always)]
-  const fn pos(&self) -> Position {
+  const fn pos(&self) -> usize {
     self.reader.read_pos
   }
 
   /// Span from some previously marked position to the current position of the walker's reader
   #[inline(always)]
-  const fn span_from(&self, marker: Position) -> Span {
+  const fn span_from(&self, marker: usize) -> Span {
     Span(marker, self.reader.read_pos)
   }
 
@@ -433,7 +561,7 @@ impl<'e, 'r, B: InternalParser<'e>> Walker<'e, 'r, B> {
   }
 
   const fn make_error(&self, code: Code) -> Error {
-    Error::from_position(code, self.pos())
+    Error::from_offset(code, self.pos())
   }
 
   fn last_context_discards(&mut self) -> Option<&mut Vec<B::Discard>> {
@@ -444,39 +572,33 @@ impl<'e, 'r, B: InternalParser<'e>> Walker<'e, 'r, B> {
   }
 }
 
-#[derive(Debug, Clone, Copy)]
-enum OpenDelimiter {
-  Vector,
-  List,
-  Map,
-  Hash,
-}
-
-// `Position`, wherever present, contains the start position of that context
-enum ContextKind<'e, B: InternalParser<'e>> {
+// `usize`, wherever present, contains the start offset of that context
+enum ContextKind<'e, B: Builder<'e>> {
   Top,
-  Vector(B::VectorContext, Position),
-  List(B::ListContext, Position),
-  Map(B::MapContext, Position),
-  Set(B::SetContext, Position),
-  Tag(&'e str, /* Span of the tag string */ Span, Position),
-  Discard(Position),
+  Vector(B::VectorContext, usize),
+  List(B::ListContext, usize),
+  Map(B::MapContext, usize),
+  Set(B::SetContext, usize),
+  Tag(&'e str, /* Span of the tag string */ Span, usize),
+  Discard(usize),
 }
 
-struct ParseContext<'e, B: InternalParser<'e>> {
+struct ParseContext<'e, B: Builder<'e>> {
   kind: ContextKind<'e, B>,
   discards: Vec<B::Discard>,
 }
 
-impl<'e, B: InternalParser<'e>> ParseContext<'e, B> {
+impl<'e, B: Builder<'e>> ParseContext<'e, B> {
   const fn no_discards(kind: ContextKind<'e, B>) -> Self {
     Self { kind, discards: Vec::new() }
   }
 }
 
-#[derive(Debug, Clone)]
+/// A form that is neither a collection nor tagged, handed to [`Builder::atom`]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(not(feature = "arbitrary-nums"), derive(Copy))]
-enum Atom<'e> {
+#[non_exhaustive]
+pub enum Atom<'e> {
   Key(&'e str),
   Symbol(&'e str),
   Str(&'e str),
@@ -493,16 +615,133 @@ enum Atom<'e> {
   Nil,
 }
 
-trait InternalParser<'e> {
+impl<'e> From<Atom<'e>> for Edn<'e> {
+  fn from(atom: Atom<'e>) -> Self {
+    match atom {
+      Atom::Key(key) => Edn::Key(key),
+      Atom::Symbol(symbol) => Edn::Symbol(symbol),
+      Atom::Str(str) => Edn::Str(str),
+      Atom::Int(int) => Edn::Int(int),
+      #[cfg(feature = "floats")]
+      Atom::Double(double) => Edn::Double(double),
+      Atom::Rational(rational) => Edn::Rational(rational),
+      #[cfg(feature = "arbitrary-nums")]
+      Atom::BigInt(big_int) => Edn::BigInt(big_int),
+      #[cfg(feature = "arbitrary-nums")]
+      Atom::BigDec(big_dec) => Edn::BigDec(big_dec),
+      Atom::Char(ch) => Edn::Char(ch),
+      Atom::Bool(bool) => Edn::Bool(bool),
+      Atom::Nil => Edn::Nil,
+    }
+  }
+}
+
+/// Builds a tree, or anything else, out of the forms the parser reads.
+///
+/// The parser calls [`Builder::atom`] for every atom, collects the items of a collection in its
+/// context and hands the context back to be finished once the collection is closed. Tagged and
+/// discarded forms are wrapped as soon as their value is complete. Implement it to parse straight
+/// into your own types with [`parse_with`]; the `Node` and [`Edn`] trees are built the same way.
+///
+/// Discards read before a form are passed along with it. Discards at the end of a collection are
+/// passed when it is finished, the ones at the end of the input are dropped.
+///
+/// # Examples
+///
+/// ```
+/// use clojure_reader::error::Error;
+/// use clojure_reader::builder::{Atom, Builder, Parsed, SourceReader, Span, parse_with};
+///
+/// /// Counts the keywords in a form without building anything
+/// struct Keywords;
+///
+/// impl<'e> Builder<'e> for Keywords {
+///   type Item = usize;
+///   type Discard = ();
+///   type VectorContext = usize;
+///   type ListContext = usize;
+///   type MapContext = usize;
+///   type SetContext = usize;
+///
+///   fn atom(&self, atom: Atom<'e>, _span: Span) -> usize {
+///     usize::from(matches!(atom, Atom::Key(_)))
+///   }
+///
+///   fn with_leading_discards(&self, item: usize, _discards: Vec<()>) -> usize {
+///     item
+///   }
+///
+///   fn new_vector_context(&self) -> usize { 0 }
+///   fn new_list_context(&self) -> usize { 0 }
+///   fn new_map_context(&self) -> usize { 0 }
+///   fn new_set_context(&self) -> usize { 0 }
+///
+///   fn add_to_vector(&self, ctx: &mut usize, parsed: Parsed<usize>, _: Vec<()>) -> Result<(), Error> {
+///     *ctx += parsed.item;
+///     Ok(())
+///   }
+///   fn add_to_list(&self, ctx: &mut usize, parsed: Parsed<usize>, _: Vec<()>) -> Result<(), Error> {
+///     *ctx += parsed.item;
+///     Ok(())
+///   }
+///   fn add_to_map(&self, ctx: &mut usize, parsed: Parsed<usize>, _: Vec<()>) -> Result<(), Error> {
+///     *ctx += parsed.item;
+///     Ok(())
+///   }
+///   fn add_to_set(&self, ctx: &mut usize, parsed: Parsed<usize>, _: Vec<()>) -> Result<(), Error> {
+///     *ctx += parsed.item;
+///     Ok(())
+///   }
+///
+///   fn finish_vector(&self, ctx: usize, _: Vec<()>, span: Span) -> Result<Parsed<usize>, Error> {
+///     Ok(Parsed::new(ctx, span))
+///   }
+///   fn finish_list(&self, ctx: usize, _: Vec<()>, span: Span) -> Result<Parsed<usize>, Error> {
+///     Ok(Parsed::new(ctx, span))
+///   }
+///   fn finish_set(
+///     &self, ctx: usize, _: Vec<()>, _validate: bool, span: Span,
+///   ) -> Result<Parsed<usize>, Error> {
+///     Ok(Parsed::new(ctx, span))
+///   }
+///   fn finish_map(
+///     &self, ctx: usize, _: Vec<()>, _validate: bool, _close_pos: usize, span: Span,
+///   ) -> Result<Parsed<usize>, Error> {
+///     Ok(Parsed::new(ctx, span))
+///   }
+///
+///   fn tag(
+///     &self, _tag: &'e str, _tag_span: Span, value: Parsed<usize>, _: Vec<()>, span: Span,
+///   ) -> Result<Parsed<usize>, Error> {
+///     Ok(Parsed::new(value.item, span))
+///   }
+///
+///   fn discard(&self, _value: Parsed<usize>, _: Vec<()>, _span: Span) {}
+/// }
+///
+/// let mut reader = SourceReader::new("{:a [:b 1 #_ :c] :d #inst \"2024\" #{:e}}");
+/// assert_eq!(parse_with(&mut reader, &Keywords).unwrap(), 4);
+/// ```
+pub trait Builder<'e> {
+  /// What every form is built into
   type Item;
+  /// What a discarded form is built into
   type Discard;
+  /// Collects the items of a vector until it is finished
   type VectorContext;
+  /// Collects the items of a list until it is finished
   type ListContext;
+  /// Collects the keys and values of a map, alternately, until it is finished
   type MapContext;
+  /// Collects the items of a set until it is finished
   type SetContext;
 
+  /// Builds an atom
   fn atom(&self, atom: Atom<'e>, span: Span) -> Self::Item;
 
+  /// Attaches the discards read before a form to it. The parser calls it for the top-level form,
+  /// every other form is handed to `add_to_*`, `tag` or `discard` with its leading discards, which
+  /// can call it for them too.
   fn with_leading_discards(
     &self,
     item: Self::Item,
@@ -517,6 +756,11 @@ trait InternalParser<'e> {
 
   fn new_set_context(&self) -> Self::SetContext;
 
+  /// Adds an item to a vector
+  ///
+  /// # Errors
+  ///
+  /// Any error stops the parse and is returned from it.
   fn add_to_vector(
     &self,
     ctx: &mut Self::VectorContext,
@@ -524,6 +768,11 @@ trait InternalParser<'e> {
     leading_discards: Vec<Self::Discard>,
   ) -> Result<(), Error>;
 
+  /// Adds an item to a list
+  ///
+  /// # Errors
+  ///
+  /// Any error stops the parse and is returned from it.
   fn add_to_list(
     &self,
     ctx: &mut Self::ListContext,
@@ -531,6 +780,11 @@ trait InternalParser<'e> {
     leading_discards: Vec<Self::Discard>,
   ) -> Result<(), Error>;
 
+  /// Adds a key, or the value of the last key, to a map
+  ///
+  /// # Errors
+  ///
+  /// Any error stops the parse and is returned from it.
   fn add_to_map(
     &self,
     ctx: &mut Self::MapContext,
@@ -538,6 +792,11 @@ trait InternalParser<'e> {
     leading_discards: Vec<Self::Discard>,
   ) -> Result<(), Error>;
 
+  /// Adds an item to a set
+  ///
+  /// # Errors
+  ///
+  /// Any error stops the parse and is returned from it.
   fn add_to_set(
     &self,
     ctx: &mut Self::SetContext,
@@ -545,6 +804,11 @@ trait InternalParser<'e> {
     leading_discards: Vec<Self::Discard>,
   ) -> Result<(), Error>;
 
+  /// Builds a vector once its `]` is read
+  ///
+  /// # Errors
+  ///
+  /// Any error stops the parse and is returned from it.
   fn finish_vector(
     &self,
     ctx: Self::VectorContext,
@@ -552,6 +816,12 @@ trait InternalParser<'e> {
     span: Span,
   ) -> Result<Parsed<Self::Item>, Error>;
 
+  /// Builds a set once its `}` is read. `validate` is false inside a discarded form, where
+  /// duplicate items are allowed.
+  ///
+  /// # Errors
+  ///
+  /// Any error stops the parse and is returned from it, usually [`Code::SetDuplicateKey`].
   fn finish_set(
     &self,
     ctx: Self::SetContext,
@@ -560,15 +830,27 @@ trait InternalParser<'e> {
     span: Span,
   ) -> Result<Parsed<Self::Item>, Error>;
 
+  /// Builds a map once its `}` is read, at `close_pos`. `validate` is false inside a discarded
+  /// form, where duplicate keys are allowed.
+  ///
+  /// # Errors
+  ///
+  /// Any error stops the parse and is returned from it, usually [`Code::HashMapDuplicateKey`], or
+  /// [`Code::UnexpectedEOF`] at `close_pos` for a key without a value.
   fn finish_map(
     &self,
     ctx: Self::MapContext,
     trailing_discards: Vec<Self::Discard>,
     validate: bool,
-    close_pos: Position,
+    close_pos: usize,
     span: Span,
   ) -> Result<Parsed<Self::Item>, Error>;
 
+  /// Builds a list once its `)` is read
+  ///
+  /// # Errors
+  ///
+  /// Any error stops the parse and is returned from it.
   fn finish_list(
     &self,
     ctx: Self::ListContext,
@@ -576,6 +858,11 @@ trait InternalParser<'e> {
     span: Span,
   ) -> Result<Parsed<Self::Item>, Error>;
 
+  /// Wraps a value in its tag. The tag hasn't been validated, see [`Code::InvalidTag`].
+  ///
+  /// # Errors
+  ///
+  /// Any error stops the parse and is returned from it.
   fn tag(
     &self,
     tag: &'e str,
@@ -585,6 +872,7 @@ trait InternalParser<'e> {
     span: Span,
   ) -> Result<Parsed<Self::Item>, Error>;
 
+  /// Builds a discarded form, `discard_span` covering the `#_` as well
   fn discard(
     &self,
     value: Parsed<Self::Item>,
@@ -592,6 +880,7 @@ trait InternalParser<'e> {
     discard_span: Span,
   ) -> Self::Discard;
 
+  /// Builds what an input without any forms reads as
   fn nil(&self, span: Span) -> Self::Item {
     self.atom(Atom::Nil, span)
   }
@@ -599,7 +888,7 @@ trait InternalParser<'e> {
 
 struct EdnBuilder;
 
-impl<'e> InternalParser<'e> for EdnBuilder {
+impl<'e> Builder<'e> for EdnBuilder {
   type Item = Edn<'e>;
   type Discard = ();
   type VectorContext = Vec<Edn<'e>>;
@@ -609,22 +898,7 @@ impl<'e> InternalParser<'e> for EdnBuilder {
 
   fn atom(&self, atom: Atom<'e>, span: Span) -> Self::Item {
     let _ = span;
-    match atom {
-      Atom::Key(key) => Edn::Key(key),
-      Atom::Symbol(symbol) => Edn::Symbol(symbol),
-      Atom::Str(str) => Edn::Str(str),
-      Atom::Int(int) => Edn::Int(int),
-      #[cfg(feature = "floats")]
-      Atom::Double(double) => Edn::Double(double),
-      Atom::Rational(rational) => Edn::Rational(rational),
-      #[cfg(feature = "arbitrary-nums")]
-      Atom::BigInt(big_int) => Edn::BigInt(big_int),
-      #[cfg(feature = "arbitrary-nums")]
-      Atom::BigDec(big_dec) => Edn::BigDec(big_dec),
-      Atom::Char(ch) => Edn::Char(ch),
-      Atom::Bool(bool) => Edn::Bool(bool),
-      Atom::Nil => Edn::Nil,
-    }
+    atom.into()
   }
 
   fn with_leading_discards(
@@ -712,10 +986,10 @@ impl<'e> InternalParser<'e> for EdnBuilder {
     validate: bool,
     span: Span,
   ) -> Result<Parsed<Self::Item>, Error> {
-    let mut set = BTreeSet::new();
+    let mut set = edn::Set::new();
     for item in ctx {
       if !set.insert(item.item) && validate {
-        return Err(Error::from_position(Code::SetDuplicateKey, item.span.1));
+        return Err(Error::from_offset(Code::SetDuplicateKey, item.span.1));
       }
     }
     Ok(Parsed::new(Edn::Set(set), span))
@@ -726,16 +1000,16 @@ impl<'e> InternalParser<'e> for EdnBuilder {
     ctx: Self::MapContext,
     _trailing_discards: Vec<Self::Discard>,
     validate: bool,
-    close_pos: Position,
+    close_pos: usize,
     span: Span,
   ) -> Result<Parsed<Self::Item>, Error> {
     if ctx.1.is_some() {
-      return Err(Error::from_position(Code::UnexpectedEOF, close_pos));
+      return Err(Error::from_offset(Code::UnexpectedEOF, close_pos));
     }
-    let mut map = BTreeMap::new();
+    let mut map = edn::Map::new();
     for (key, value) in ctx.0 {
       if map.insert(key.item, value.item).is_some() && validate {
-        return Err(Error::from_position(Code::HashMapDuplicateKey, value.span.1));
+        return Err(Error::from_offset(Code::HashMapDuplicateKey, value.span.1));
       }
     }
     Ok(Parsed::new(Edn::Map(map), span))
@@ -759,8 +1033,8 @@ impl<'e> InternalParser<'e> for EdnBuilder {
     span: Span,
   ) -> Result<Parsed<Self::Item>, Error> {
     crate::edn::validate_tag(tag, tag_span)?;
-    if tag.starts_with(':') && !matches!(&value.item, Edn::Map(_)) {
-      return Err(Error::from_position(Code::InvalidTag, tag_span.0));
+    if tag.starts_with(':') && !matches!(value.item, Edn::Map(_)) {
+      return Err(Error::from_offset(Code::InvalidTag, tag_span.0));
     }
     Ok(Parsed::new(Edn::Tagged(tag, Box::new(value.item)), span))
   }
@@ -774,9 +1048,190 @@ impl<'e> InternalParser<'e> for EdnBuilder {
   }
 }
 
+/// Builds an [`Edn`] like [`EdnBuilder`], checking that its namespaced maps can be expanded in
+/// the namespaces, with errors where the keys and items that'd end up the same are.
+struct NamespacedBuilder<'n>(&'n Namespaces<'n>);
+
+// A form, and for a map the keywords and symbols keying it with where they start, which a
+// namespaced map's tag needs to check them
+type Keyed<'e> = (Edn<'e>, Vec<(Edn<'e>, usize)>);
+
+// The form alone, for `EdnBuilder`
+fn unkeyed(Parsed { item: (edn, _), span }: Parsed<Keyed<'_>>) -> Parsed<Edn<'_>> {
+  Parsed::new(edn, span)
+}
+
+fn keyed(Parsed { item, span }: Parsed<Edn<'_>>) -> Parsed<Keyed<'_>> {
+  Parsed::new((item, Vec::new()), span)
+}
+
+impl<'e> Builder<'e> for NamespacedBuilder<'_> {
+  type Item = Keyed<'e>;
+  type Discard = ();
+  type VectorContext = <EdnBuilder as Builder<'e>>::VectorContext;
+  type ListContext = <EdnBuilder as Builder<'e>>::ListContext;
+  type MapContext = <EdnBuilder as Builder<'e>>::MapContext;
+  type SetContext = <EdnBuilder as Builder<'e>>::SetContext;
+
+  fn atom(&self, atom: Atom<'e>, span: Span) -> Self::Item {
+    (EdnBuilder.atom(atom, span), Vec::new())
+  }
+
+  fn with_leading_discards(
+    &self,
+    item: Self::Item,
+    _leading_discards: Vec<Self::Discard>,
+  ) -> Self::Item {
+    item
+  }
+
+  fn new_vector_context(&self) -> Self::VectorContext {
+    EdnBuilder.new_vector_context()
+  }
+
+  fn new_list_context(&self) -> Self::ListContext {
+    EdnBuilder.new_list_context()
+  }
+
+  fn new_map_context(&self) -> Self::MapContext {
+    EdnBuilder.new_map_context()
+  }
+
+  fn new_set_context(&self) -> Self::SetContext {
+    EdnBuilder.new_set_context()
+  }
+
+  fn add_to_vector(
+    &self,
+    ctx: &mut Self::VectorContext,
+    parsed: Parsed<Self::Item>,
+    leading_discards: Vec<Self::Discard>,
+  ) -> Result<(), Error> {
+    EdnBuilder.add_to_vector(ctx, unkeyed(parsed), leading_discards)
+  }
+
+  fn add_to_list(
+    &self,
+    ctx: &mut Self::ListContext,
+    parsed: Parsed<Self::Item>,
+    leading_discards: Vec<Self::Discard>,
+  ) -> Result<(), Error> {
+    EdnBuilder.add_to_list(ctx, unkeyed(parsed), leading_discards)
+  }
+
+  fn add_to_map(
+    &self,
+    ctx: &mut Self::MapContext,
+    parsed: Parsed<Self::Item>,
+    leading_discards: Vec<Self::Discard>,
+  ) -> Result<(), Error> {
+    EdnBuilder.add_to_map(ctx, unkeyed(parsed), leading_discards)
+  }
+
+  fn add_to_set(
+    &self,
+    ctx: &mut Self::SetContext,
+    parsed: Parsed<Self::Item>,
+    leading_discards: Vec<Self::Discard>,
+  ) -> Result<(), Error> {
+    EdnBuilder.add_to_set(ctx, unkeyed(parsed), leading_discards)
+  }
+
+  fn finish_vector(
+    &self,
+    ctx: Self::VectorContext,
+    trailing_discards: Vec<Self::Discard>,
+    span: Span,
+  ) -> Result<Parsed<Self::Item>, Error> {
+    EdnBuilder.finish_vector(ctx, trailing_discards, span).map(keyed)
+  }
+
+  fn finish_set(
+    &self,
+    ctx: Self::SetContext,
+    trailing_discards: Vec<Self::Discard>,
+    validate: bool,
+    span: Span,
+  ) -> Result<Parsed<Self::Item>, Error> {
+    if validate {
+      let items: Vec<_> = ctx.iter().map(|item| (&item.item, item.span.0)).collect();
+      if let Some(start) = self.0.first_duplicate(&items) {
+        return Err(Error::from_offset(Code::SetDuplicateKey, start));
+      }
+    }
+    EdnBuilder.finish_set(ctx, trailing_discards, validate, span).map(keyed)
+  }
+
+  fn finish_map(
+    &self,
+    ctx: Self::MapContext,
+    trailing_discards: Vec<Self::Discard>,
+    validate: bool,
+    close_pos: usize,
+    span: Span,
+  ) -> Result<Parsed<Self::Item>, Error> {
+    if validate && ctx.1.is_none() {
+      let keys: Vec<_> = ctx.0.iter().map(|(key, _)| (&key.item, key.span.0)).collect();
+      if let Some(start) = self.0.first_duplicate(&keys) {
+        return Err(Error::from_offset(Code::HashMapDuplicateKey, start));
+      }
+    }
+    let names = ctx.0.iter().filter(|(key, _)| matches!(key.item, Edn::Key(_) | Edn::Symbol(_)));
+    let names = names.map(|(key, _)| (key.item.clone(), key.span.0)).collect();
+    let Parsed { item, span } =
+      EdnBuilder.finish_map(ctx, trailing_discards, validate, close_pos, span)?;
+    Ok(Parsed::new((item, names), span))
+  }
+
+  fn finish_list(
+    &self,
+    ctx: Self::ListContext,
+    trailing_discards: Vec<Self::Discard>,
+    span: Span,
+  ) -> Result<Parsed<Self::Item>, Error> {
+    EdnBuilder.finish_list(ctx, trailing_discards, span).map(keyed)
+  }
+
+  fn tag(
+    &self,
+    tag: &'e str,
+    tag_span: Span,
+    value: Parsed<Self::Item>,
+    leading_discards: Vec<Self::Discard>,
+    span: Span,
+  ) -> Result<Parsed<Self::Item>, Error> {
+    let Some(namespace) = self.0.resolve(tag) else {
+      if tag.starts_with("::") {
+        return Err(Error::from_offset(Code::InvalidTag, tag_span.0));
+      }
+      return EdnBuilder.tag(tag, tag_span, unkeyed(value), leading_discards, span).map(keyed);
+    };
+    let Parsed { item: (edn, names), .. } = value;
+    if !matches!(edn, Edn::Map(_)) {
+      return Err(Error::from_offset(Code::InvalidTag, tag_span.0));
+    }
+    // Keys in the namespace of the map that end up the same as one before them
+    let mut seen = BTreeSet::new();
+    for (name, start) in &names {
+      if edn::qualified_key(namespace, name).is_some_and(|key| !seen.insert(key)) {
+        return Err(Error::from_offset(Code::HashMapDuplicateKey, *start));
+      }
+    }
+    Ok(Parsed::new((Edn::Tagged(tag, Box::new(edn)), Vec::new()), span))
+  }
+
+  fn discard(
+    &self,
+    _value: Parsed<Self::Item>,
+    _leading_discards: Vec<Self::Discard>,
+    _discard_span: Span,
+  ) -> Self::Discard {
+  }
+}
+
 struct NodeBuilder;
 
-impl<'e> InternalParser<'e> for NodeBuilder {
+impl<'e> Builder<'e> for NodeBuilder {
   type Item = Node<'e>;
   type Discard = Discard<'e>;
   type VectorContext = Vec<Node<'e>>;
@@ -899,11 +1354,11 @@ impl<'e> InternalParser<'e> for NodeBuilder {
     ctx: Self::MapContext,
     trailing_discards: Vec<Self::Discard>,
     _validate: bool,
-    close_pos: Position,
+    close_pos: usize,
     span: Span,
   ) -> Result<Parsed<Self::Item>, Error> {
     if ctx.1.is_some() {
-      return Err(Error::from_position(Code::UnexpectedEOF, close_pos));
+      return Err(Error::from_offset(Code::UnexpectedEOF, close_pos));
     }
     Ok(Parsed::new(Node::no_discards(NodeKind::Map(ctx.0, trailing_discards), span), span))
   }
@@ -935,7 +1390,7 @@ impl<'e> InternalParser<'e> for NodeBuilder {
     leading_discards: Vec<Self::Discard>,
     discard_span: Span,
   ) -> Self::Discard {
-    Discard(self.with_leading_discards(value.item, leading_discards), discard_span)
+    Discard(self.with_leading_discards(value.item, leading_discards), discard_span, Vec::new())
   }
 }
 
@@ -945,7 +1400,7 @@ const fn take_discards<D>(discards: &mut Vec<D>) -> Vec<D> {
 }
 
 #[inline]
-fn add_to_context<'e, B: InternalParser<'e>>(
+fn add_to_context<'e, B: Builder<'e>>(
   context: &mut Option<&mut ParseContext<'e, B>>,
   builder: &B,
   parsed: Parsed<B::Item>,
@@ -969,69 +1424,22 @@ fn add_to_context<'e, B: InternalParser<'e>>(
 }
 
 #[inline]
-fn handle_open_delimiter<'e, B: InternalParser<'e>>(
+fn handle_open_delimiter<'e, B: Builder<'e>>(
   walker: &mut Walker<'e, '_, B>,
   builder: &B,
-  delim: OpenDelimiter,
-) -> Result<(), Error> {
-  let pos_start = walker.pos();
-  match delim {
-    OpenDelimiter::Vector => {
-      let _ = walker.reader.nibble_next();
-      walker.push_context(ParseContext::no_discards(ContextKind::Vector(
-        builder.new_vector_context(),
-        pos_start,
-      )));
-    }
-    OpenDelimiter::List => {
-      let _ = walker.reader.nibble_next();
-      walker.push_context(ParseContext::no_discards(ContextKind::List(
-        builder.new_list_context(),
-        pos_start,
-      )));
-    }
-    OpenDelimiter::Map => {
-      let _ = walker.reader.nibble_next();
-      walker.push_context(ParseContext::no_discards(ContextKind::Map(
-        builder.new_map_context(),
-        pos_start,
-      )));
-    }
-    OpenDelimiter::Hash => {
-      let _ = walker.reader.nibble_next();
-      match walker.reader.peek_next() {
-        Some('{') => {
-          let _ = walker.reader.nibble_next();
-          walker.push_context(ParseContext::no_discards(ContextKind::Set(
-            builder.new_set_context(),
-            pos_start,
-          )));
-        }
-        Some('_') => {
-          let _ = walker.reader.nibble_next();
-          walker.push_context(ParseContext::no_discards(ContextKind::Discard(pos_start)));
-        }
-        Some(c) if !c.is_whitespace() && !DELIMITERS.contains(&c) => {
-          let tag_pos_start = walker.pos();
-          let tag = walker.reader.slurp_tag()?;
-          let tag_span = walker.span_from(tag_pos_start);
-          if tag.is_empty() {
-            return Err(walker.make_error(Code::InvalidTag));
-          }
-
-          walker.reader.nibble_whitespace();
-          walker
-            .push_context(ParseContext::no_discards(ContextKind::Tag(tag, tag_span, pos_start)));
-        }
-        Some(_) => return Err(walker.make_error(Code::InvalidTag)),
-        None => return Err(walker.make_error(Code::UnexpectedEOF)),
-      }
-    }
-  }
-  Ok(())
+  open: Open,
+  pos_start: usize,
+) {
+  let kind = match open {
+    Open::Vector => ContextKind::Vector(builder.new_vector_context(), pos_start),
+    Open::List => ContextKind::List(builder.new_list_context(), pos_start),
+    Open::Map => ContextKind::Map(builder.new_map_context(), pos_start),
+    Open::Set => ContextKind::Set(builder.new_set_context(), pos_start),
+  };
+  walker.push_context(ParseContext::no_discards(kind));
 }
 
-fn wrap_pending_tags<'e, 'r, B: InternalParser<'e>>(
+fn wrap_pending_tags<'e, 'r, B: Builder<'e>>(
   walker: &mut Walker<'e, 'r, B>,
   builder: &B,
   mut parsed: Parsed<B::Item>,
@@ -1049,11 +1457,11 @@ fn wrap_pending_tags<'e, 'r, B: InternalParser<'e>>(
   Ok(parsed)
 }
 
-fn under_discard<'e, B: InternalParser<'e>>(walker: &Walker<'e, '_, B>) -> bool {
+fn under_discard<'e, B: Builder<'e>>(walker: &Walker<'e, '_, B>) -> bool {
   walker.stack.iter().any(|ctx| matches!(ctx.kind, ContextKind::Discard(..)))
 }
 
-fn complete_value<'e, 'r, B: InternalParser<'e>>(
+fn complete_value<'e, 'r, B: Builder<'e>>(
   walker: &mut Walker<'e, 'r, B>,
   builder: &B,
   parsed: Parsed<B::Item>,
@@ -1082,13 +1490,15 @@ fn complete_value<'e, 'r, B: InternalParser<'e>>(
 }
 
 #[inline]
-fn handle_close_delimiter<'e, B: InternalParser<'e>>(
+fn handle_close_delimiter<'e, B: Builder<'e>>(
   walker: &mut Walker<'e, '_, B>,
   builder: &B,
   delimiter: char,
+  close_pos: usize,
 ) -> Result<Option<B::Item>, Error> {
+  let unmatched = || Error::from_offset(Code::UnmatchedDelimiter(delimiter), close_pos);
   if walker.stack_len() <= 1 {
-    return Err(walker.make_error(Code::UnmatchedDelimiter(delimiter)));
+    return Err(unmatched());
   }
 
   let expected = match walker.stack.last().expect("Len > 1 is never empty") {
@@ -1096,86 +1506,100 @@ fn handle_close_delimiter<'e, B: InternalParser<'e>>(
     ParseContext { kind: ContextKind::List(..), .. } => ')',
     ParseContext { kind: ContextKind::Map(..) | ContextKind::Set(..), .. } => '}',
     _ => {
-      return Err(walker.make_error(Code::UnmatchedDelimiter(delimiter)));
+      return Err(unmatched());
     }
   };
 
   if delimiter != expected {
-    return Err(walker.make_error(Code::UnmatchedDelimiter(delimiter)));
+    return Err(unmatched());
   }
 
   let parsed = match walker.pop_context() {
     Some(ParseContext { kind: ContextKind::Vector(ctx, pos_start), discards }) => {
-      let _ = walker.reader.nibble_next();
       builder.finish_vector(ctx, discards, walker.span_from(pos_start))?
     }
     Some(ParseContext { kind: ContextKind::List(ctx, pos_start), discards }) => {
-      let _ = walker.reader.nibble_next();
       builder.finish_list(ctx, discards, walker.span_from(pos_start))?
     }
     Some(ParseContext { kind: ContextKind::Map(ctx, pos_start), discards }) => {
       let validate = !under_discard(walker);
-      let close_pos = walker.pos();
-      let _ = walker.reader.nibble_next();
       builder.finish_map(ctx, discards, validate, close_pos, walker.span_from(pos_start))?
     }
     Some(ParseContext { kind: ContextKind::Set(ctx, pos_start), discards }) => {
       let validate = !under_discard(walker);
-      let _ = walker.reader.nibble_next();
       builder.finish_set(ctx, discards, validate, walker.span_from(pos_start))?
     }
     _ => {
-      return Err(walker.make_error(Code::UnmatchedDelimiter(delimiter)));
+      return Err(unmatched());
     }
   };
 
   complete_value(walker, builder, parsed)
 }
 
-fn parse_internal<'e, B: InternalParser<'e>>(
+fn parse_internal<'e, B: Builder<'e>>(
   walker: &mut Walker<'e, '_, B>,
   builder: &B,
 ) -> Result<Option<B::Item>, Error> {
-  let mut result = None;
   loop {
-    walker.reader.nibble_whitespace();
-    match walker.reader.peek_next() {
-      Some(';') => walker.reader.nibble_newline(),
-      Some('[') => handle_open_delimiter(walker, builder, OpenDelimiter::Vector)?,
-      Some('(') => handle_open_delimiter(walker, builder, OpenDelimiter::List)?,
-      Some('{') => handle_open_delimiter(walker, builder, OpenDelimiter::Map)?,
-      Some('#') => handle_open_delimiter(walker, builder, OpenDelimiter::Hash)?,
-      Some(d) if matches!(d, ']' | ')' | '}') => {
-        if let Some(parsed) = handle_close_delimiter(walker, builder, d)? {
-          result = Some(parsed);
-          break;
-        }
+    let Some((pos_start, token)) = walker.reader.next_token()? else {
+      if walker.stack_len() > 1 {
+        return Err(walker.make_error(Code::UnexpectedEOF));
       }
-      Some(c) => {
-        let pos_start = walker.reader.read_pos;
-        let atom = match c {
-          '\\' => parse_char(walker.reader.slurp_char()).map(Atom::Char),
-          '"' => Ok(Atom::Str(walker.reader.slurp_str()?)),
-          _ => edn_literal(walker.reader.slurp_literal()),
-        }
-        .map_err(|code| Error::from_position(code, pos_start))?;
-        let span = walker.reader.span_from(pos_start);
-        let parsed = Parsed::new(builder.atom(atom, span), span);
-
-        if let Some(parsed) = complete_value(walker, builder, parsed)? {
-          result = Some(parsed);
-          break;
-        }
+      return Ok(None);
+    };
+    let parsed = match token {
+      Token::Open(open) => {
+        handle_open_delimiter(walker, builder, open, pos_start);
+        None
       }
-      None => {
-        if walker.stack_len() > 1 {
-          return Err(walker.make_error(Code::UnexpectedEOF));
-        }
-        break;
+      Token::Close(delimiter) => handle_close_delimiter(walker, builder, delimiter, pos_start)?,
+      Token::Tag(tag, tag_span) => {
+        walker.push_context(ParseContext::no_discards(ContextKind::Tag(tag, tag_span, pos_start)));
+        None
+      }
+      Token::Discard => {
+        walker.push_context(ParseContext::no_discards(ContextKind::Discard(pos_start)));
+        None
       }
+      Token::Atom(atom) => {
+        let span = walker.span_from(pos_start);
+        complete_value(walker, builder, Parsed::new(builder.atom(atom, span), span))?
+      }
+    };
+    if parsed.is_some() {
+      return Ok(parsed);
     }
   }
-  Ok(result)
+}
+
+/// Reads the next form and throws it away, the way `#_` does. It has to be valid EDN, but
+/// duplicate keys aren't checked.
+///
+/// # Errors
+///
+/// See [`crate::error::Error`].
+#[cfg(feature = "serde")]
+#[cfg_attr(not(feature = "unstable"), expect(clippy::redundant_pub_crate))]
+pub(crate) fn discard_form(reader: &mut SourceReader<'_>) -> Result<(), Error> {
+  let mut walker = Walker::new(reader);
+  walker.stack = alloc::vec![ParseContext::no_discards(ContextKind::Discard(walker.pos()))];
+  match parse_internal(&mut walker, &EdnBuilder)? {
+    Some(_) => Ok(()),
+    None => Err(walker.make_error(Code::UnexpectedEOF)),
+  }
+}
+
+/// Reads the next form from wherever the reader is.
+///
+/// # Errors
+///
+/// See [`crate::error::Error`].
+#[cfg(feature = "serde")]
+#[cfg_attr(not(feature = "unstable"), expect(clippy::redundant_pub_crate))]
+pub(crate) fn read_form<'e>(reader: &mut SourceReader<'e>) -> Result<Edn<'e>, Error> {
+  let mut walker = Walker::new(reader);
+  parse_internal(&mut walker, &EdnBuilder)?.ok_or_else(|| walker.make_error(Code::UnexpectedEOF))
 }
 
 #[inline]
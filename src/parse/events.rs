@@ -0,0 +1,201 @@
+//! A pull parser reading EDN one event at a time, without building a tree.
+
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+use crate::edn::validate_tag;
+use crate::error::{Code, Error};
+
+use super::{Atom, Open, SourceReader, Span, Token};
+
+/// What [`Events`] read next
+///
+/// Every event comes with a span. Starts, tags and discards cover what was read for them, `#` and
+/// all, and an [`Event::End`] covers the whole collection it ends.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Event<'e> {
+  StartVector,
+  StartList,
+  StartMap,
+  StartSet,
+  /// Ends the collection started last
+  End,
+  /// Tags the form that follows
+  Tag(&'e str),
+  /// Discards the form that follows, `#_`
+  Discard,
+  Atom(Atom<'e>),
+}
+
+// What the forms read next belong to, `usize` being where it starts
+enum Frame {
+  Vector(usize),
+  List(usize),
+  Set(usize),
+  // Whether a key is waiting for its value
+  Map(usize, bool),
+  // Namespaced map tags have to be followed by a map, keeps where the tag starts if so
+  Tag(Option<usize>),
+  Discard,
+}
+
+/// Reads the forms of a [`SourceReader`] as a stream of [`Event`]s, until the input ends.
+///
+/// Delimiters, tags and maps are validated as they are read, but nothing is kept around to check
+/// for duplicate keys, so they are allowed. Use [`Events::skip_form`] to pass over a form you aren't
+/// interested in. After an error the iterator ends.
+///
+/// # Examples
+///
+/// ```
+/// #[cfg(feature = "unstable")]
+/// {
+///   use clojure_reader::parse::{Atom, Event, Events, SourceReader, Span};
+///
+///   let mut reader = SourceReader::new(r#"{:deps {big/lib {:mvn/version "1.0"}} :version "2.1"}"#);
+///   let mut events = Events::new(&mut reader);
+///   assert_eq!(events.next().unwrap().unwrap(), (Event::StartMap, Span(0, 1)));
+///
+///   let mut version = None;
+///   while let Some((Event::Atom(key), _)) = events.next().transpose().unwrap() {
+///     let (value, span) = events.next().unwrap().unwrap();
+///     match value {
+///       Event::Atom(Atom::Str(value)) if key == Atom::Key("version") => version = Some((value, span)),
+///       // Without reading what's inside
+///       _ => events.skip_form().unwrap(),
+///     }
+///   }
+///   assert_eq!(version, Some(("2.1", Span(47, 52))));
+/// }
+/// ```
+pub struct Events<'r, 'e> {
+  reader: &'r mut SourceReader<'e>,
+  stack: Vec<Frame>,
+  // How deep the last event was read, to know what to skip
+  depth: usize,
+  failed: bool,
+}
+
+impl<'r, 'e> Events<'r, 'e> {
+  pub const fn new(reader: &'r mut SourceReader<'e>) -> Self {
+    Self { reader, stack: Vec::new(), depth: 0, failed: false }
+  }
+
+  /// Skips the rest of the form the last event started: what's left of a collection, or the form
+  /// after a tag or a discard. Does nothing after an atom or an [`Event::End`].
+  ///
+  /// # Errors
+  ///
+  /// See [`crate::error::Error`].
+  pub fn skip_form(&mut self) -> Result<(), Error> {
+    let depth = self.depth;
+    while self.stack.len() > depth {
+      match self.next() {
+        Some(Ok(_)) => {}
+        Some(Err(err)) => return Err(err),
+        None => break,
+      }
+    }
+    Ok(())
+  }
+
+  fn next_event(&mut self) -> Result<Option<(Event<'e>, Span)>, Error> {
+    let Some((start, token)) = self.reader.next_token()? else {
+      if self.stack.is_empty() {
+        return Ok(None);
+      }
+      return Err(Error::from_offset(Code::UnexpectedEOF, self.reader.read_pos));
+    };
+
+    let event = match token {
+      Token::Open(Open::Vector) => {
+        self.stack.push(Frame::Vector(start));
+        Event::StartVector
+      }
+      Token::Open(Open::List) => {
+        self.stack.push(Frame::List(start));
+        Event::StartList
+      }
+      Token::Open(Open::Map) => {
+        self.stack.push(Frame::Map(start, false));
+        Event::StartMap
+      }
+      Token::Open(Open::Set) => {
+        self.stack.push(Frame::Set(start));
+        Event::StartSet
+      }
+      Token::Close(close) => {
+        let unmatched = Error::from_offset(Code::UnmatchedDelimiter(close), start);
+        let (expected, open_start) = match self.stack.last() {
+          Some(Frame::Vector(open_start)) => (']', *open_start),
+          Some(Frame::List(open_start)) => (')', *open_start),
+          Some(Frame::Set(open_start) | Frame::Map(open_start, _)) => ('}', *open_start),
+          _ => return Err(unmatched),
+        };
+        if close != expected {
+          return Err(unmatched);
+        }
+        let is_map = match self.stack.pop() {
+          // A key without a value
+          Some(Frame::Map(_, true)) => return Err(Error::from_offset(Code::UnexpectedEOF, start)),
+          frame => matches!(frame, Some(Frame::Map(..))),
+        };
+        self.complete(is_map)?;
+        return Ok(Some((Event::End, self.reader.span_from(open_start))));
+      }
+      Token::Tag(tag, tag_span) => {
+        validate_tag(tag, tag_span)?;
+        self.stack.push(Frame::Tag(tag.starts_with(':').then_some(tag_span.0)));
+        Event::Tag(tag)
+      }
+      Token::Discard => {
+        self.stack.push(Frame::Discard);
+        Event::Discard
+      }
+      Token::Atom(atom) => {
+        self.complete(false)?;
+        Event::Atom(atom)
+      }
+    };
+    Ok(Some((event, self.reader.span_from(start))))
+  }
+
+  // A form is complete, pops the tags and the discard it completes
+  fn complete(&mut self, mut is_map: bool) -> Result<(), Error> {
+    while let Some(Frame::Tag(namespaced)) = self.stack.last() {
+      if let Some(tag_start) = *namespaced
+        && !is_map
+      {
+        return Err(Error::from_offset(Code::InvalidTag, tag_start));
+      }
+      // It's a tagged form now
+      is_map = false;
+      self.stack.pop();
+    }
+    match self.stack.last_mut() {
+      Some(Frame::Discard) => {
+        self.stack.pop();
+      }
+      Some(Frame::Map(_, pending)) => *pending = !*pending,
+      _ => {}
+    }
+    Ok(())
+  }
+}
+
+impl<'e> Iterator for Events<'_, 'e> {
+  type Item = Result<(Event<'e>, Span), Error>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.failed {
+      return None;
+    }
+    self.depth = self.stack.len();
+    let next = self.next_event().map_err(|err| err.with_source(self.reader.slice)).transpose();
+    self.failed = matches!(next, Some(Err(_)));
+    next
+  }
+}
+
+impl FusedIterator for Events<'_, '_> {}
@@ -0,0 +1,438 @@
+//! Formatting source the way cljfmt does.
+
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::{iter, mem};
+
+use crate::error::{Code, Error};
+
+use super::{Discard, Node, NodeKind, SourceReader, Trivia, parse_with_trivia};
+
+/// How the lines of a list starting with a given symbol are indented, see [`Formatter::indent`].
+///
+/// Lists without any are indented like function calls: lines line up with the first argument if
+/// it's on the same line as the symbol, or one space in otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Indent {
+  /// Indents two spaces once the first `n` arguments are done, like the body of a `let` after
+  /// its bindings. Arguments started on the first line keep lining up with the first one.
+  Block(usize),
+  /// Indents two spaces in lists that many levels down, `0` being the list itself, like the body
+  /// of a `defn`. Methods in a `reify` are `1` down.
+  Inner(usize),
+}
+
+/// Formats EDN and Clojure source following cljfmt's core rules.
+///
+/// - Lines are indented according to the collections they're in, and the [`Indent`]s of lists.
+/// - Whitespace is removed after opening and before closing delimiters, and at the end of lines.
+/// - Forms right next to each other, like `(f(g))`, are separated with a space.
+/// - No more than [`Formatter::max_blank_lines`] blank lines are kept in a row.
+///
+/// Everything else is left as it was: comments, discards, commas and the spelling of atoms, as
+/// well as how the source is broken into lines. Formatting formatted source changes nothing.
+///
+/// # Examples
+///
+/// ```
+/// #[cfg(feature = "unstable")]
+/// {
+///   use clojure_reader::parse::{Formatter, Indent};
+///
+///   let source = "(defn greet [name]\n(let [greeting \"Hello\"] ; says hi\n(str greeting  \", \"\nname)))\n";
+///   assert_eq!(
+///     Formatter::default().format(source).unwrap(),
+///     "(defn greet [name]\n  (let [greeting \"Hello\"] ; says hi\n    (str greeting  \", \"\n         name)))\n"
+///   );
+///
+///   let formatter = Formatter::default().indent("my/with-retries", &[Indent::Block(1)]);
+///   assert_eq!(
+///     formatter.format("(my/with-retries 3\n(fetch))").unwrap(),
+///     "(my/with-retries 3\n  (fetch))"
+///   );
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Formatter {
+  indents: BTreeMap<String, Vec<Indent>>,
+  max_blank_lines: usize,
+}
+
+impl Default for Formatter {
+  /// A formatter with cljfmt's indents for `clojure.core` and `clojure.test`, and at most two
+  /// blank lines in a row.
+  fn default() -> Self {
+    use Indent::{Block, Inner};
+
+    let indents = [
+      ("as->", &[Block(2)][..]),
+      ("binding", &[Block(1)]),
+      ("bound-fn", &[Inner(0)]),
+      ("case", &[Block(1)]),
+      ("catch", &[Block(2)]),
+      ("comment", &[Block(0)]),
+      ("cond", &[Block(0)]),
+      ("cond->", &[Block(1)]),
+      ("cond->>", &[Block(1)]),
+      ("condp", &[Block(2)]),
+      ("def", &[Inner(0)]),
+      ("definterface", &[Block(1), Inner(1)]),
+      ("defmacro", &[Inner(0)]),
+      ("defmethod", &[Inner(0)]),
+      ("defmulti", &[Inner(0)]),
+      ("defn", &[Inner(0)]),
+      ("defn-", &[Inner(0)]),
+      ("defonce", &[Inner(0)]),
+      ("defprotocol", &[Block(1), Inner(1)]),
+      ("defrecord", &[Block(2), Inner(1)]),
+      ("defstruct", &[Block(1)]),
+      ("deftest", &[Inner(0)]),
+      ("deftype", &[Block(2), Inner(1)]),
+      ("do", &[Block(0)]),
+      ("doseq", &[Block(1)]),
+      ("dotimes", &[Block(1)]),
+      ("doto", &[Block(1)]),
+      ("extend", &[Block(1)]),
+      ("extend-protocol", &[Block(1), Inner(1)]),
+      ("extend-type", &[Block(1), Inner(1)]),
+      ("finally", &[Block(0)]),
+      ("fn", &[Inner(0)]),
+      ("for", &[Block(1)]),
+      ("future", &[Block(0)]),
+      ("if", &[Block(1)]),
+      ("if-let", &[Block(1)]),
+      ("if-not", &[Block(1)]),
+      ("if-some", &[Block(1)]),
+      ("let", &[Block(1)]),
+      ("letfn", &[Block(1)]),
+      ("locking", &[Block(1)]),
+      ("loop", &[Block(1)]),
+      ("ns", &[Block(1)]),
+      ("proxy", &[Block(2), Inner(1)]),
+      ("reify", &[Inner(0), Inner(1)]),
+      ("struct-map", &[Block(1)]),
+      ("testing", &[Block(1)]),
+      ("try", &[Block(0)]),
+      ("use-fixtures", &[Inner(0)]),
+      ("when", &[Block(1)]),
+      ("when-first", &[Block(1)]),
+      ("when-let", &[Block(1)]),
+      ("when-not", &[Block(1)]),
+      ("when-some", &[Block(1)]),
+      ("while", &[Block(1)]),
+      ("with-local-vars", &[Block(1)]),
+      ("with-open", &[Block(1)]),
+      ("with-out-str", &[Block(0)]),
+      ("with-precision", &[Block(1)]),
+      ("with-redefs", &[Block(1)]),
+    ];
+    Self {
+      indents: indents
+        .into_iter()
+        .map(|(symbol, indents)| (symbol.to_string(), indents.to_vec()))
+        .collect(),
+      max_blank_lines: 2,
+    }
+  }
+}
+
+impl Formatter {
+  /// Sets how lists starting with `symbol` are indented, replacing what was set for it.
+  ///
+  /// Qualified symbols fall back to the indents of their name, so `clojure.core/let` is indented
+  /// like `let` unless it has its own.
+  #[must_use]
+  pub fn indent(mut self, symbol: &str, indents: &[Indent]) -> Self {
+    self.indents.insert(symbol.to_string(), indents.to_vec());
+    self
+  }
+
+  /// Sets how many blank lines are kept in a row.
+  #[must_use]
+  pub const fn max_blank_lines(mut self, max_blank_lines: usize) -> Self {
+    self.max_blank_lines = max_blank_lines;
+    self
+  }
+
+  /// Formats every form of `source`.
+  ///
+  /// # Errors
+  ///
+  /// See [`crate::error::Error`].
+  pub fn format(&self, source: &str) -> Result<String, Error> {
+    let mut writer = Writer {
+      formatter: self,
+      source,
+      out: String::new(),
+      column: 0,
+      line_start: true,
+      heads: Vec::new(),
+    };
+    let mut reader = SourceReader::new(source);
+    let mut place = Place::Start;
+    let (mut discards, mut trivia) = (Vec::new(), Vec::new());
+    while let Some(node) = next_form(&mut reader, &mut discards, &mut trivia)? {
+      writer.node(&node, place, 0);
+      place = Place::Between;
+    }
+    writer.end(&discards, &trivia, place, 0);
+    // The last line still ends like it did
+    if source.ends_with('\n') && !writer.out.is_empty() && !writer.out.ends_with('\n') {
+      writer.out.push('\n');
+    }
+    Ok(writer.out)
+  }
+
+  fn indents(&self, symbol: &str) -> &[Indent] {
+    let name = symbol.split_once('/').map_or(symbol, |(_, name)| name);
+    self.indents.get(symbol).or_else(|| self.indents.get(name)).map_or(&[], Vec::as_slice)
+  }
+}
+
+// Reads the discards and trivia before the next form, then the form with them if the input
+// doesn't end first
+pub(super) fn next_form<'e>(
+  reader: &mut SourceReader<'e>,
+  discards: &mut Vec<Discard<'e>>,
+  trivia: &mut Vec<Trivia<'e>>,
+) -> Result<Option<Node<'e>>, Error> {
+  loop {
+    trivia.extend(iter::from_fn(|| reader.next_trivia()));
+    if !reader.remaining().starts_with("#_") {
+      break;
+    }
+    let start = reader.read_pos;
+    reader.read_pos += 2;
+    let Some(node) = next_form(reader, &mut Vec::new(), &mut Vec::new())? else {
+      let err = Error::from_offset(Code::UnexpectedEOF, reader.read_pos);
+      return Err(err.with_source(reader.slice));
+    };
+    discards.push(Discard(node, reader.span_from(start), mem::take(trivia)));
+  }
+  if reader.remaining().is_empty() {
+    return Ok(None);
+  }
+  let mut node = parse_with_trivia(reader)?;
+  node.leading_discards = mem::take(discards);
+  node.leading_trivia = mem::take(trivia);
+  Ok(Some(node))
+}
+
+// Where a gap between forms is
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Place {
+  // Right after an opening delimiter or at the start of the input
+  Start,
+  // Between forms of a collection or of the input
+  Between,
+  // Inside a discard or a tagged form, after the `#_` or the tag
+  Inside,
+}
+
+// Where something started in the output
+#[derive(Clone, Copy)]
+struct Mark {
+  column: usize,
+  // Whether it started a line
+  line_start: bool,
+}
+
+// The collection forms are being written in
+struct Coll {
+  // Where its forms are, the column after the opening delimiter
+  column: usize,
+  list: bool,
+  elements: Vec<Mark>,
+}
+
+struct Writer<'f, 'e> {
+  formatter: &'f Formatter,
+  source: &'e str,
+  out: String,
+  column: usize,
+  // Whether only indentation is on the current line
+  line_start: bool,
+  // The symbol each collection being written starts with, for lists
+  heads: Vec<Option<&'e str>>,
+}
+
+impl<'e> Writer<'_, 'e> {
+  fn write(&mut self, text: &str) {
+    if let Some((_, line)) = text.rsplit_once('\n') {
+      self.column = line.chars().count();
+      self.line_start = line.bytes().all(|b| b == b' ');
+    } else {
+      self.column += text.chars().count();
+      self.line_start &= text.bytes().all(|b| b == b' ');
+    }
+    self.out.push_str(text);
+  }
+
+  const fn mark(&self) -> Mark {
+    Mark { column: self.column, line_start: self.line_start }
+  }
+
+  fn newlines(&mut self, newlines: usize, indent: usize) {
+    self.out.extend(iter::repeat_n('\n', newlines.min(self.formatter.max_blank_lines + 1)));
+    self.out.extend(iter::repeat_n(' ', indent));
+    self.column = indent;
+    self.line_start = true;
+  }
+
+  // Writes the trivia between forms, `end` if a closing delimiter or the end of the input follows
+  fn gap(&mut self, trivia: &[Trivia<'_>], place: Place, end: bool, indent: usize) {
+    if trivia.is_empty() && place == Place::Between && !end {
+      return self.write(" ");
+    }
+    let mut after_comment = false;
+    for (i, item) in trivia.iter().enumerate() {
+      let space = match item {
+        Trivia::Comment(comment) => {
+          self.write(comment.trim_end());
+          after_comment = true;
+          continue;
+        }
+        Trivia::Whitespace(space) => space,
+      };
+      let last = i + 1 == trivia.len();
+      let newlines = space.matches('\n').count();
+      // Hugging the delimiters, unless a comment has to end its line
+      if (place == Place::Start && i == 0) || (end && last && !after_comment) {
+        continue;
+      }
+      if newlines == 0 {
+        self.write(space);
+      } else if end && last {
+        self.newlines(1, indent);
+      } else {
+        // Commas are kept, but not trailing whitespace
+        let (first, _) = space.split_once('\n').unwrap_or_default();
+        self.write(first.trim_end());
+        self.newlines(newlines, indent);
+      }
+      after_comment = false;
+    }
+  }
+
+  fn discard(
+    &mut self,
+    Discard(node, _, trivia): &Discard<'e>,
+    place: Place,
+    indent: usize,
+  ) -> Mark {
+    self.gap(trivia, place, false, indent);
+    let mark = self.mark();
+    self.write("#_");
+    self.node(node, Place::Inside, mark.column);
+    mark
+  }
+
+  // Writes the trailing discards and trivia of a collection or the input
+  fn end(
+    &mut self,
+    discards: &[Discard<'e>],
+    trivia: &[Trivia<'_>],
+    mut place: Place,
+    indent: usize,
+  ) {
+    for discard in discards {
+      self.discard(discard, place, indent);
+      place = Place::Between;
+    }
+    self.gap(trivia, place, true, indent);
+  }
+
+  // Writes a form and what leads it, telling where it started
+  fn node(&mut self, node: &Node<'e>, mut place: Place, indent: usize) -> Mark {
+    let mut start = None;
+    for discard in &node.leading_discards {
+      let mark = self.discard(discard, place, indent);
+      start.get_or_insert(mark);
+      place = Place::Between;
+    }
+    self.gap(&node.leading_trivia, place, false, indent);
+    let mark = self.mark();
+
+    match &node.kind {
+      NodeKind::Vector(items, discards) => self.coll("[", items.iter(), None, discards, node, "]"),
+      NodeKind::List(items, discards) => {
+        let head = match items.first().map(|item| &item.kind) {
+          Some(NodeKind::Symbol(symbol)) => Some(*symbol),
+          _ => None,
+        };
+        self.coll("(", items.iter(), head, discards, node, ")");
+      }
+      NodeKind::Set(items, discards) => self.coll("#{", items.iter(), None, discards, node, "}"),
+      NodeKind::Map(entries, discards) => {
+        let items = entries.iter().flat_map(|(key, value)| [key, value]);
+        self.coll("{", items, None, discards, node, "}");
+      }
+      NodeKind::Tagged(tag, _, value) => {
+        self.write("#");
+        self.write(tag);
+        self.node(value, Place::Inside, mark.column);
+      }
+      _ => self.write(self.source.get(node.span.0..node.span.1).unwrap_or_default()),
+    }
+    start.unwrap_or(mark)
+  }
+
+  fn coll<'n>(
+    &mut self,
+    open: &str,
+    items: impl Iterator<Item = &'n Node<'e>>,
+    head: Option<&'e str>,
+    discards: &[Discard<'e>],
+    node: &Node<'e>,
+    close: &str,
+  ) where
+    'e: 'n,
+  {
+    self.write(open);
+    let mut coll = Coll { column: self.column, list: open == "(", elements: Vec::new() };
+    self.heads.push(head);
+    for item in items {
+      let indent = self.indent(&coll);
+      let place = if coll.elements.is_empty() { Place::Start } else { Place::Between };
+      let mark = self.node(item, place, indent);
+      coll.elements.push(mark);
+    }
+    let indent = self.indent(&coll);
+    let place = if coll.elements.is_empty() { Place::Start } else { Place::Between };
+    self.end(discards, &node.trailing_trivia, place, indent);
+    self.heads.pop();
+    self.write(close);
+  }
+
+  // Where a line starting with the next element of `coll` is indented to
+  fn indent(&self, coll: &Coll) -> usize {
+    if !coll.list {
+      return coll.column;
+    }
+    let index = coll.elements.len();
+    // The list itself, then the ones around it
+    for (depth, head) in self.heads.iter().rev().enumerate() {
+      let Some(head) = head else { continue };
+      for indent in self.formatter.indents(head) {
+        match *indent {
+          Indent::Inner(inner) if inner == depth => return coll.column + 1,
+          // The body, if it starts a line
+          Indent::Block(args)
+            if depth == 0
+              && index > args
+              && coll.elements.get(args + 1).is_none_or(|mark| mark.line_start) =>
+          {
+            return coll.column + 1;
+          }
+          _ => {}
+        }
+      }
+    }
+    match coll.elements.get(1) {
+      Some(arg) if index > 1 => arg.column,
+      _ => coll.column,
+    }
+  }
+}
@@ -0,0 +1,190 @@
+//! Printing a [`Node`] back to source.
+
+use core::fmt::{self, Display};
+
+use crate::edn::Edn;
+
+use super::{Builder, Discard, Node, NodeBuilder, NodeKind, SourceReader, Span, Token, Trivia};
+
+/// Prints the node as EDN, discards included.
+///
+/// Recorded trivia is printed as it was, and forms that were next to each other in the source
+/// still are, so a node from [`parse_with_trivia`](super::parse_with_trivia) prints the way it
+/// was read. Anywhere else a space separates forms, like [`Edn`] does. Numbers and chars are
+/// printed the way [`Edn`] prints them, see [`Node::display_with_source`] to keep their spelling.
+impl Display for Node<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    Printer { f, source: None, last_end: None }.node(self, "")
+  }
+}
+
+impl Node<'_> {
+  /// Displays the node like its [`Display`] does, except that atoms still matching the `source`
+  /// at their span are spelled the way they were read, `0x2A` rather than `42`.
+  ///
+  /// ```
+  /// #[cfg(feature = "unstable")]
+  /// {
+  ///   use clojure_reader::parse::{NodeKind, SourceReader, parse_with_trivia};
+  ///
+  ///   let source = "{:retries 0x0A ; hex\n :delay 1.50}";
+  ///   let mut node = parse_with_trivia(&mut SourceReader::new(source)).unwrap();
+  ///   assert_eq!(node.display_with_source(source).to_string(), source);
+  ///
+  ///   let NodeKind::Map(entries, _) = &mut node.kind else { panic!("unexpected") };
+  ///   entries[0].1.kind = NodeKind::Int(12);
+  ///   assert_eq!(node.display_with_source(source).to_string(), "{:retries 12 ; hex\n :delay 1.50}");
+  /// }
+  /// ```
+  pub fn display_with_source<'a>(&'a self, source: &'a str) -> impl Display + 'a {
+    WithSource(self, source)
+  }
+}
+
+struct WithSource<'a, 'e>(&'a Node<'e>, &'a str);
+
+impl Display for WithSource<'_, '_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    Printer { f, source: Some(self.1), last_end: None }.node(self.0, "")
+  }
+}
+
+// Prints the forms of an input and the discards and trivia after them, a form to a line where
+// there's nothing between them
+pub(super) fn forms(
+  f: &mut fmt::Formatter<'_>,
+  source: &str,
+  forms: &[Node<'_>],
+  discards: &[Discard<'_>],
+  trivia: &[Trivia<'_>],
+) -> fmt::Result {
+  let mut printer = Printer { f, source: Some(source), last_end: None };
+  let mut separator = "";
+  for form in forms {
+    printer.node(form, separator)?;
+    separator = "\n";
+  }
+  for discard in discards {
+    printer.discard(discard, separator)?;
+    separator = "\n";
+  }
+  printer.gap(trivia, source.len(), "")
+}
+
+struct Printer<'a, 'f> {
+  f: &'a mut fmt::Formatter<'f>,
+  source: Option<&'a str>,
+  // Where what was printed last ended in the source, if it came from there
+  last_end: Option<usize>,
+}
+
+impl<'a> Printer<'a, '_> {
+  // What goes before something starting at `start`: its trivia if it has any, nothing if it
+  // followed what was printed last in the source too, `separator` otherwise
+  fn gap(&mut self, trivia: &[Trivia<'_>], start: usize, separator: &str) -> fmt::Result {
+    if !trivia.is_empty() {
+      return trivia.iter().try_for_each(|trivia| self.f.write_str(trivia.as_str()));
+    }
+    if self.last_end == Some(start) {
+      return Ok(());
+    }
+    self.f.write_str(separator)
+  }
+
+  // Writes a token, which ends at `end` in the source if it was read from there
+  fn token(&mut self, token: &str, end: Option<usize>) -> fmt::Result {
+    self.last_end = end;
+    self.f.write_str(token)
+  }
+
+  fn discard(&mut self, Discard(node, span, trivia): &Discard<'_>, separator: &str) -> fmt::Result {
+    self.gap(trivia, span.0, separator)?;
+    self.token("#_", read(*span).then_some(span.0 + 2))?;
+    self.node(node, "")
+  }
+
+  fn node(&mut self, node: &Node<'_>, mut separator: &str) -> fmt::Result {
+    for discard in &node.leading_discards {
+      self.discard(discard, separator)?;
+      separator = " ";
+    }
+    self.gap(&node.leading_trivia, node.span.0, separator)?;
+
+    let Span(start, end) = node.span;
+    let (open, items, discards, close) = match &node.kind {
+      NodeKind::Vector(items, discards) => ("[", items, discards, "]"),
+      NodeKind::List(items, discards) => ("(", items, discards, ")"),
+      NodeKind::Set(items, discards) => ("#{", items, discards, "}"),
+      NodeKind::Map(entries, discards) => {
+        self.token("{", read(node.span).then_some(start + 1))?;
+        let mut separator = "";
+        for (key, value) in entries {
+          self.node(key, separator)?;
+          self.node(value, " ")?;
+          separator = ", ";
+        }
+        return self.close(node, discards, !entries.is_empty(), "}");
+      }
+      NodeKind::Tagged(tag, tag_span, value) => {
+        self.token("#", None)?;
+        self.token(tag, read(*tag_span).then_some(tag_span.1))?;
+        self.node(value, " ")?;
+        self.last_end = read(node.span).then_some(end);
+        return Ok(());
+      }
+      _ => return self.atom(node),
+    };
+    self.token(open, read(node.span).then_some(start + open.len()))?;
+    let mut separator = "";
+    for item in items {
+      self.node(item, separator)?;
+      separator = " ";
+    }
+    self.close(node, discards, !items.is_empty(), close)
+  }
+
+  fn close(
+    &mut self,
+    node: &Node<'_>,
+    discards: &[Discard<'_>],
+    has_items: bool,
+    close: &str,
+  ) -> fmt::Result {
+    let mut separator = if has_items { " " } else { "" };
+    for discard in discards {
+      self.discard(discard, separator)?;
+      separator = " ";
+    }
+    self.gap(&node.trailing_trivia, node.span.1.saturating_sub(1), "")?;
+    self.token(close, read(node.span).then_some(node.span.1))
+  }
+
+  fn atom(&mut self, node: &Node<'_>) -> fmt::Result {
+    if let Some(spelling) = self.spelling(node) {
+      return self.token(spelling, Some(node.span.1));
+    }
+    self.last_end = read(node.span).then_some(node.span.1);
+    match &node.kind {
+      // Already spelled the way they were read
+      NodeKind::Str(str) => write!(self.f, "\"{str}\""),
+      kind => {
+        let edn = Edn::try_from(Node::no_discards(kind.clone(), node.span));
+        write!(self.f, "{}", edn.map_err(|_| fmt::Error)?)
+      }
+    }
+  }
+
+  // The source of an atom, if it still reads as the atom
+  fn spelling(&self, node: &Node<'_>) -> Option<&'a str> {
+    let spelling = self.source?.get(node.span.0..node.span.1)?;
+    let mut reader = SourceReader::new(spelling);
+    let Ok(Some((0, Token::Atom(atom)))) = reader.next_token() else { return None };
+    let same = reader.remaining().is_empty() && NodeBuilder.atom(atom, node.span).kind == node.kind;
+    same.then_some(spelling)
+  }
+}
+
+// Whether something was read from the source, rather than made up with an empty span
+const fn read(span: Span) -> bool {
+  !span.is_empty()
+}
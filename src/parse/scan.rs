@@ -0,0 +1,232 @@
+//! Byte-level scanning for the [`SourceReader`](super::SourceReader).
+//!
+//! Everything that structures EDN is ASCII, so the reader classifies bytes and only decodes a char
+//! when it meets a non-ASCII one, which can still be Unicode whitespace. Strings and comments are
+//! the long runs in most sources, with the `simd` feature they are searched with `memchr`, and
+//! whitespace and tokens are classified eight bytes at a time.
+
+/// Whitespace, including commas, skipped between forms
+pub const SPACE: u8 = 1;
+/// Ends tokens, `,()[]{};`
+pub const DELIMITER: u8 = 1 << 1;
+/// Ends tokens too, but isn't a delimiter when reading chars
+pub const QUOTE: u8 = 1 << 2;
+/// Starts or continues a multi-byte char
+const NON_ASCII: u8 = 1 << 3;
+
+// Compiles down to a table lookup
+#[inline(always)]
+const fn class(byte: u8) -> u8 {
+  match byte {
+    b'\t' | b'\n' | b'\x0B' | b'\x0C' | b'\r' | b' ' => SPACE,
+    b',' => SPACE | DELIMITER,
+    b'(' | b')' | b'[' | b']' | b'{' | b'}' | b';' => DELIMITER,
+    b'"' => QUOTE,
+    0x80.. => NON_ASCII,
+    _ => 0,
+  }
+}
+
+/// Classes of `c`, the same as its byte's for ASCII
+#[inline(always)]
+pub fn char_class(c: char) -> u8 {
+  match u8::try_from(c) {
+    Ok(byte) if byte.is_ascii() => class(byte),
+    _ if c.is_whitespace() => SPACE,
+    _ => 0,
+  }
+}
+
+/// Length in bytes of the start of `s` whose chars are all in one of the `classes`
+#[inline(always)]
+pub fn len_while(s: &str, classes: u8) -> usize {
+  #[cfg(feature = "simd")]
+  {
+    swar::run_len(s, classes, swar::len_while, |class| class & classes != 0)
+  }
+  #[cfg(not(feature = "simd"))]
+  {
+    scan(s, |class| class & classes != 0)
+  }
+}
+
+/// Length in bytes of the start of `s` whose chars are in none of the `classes`
+#[inline(always)]
+pub fn len_until(s: &str, classes: u8) -> usize {
+  #[cfg(feature = "simd")]
+  {
+    swar::run_len(s, classes, swar::len_until, |class| class & classes == 0)
+  }
+  #[cfg(not(feature = "simd"))]
+  {
+    scan(s, |class| class & classes == 0)
+  }
+}
+
+#[inline(always)]
+fn scan(s: &str, mut accept: impl FnMut(u8) -> bool) -> usize {
+  let bytes = s.as_bytes();
+  let mut len = 0;
+  while let Some(&byte) = bytes.get(len) {
+    let byte_class = class(byte);
+    if byte_class & NON_ASCII == 0 {
+      if !accept(byte_class) {
+        break;
+      }
+      len += 1;
+    } else {
+      // Only ever at the start of a char, every other step is over whole chars
+      let Some(c) = s.get(len..).and_then(|rest| rest.chars().next()) else { break };
+      if !accept(char_class(c)) {
+        break;
+      }
+      len += c.len_utf8();
+    }
+  }
+  len
+}
+
+/// Offset of the first `"` or `\` in `s`, which end the plain parts of a string
+#[inline(always)]
+pub fn find_quote_or_escape(s: &str) -> Option<usize> {
+  #[cfg(feature = "simd")]
+  {
+    memchr::memchr2(b'"', b'\\', s.as_bytes())
+  }
+  #[cfg(not(feature = "simd"))]
+  {
+    s.bytes().position(|byte| matches!(byte, b'"' | b'\\'))
+  }
+}
+
+/// Length in bytes of the `;` or `#!` comment `s` starts with, up to the end of its line
+#[inline(always)]
+pub fn comment_len(s: &str) -> usize {
+  match s.as_bytes() {
+    [b';', ..] | [b'#', b'!', ..] => find_newline(s).unwrap_or(s.len()),
+    _ => 0,
+  }
+}
+
+/// Offset of the first line break in `s`, which ends a comment
+#[inline(always)]
+pub fn find_newline(s: &str) -> Option<usize> {
+  #[cfg(feature = "simd")]
+  {
+    memchr::memchr2(b'\n', b'\r', s.as_bytes())
+  }
+  #[cfg(not(feature = "simd"))]
+  {
+    s.bytes().position(|byte| matches!(byte, b'\n' | b'\r'))
+  }
+}
+
+/// Classifies eight bytes at once in a `u64`, SIMD within a register, so it needs neither `unsafe`
+/// nor a target with vector instructions. Each function sets the high bit of every byte that
+/// matches, the others are clear.
+#[cfg(feature = "simd")]
+mod swar {
+  use super::{DELIMITER, NON_ASCII, QUOTE, SPACE, class};
+
+  const ONES: u64 = 0x0101_0101_0101_0101;
+  const HIGH: u64 = ONES * 0x80;
+  const LOW: u64 = !HIGH;
+
+  // Bytes equal to `byte`, without false positives from carries
+  #[inline(always)]
+  const fn eq(word: u64, byte: u8) -> u64 {
+    let diff = word ^ (ONES * byte as u64);
+    !(((diff & LOW) + LOW) | diff) & HIGH
+  }
+
+  // ASCII bytes from `start` to `end` inclusive, `word` can't have any others
+  #[inline(always)]
+  const fn in_range(ascii: u64, start: u8, end: u8) -> u64 {
+    let at_least_start = ascii + ONES * (0x80 - start as u64);
+    let past_end = ascii + ONES * (0x7F - end as u64);
+    at_least_start & !past_end & HIGH
+  }
+
+  // Bytes in one of the `classes`, the same as `class` for ASCII, and never non-ASCII ones
+  #[inline(always)]
+  const fn matching(word: u64, classes: u8) -> u64 {
+    let ascii = word & LOW;
+    let mut mask = 0;
+    if classes & SPACE != 0 {
+      mask |= in_range(ascii, b'\t', b'\r') | eq(word, b' ') | eq(word, b',');
+    }
+    if classes & DELIMITER != 0 {
+      mask |= in_range(ascii, b'(', b')') | eq(word, b'[') | eq(word, b']');
+      mask |= eq(word, b'{') | eq(word, b'}') | eq(word, b';') | eq(word, b',');
+    }
+    if classes & QUOTE != 0 {
+      mask |= eq(word, b'"');
+    }
+    mask & !(word & HIGH)
+  }
+
+  /// Length of the start of `s` that `accept` takes, looking at its first bytes one by one as most
+  /// runs are short, and then at whole words with `words_len` before the bytes after them
+  #[inline(always)]
+  pub fn run_len(
+    s: &str,
+    classes: u8,
+    words_len: fn(&[u8], u8) -> usize,
+    accept: impl Fn(u8) -> bool,
+  ) -> usize {
+    let bytes = s.as_bytes();
+    let mut len = 0;
+    while let Some(&byte) = bytes.get(len) {
+      let byte_class = class(byte);
+      if byte_class & NON_ASCII != 0 {
+        break;
+      }
+      if !accept(byte_class) {
+        return len;
+      }
+      len += 1;
+      if len == 8 {
+        len += bytes.get(len..).map_or(0, |rest| words_len(rest, classes));
+        break;
+      }
+    }
+    len + s.get(len..).map_or(0, |rest| super::scan(rest, accept))
+  }
+
+  // Loads the next eight bytes, the first one lowest
+  #[inline(always)]
+  fn words(bytes: &[u8]) -> impl Iterator<Item = u64> {
+    bytes.chunks_exact(8).filter_map(|chunk| chunk.try_into().ok()).map(u64::from_le_bytes)
+  }
+
+  /// Length of the whole words at the start of `bytes` that are all ASCII in one of the `classes`,
+  /// and the bytes like that in the word after
+  #[inline(always)]
+  pub fn len_while(bytes: &[u8], classes: u8) -> usize {
+    let mut len = 0;
+    for word in words(bytes) {
+      let rejected = !matching(word, classes) & HIGH;
+      if rejected != 0 {
+        // Non-ASCII bytes are rejected too, their chars are left to be decoded
+        return len + (rejected.trailing_zeros() / 8) as usize;
+      }
+      len += 8;
+    }
+    len
+  }
+
+  /// Length of the start of `bytes` before the first byte that's non-ASCII or in one of the
+  /// `classes`, in whole words and the bytes before it in the word it's in
+  #[inline(always)]
+  pub fn len_until(bytes: &[u8], classes: u8) -> usize {
+    let mut len = 0;
+    for word in words(bytes) {
+      let stop = matching(word, classes) | (word & HIGH);
+      if stop != 0 {
+        return len + (stop.trailing_zeros() / 8) as usize;
+      }
+      len += 8;
+    }
+    len
+  }
+}
@@ -0,0 +1,641 @@
+//! Elaborated EDN values that remember where they were read from.
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::edn::{self, Edn};
+use crate::error::{Code, Error};
+
+#[cfg(feature = "arbitrary-nums")]
+use bigdecimal::BigDecimal;
+#[cfg(feature = "arbitrary-nums")]
+use num_bigint::BigInt;
+#[cfg(feature = "floats")]
+use ordered_float::OrderedFloat;
+
+use super::{Atom, Builder, Parsed, SourceReader, Span, parse_with};
+
+/// Possible kinds of a [`SpannedEdn`]
+///
+/// Maps and sets keep what's in them in the order it's read, deduplicated and looked up by value
+/// like [`Edn::Map`] and [`Edn::Set`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SpannedKind<'e> {
+  Vector(Vec<SpannedEdn<'e>>),
+  Set(SpannedSet<'e>),
+  Map(SpannedMap<'e>),
+  List(Vec<SpannedEdn<'e>>),
+  Key(&'e str),
+  Symbol(&'e str),
+  Str(&'e str),
+  Int(i64),
+  Tagged(&'e str, /* Span of the tag string */ Span, Box<SpannedEdn<'e>>),
+  #[cfg(feature = "floats")]
+  Double(OrderedFloat<f64>),
+  Rational((i64, i64)),
+  #[cfg(feature = "arbitrary-nums")]
+  BigInt(BigInt),
+  #[cfg(feature = "arbitrary-nums")]
+  BigDec(BigDecimal),
+  Char(char),
+  Bool(bool),
+  Nil,
+}
+
+/// Elaborated EDN value, along with the [`Span`] of every value and map key in it.
+///
+/// Unlike a [`Node`](super::Node), duplicate keys have been rejected, tags are validated and
+/// discards are gone. Read one with [`parse_spanned`], or drop the spans with [`Edn::from`].
+///
+/// ```
+/// #[cfg(feature = "unstable")]
+/// {
+///   use clojure_reader::edn::Edn;
+///   use clojure_reader::line_index::{LineCol, LineIndex};
+///   use clojure_reader::parse::{SourceReader, parse_spanned};
+///
+///   let source = "{:ports [80\n 443]}";
+///   let edn = parse_spanned(&mut SourceReader::new(source)).unwrap();
+///   let port = edn.get(&Edn::Key("ports")).and_then(|ports| ports.nth(1)).unwrap();
+///
+///   assert_eq!(*port, Edn::Int(443));
+///   assert_eq!(LineIndex::new(source).line_col(port.span.0), LineCol { line: 2, column: 2 });
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpannedEdn<'e> {
+  pub kind: SpannedKind<'e>,
+  pub span: Span,
+}
+
+/// Parse a single [`SpannedEdn`] from a [`SourceReader`], consuming that form.
+///
+/// # Errors
+///
+/// See [`crate::error::Error`].
+pub fn parse_spanned<'r, 'e: 'r>(
+  reader: &'r mut SourceReader<'e>,
+) -> Result<SpannedEdn<'e>, Error> {
+  parse_with(reader, &SpannedBuilder)
+}
+
+impl<'e> SpannedEdn<'e> {
+  #[inline]
+  pub const fn span(&self) -> Span {
+    self.span
+  }
+
+  /// Same as [`Edn::get`]
+  pub fn get(&self, e: &Edn<'e>) -> Option<&Self> {
+    self.get_key_value(e).map(|(_, value)| value)
+  }
+
+  /// Same as [`SpannedEdn::get`], but also returns the key as it was written.
+  pub fn get_key_value(&self, e: &Edn<'e>) -> Option<(&Self, &Self)> {
+    match &self.kind {
+      SpannedKind::Map(m) => m.get_key_value(e),
+      SpannedKind::Tagged(tag, _, m) => {
+        if let Edn::Key(key) = e {
+          let tag = edn::get_tag(tag, key)?;
+          let key = edn::check_key(tag, key);
+
+          return m.get_key_value(&Edn::Key(key));
+        }
+
+        // Cover cases where it's not a keyword
+        m.get_key_value(e)
+      }
+      _ => None,
+    }
+  }
+
+  /// Same as [`Edn::nth`]
+  pub fn nth(&self, i: usize) -> Option<&Self> {
+    let vec = match &self.kind {
+      SpannedKind::Vector(v) => v,
+      SpannedKind::List(l) => l,
+      _ => return None,
+    };
+
+    vec.get(i)
+  }
+
+  /// Same as [`Edn::contains`]
+  pub fn contains(&self, e: &Edn<'e>) -> bool {
+    match &self.kind {
+      SpannedKind::Map(m) => m.contains_key(e),
+      SpannedKind::Tagged(tag, _, m) => {
+        if let Edn::Key(key) = e {
+          let Some(tag) = edn::get_tag(tag, key) else { return false };
+          let key = edn::check_key(tag, key);
+
+          return m.contains(&Edn::Key(key));
+        }
+
+        // Cover cases where it's not a keyword
+        m.contains(e)
+      }
+      SpannedKind::Vector(v) | SpannedKind::List(v) => v.iter().any(|item| item == e),
+      SpannedKind::Set(s) => s.contains(e),
+      _ => false,
+    }
+  }
+}
+
+/// Compares the values, ignoring spans.
+impl<'e> PartialEq<Edn<'e>> for SpannedEdn<'e> {
+  fn eq(&self, other: &Edn<'e>) -> bool {
+    fn all_eq<'e>(spanned: &[SpannedEdn<'e>], edn: &[Edn<'e>]) -> bool {
+      spanned.len() == edn.len() && spanned.iter().zip(edn).all(|(s, e)| s == e)
+    }
+
+    match (&self.kind, other) {
+      (SpannedKind::Vector(s), Edn::Vector(e)) | (SpannedKind::List(s), Edn::List(e)) => {
+        all_eq(s, e)
+      }
+      (SpannedKind::Set(s), Edn::Set(e)) => s.len() == e.len() && e.iter().all(|e| s.contains(e)),
+      (SpannedKind::Map(s), Edn::Map(e)) => {
+        s.len() == e.len() && e.iter().all(|(k, ev)| s.get(k).is_some_and(|sv| sv == ev))
+      }
+      (SpannedKind::Key(s), Edn::Key(e))
+      | (SpannedKind::Symbol(s), Edn::Symbol(e))
+      | (SpannedKind::Str(s), Edn::Str(e)) => s == e,
+      (SpannedKind::Int(s), Edn::Int(e)) => s == e,
+      (SpannedKind::Tagged(s_tag, _, s), Edn::Tagged(e_tag, e)) => s_tag == e_tag && **s == **e,
+      #[cfg(feature = "floats")]
+      (SpannedKind::Double(s), Edn::Double(e)) => s == e,
+      (SpannedKind::Rational(s), Edn::Rational(e)) => s == e,
+      #[cfg(feature = "arbitrary-nums")]
+      (SpannedKind::BigInt(s), Edn::BigInt(e)) => s == e,
+      #[cfg(feature = "arbitrary-nums")]
+      (SpannedKind::BigDec(s), Edn::BigDec(e)) => s == e,
+      (SpannedKind::Char(s), Edn::Char(e)) => s == e,
+      (SpannedKind::Bool(s), Edn::Bool(e)) => s == e,
+      (SpannedKind::Nil, Edn::Nil) => true,
+      _ => false,
+    }
+  }
+}
+
+impl<'e> From<SpannedEdn<'e>> for Edn<'e> {
+  /// Drops the spans, keeping the value.
+  fn from(SpannedEdn { kind, .. }: SpannedEdn<'e>) -> Self {
+    match kind {
+      SpannedKind::Vector(items) => Edn::Vector(items.into_iter().map(Edn::from).collect()),
+      SpannedKind::Set(set) => Edn::Set(set.items.into_iter().map(Edn::from).collect()),
+      SpannedKind::Map(map) => {
+        Edn::Map(map.entries.into_iter().map(|(key, value)| (key.into(), value.into())).collect())
+      }
+      SpannedKind::List(items) => Edn::List(items.into_iter().map(Edn::from).collect()),
+      SpannedKind::Key(key) => Edn::Key(key),
+      SpannedKind::Symbol(symbol) => Edn::Symbol(symbol),
+      SpannedKind::Str(str) => Edn::Str(str),
+      SpannedKind::Int(int) => Edn::Int(int),
+      SpannedKind::Tagged(tag, _, value) => Edn::Tagged(tag, Box::new((*value).into())),
+      #[cfg(feature = "floats")]
+      SpannedKind::Double(double) => Edn::Double(double),
+      SpannedKind::Rational(rational) => Edn::Rational(rational),
+      #[cfg(feature = "arbitrary-nums")]
+      SpannedKind::BigInt(big_int) => Edn::BigInt(big_int),
+      #[cfg(feature = "arbitrary-nums")]
+      SpannedKind::BigDec(big_dec) => Edn::BigDec(big_dec),
+      SpannedKind::Char(ch) => Edn::Char(ch),
+      SpannedKind::Bool(bool) => Edn::Bool(bool),
+      SpannedKind::Nil => Edn::Nil,
+    }
+  }
+}
+
+/// The items of a [`SpannedKind::Set`], in the order they're read.
+#[derive(Debug, Clone)]
+pub struct SpannedSet<'e> {
+  items: Vec<SpannedEdn<'e>>,
+  // Positions of the items sorted by value, to look them up without a copy of each
+  sorted: Vec<usize>,
+}
+
+impl<'e> SpannedSet<'e> {
+  // The set, and where the first item that's equal to one before it ends
+  fn new(items: Vec<SpannedEdn<'e>>) -> (Self, Option<usize>) {
+    let (sorted, duplicate) = sort_keys(&items, |item| item);
+    let duplicate = duplicate.and_then(|duplicate| items.get(duplicate)).map(|item| item.span.1);
+    (Self { items, sorted }, duplicate)
+  }
+
+  pub const fn len(&self) -> usize {
+    self.items.len()
+  }
+
+  pub const fn is_empty(&self) -> bool {
+    self.items.is_empty()
+  }
+
+  /// The items, in the order they're read.
+  pub fn iter(&self) -> core::slice::Iter<'_, SpannedEdn<'e>> {
+    self.items.iter()
+  }
+
+  /// The item equal to `e`, with its span.
+  pub fn get(&self, e: &Edn<'e>) -> Option<&SpannedEdn<'e>> {
+    let position = find_key(&self.items, &self.sorted, |item| item, &spanned(e))?;
+    self.items.get(position)
+  }
+
+  pub fn contains(&self, e: &Edn<'e>) -> bool {
+    self.get(e).is_some()
+  }
+
+  fn sorted(&self) -> impl Iterator<Item = &SpannedEdn<'e>> {
+    self.sorted.iter().filter_map(|&position| self.items.get(position))
+  }
+}
+
+impl<'a, 'e> IntoIterator for &'a SpannedSet<'e> {
+  type Item = &'a SpannedEdn<'e>;
+  type IntoIter = core::slice::Iter<'a, SpannedEdn<'e>>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+/// Compares the items and their spans, in any order.
+impl PartialEq for SpannedSet<'_> {
+  fn eq(&self, other: &Self) -> bool {
+    self.len() == other.len() && self.sorted().eq(other.sorted())
+  }
+}
+
+impl Eq for SpannedSet<'_> {}
+
+/// The entries of a [`SpannedKind::Map`], in the order they're read.
+#[derive(Debug, Clone)]
+pub struct SpannedMap<'e> {
+  entries: Vec<(SpannedEdn<'e>, SpannedEdn<'e>)>,
+  // Positions of the entries sorted by key, to look them up without a copy of each
+  sorted: Vec<usize>,
+}
+
+impl<'e> SpannedMap<'e> {
+  // The map, and where the first entry whose key is equal to one before it ends
+  fn new(entries: Vec<(SpannedEdn<'e>, SpannedEdn<'e>)>) -> (Self, Option<usize>) {
+    let (sorted, duplicate) = sort_keys(&entries, |(key, _)| key);
+    let duplicate =
+      duplicate.and_then(|duplicate| entries.get(duplicate)).map(|(_, value)| value.span.1);
+    (Self { entries, sorted }, duplicate)
+  }
+
+  pub const fn len(&self) -> usize {
+    self.entries.len()
+  }
+
+  pub const fn is_empty(&self) -> bool {
+    self.entries.is_empty()
+  }
+
+  /// The keys and values, in the order they're read.
+  pub fn iter(&self) -> core::slice::Iter<'_, (SpannedEdn<'e>, SpannedEdn<'e>)> {
+    self.entries.iter()
+  }
+
+  /// The value under the key equal to `e`.
+  pub fn get(&self, e: &Edn<'e>) -> Option<&SpannedEdn<'e>> {
+    self.get_key_value(e).map(|(_, value)| value)
+  }
+
+  /// The key equal to `e` as it was written, and its value.
+  pub fn get_key_value(&self, e: &Edn<'e>) -> Option<(&SpannedEdn<'e>, &SpannedEdn<'e>)> {
+    let position = find_key(&self.entries, &self.sorted, |(key, _)| key, &spanned(e))?;
+    self.entries.get(position).map(|(key, value)| (key, value))
+  }
+
+  pub fn contains_key(&self, e: &Edn<'e>) -> bool {
+    self.get(e).is_some()
+  }
+
+  fn sorted(&self) -> impl Iterator<Item = &(SpannedEdn<'e>, SpannedEdn<'e>)> {
+    self.sorted.iter().filter_map(|&position| self.entries.get(position))
+  }
+}
+
+impl<'a, 'e> IntoIterator for &'a SpannedMap<'e> {
+  type Item = &'a (SpannedEdn<'e>, SpannedEdn<'e>);
+  type IntoIter = core::slice::Iter<'a, (SpannedEdn<'e>, SpannedEdn<'e>)>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+/// Compares the entries and their spans, in any order.
+impl PartialEq for SpannedMap<'_> {
+  fn eq(&self, other: &Self) -> bool {
+    self.len() == other.len() && self.sorted().eq(other.sorted())
+  }
+}
+
+impl Eq for SpannedMap<'_> {}
+
+// The positions of `entries` sorted by key, and the first one whose key is equal to one before it
+fn sort_keys<'e, T>(
+  entries: &[T],
+  key: impl Fn(&T) -> &SpannedEdn<'e>,
+) -> (Vec<usize>, Option<usize>) {
+  let mut sorted: Vec<(usize, &SpannedEdn<'e>)> = entries.iter().map(key).enumerate().collect();
+  // Stable, so equal keys stay in the order they're read
+  sorted.sort_by(|(_, a), (_, b)| cmp_values(a, b));
+  let duplicate = sorted
+    .windows(2)
+    .filter_map(|pair| match pair {
+      [(_, a), (position, b)] if cmp_values(a, b).is_eq() => Some(*position),
+      _ => None,
+    })
+    .min();
+  (sorted.into_iter().map(|(position, _)| position).collect(), duplicate)
+}
+
+fn find_key<'e, T>(
+  entries: &[T],
+  sorted: &[usize],
+  key: impl Fn(&T) -> &SpannedEdn<'e>,
+  e: &SpannedEdn<'e>,
+) -> Option<usize> {
+  let found = sorted.binary_search_by(|&position| {
+    entries.get(position).map_or(Ordering::Greater, |entry| cmp_values(key(entry), e))
+  });
+  found.ok().and_then(|found| sorted.get(found).copied())
+}
+
+// `e` with empty spans, to be looked up
+fn spanned<'e>(e: &Edn<'e>) -> SpannedEdn<'e> {
+  let kind = match e {
+    Edn::Vector(items) => SpannedKind::Vector(items.iter().map(spanned).collect()),
+    Edn::Set(items) => SpannedKind::Set(SpannedSet::new(items.iter().map(spanned).collect()).0),
+    Edn::Map(entries) => SpannedKind::Map(
+      SpannedMap::new(entries.iter().map(|(key, value)| (spanned(key), spanned(value))).collect())
+        .0,
+    ),
+    Edn::List(items) => SpannedKind::List(items.iter().map(spanned).collect()),
+    Edn::Key(key) => SpannedKind::Key(key),
+    Edn::Symbol(symbol) => SpannedKind::Symbol(symbol),
+    Edn::Str(str) => SpannedKind::Str(str),
+    Edn::Int(int) => SpannedKind::Int(*int),
+    Edn::Tagged(tag, value) => SpannedKind::Tagged(tag, Span::default(), Box::new(spanned(value))),
+    #[cfg(feature = "floats")]
+    Edn::Double(double) => SpannedKind::Double(*double),
+    Edn::Rational(rational) => SpannedKind::Rational(*rational),
+    #[cfg(feature = "arbitrary-nums")]
+    Edn::BigInt(big_int) => SpannedKind::BigInt(big_int.clone()),
+    #[cfg(feature = "arbitrary-nums")]
+    Edn::BigDec(big_dec) => SpannedKind::BigDec(big_dec.clone()),
+    Edn::Char(ch) => SpannedKind::Char(*ch),
+    Edn::Bool(bool) => SpannedKind::Bool(*bool),
+    Edn::Nil => SpannedKind::Nil,
+  };
+  SpannedEdn { kind, span: Span::default() }
+}
+
+// Orders values, ignoring spans, so equal ones are next to each other
+fn cmp_values(a: &SpannedEdn<'_>, b: &SpannedEdn<'_>) -> Ordering {
+  fn cmp_all<'a, 'e: 'a>(
+    a: impl Iterator<Item = &'a SpannedEdn<'e>>,
+    mut b: impl Iterator<Item = &'a SpannedEdn<'e>>,
+  ) -> Ordering {
+    for a in a {
+      let Some(b) = b.next() else { return Ordering::Greater };
+      let ordering = cmp_values(a, b);
+      if ordering.is_ne() {
+        return ordering;
+      }
+    }
+    if b.next().is_some() { Ordering::Less } else { Ordering::Equal }
+  }
+
+  const fn rank(kind: &SpannedKind<'_>) -> u8 {
+    match kind {
+      SpannedKind::Vector(_) => 0,
+      SpannedKind::Set(_) => 1,
+      SpannedKind::Map(_) => 2,
+      SpannedKind::List(_) => 3,
+      SpannedKind::Key(_) => 4,
+      SpannedKind::Symbol(_) => 5,
+      SpannedKind::Str(_) => 6,
+      SpannedKind::Int(_) => 7,
+      SpannedKind::Tagged(..) => 8,
+      #[cfg(feature = "floats")]
+      SpannedKind::Double(_) => 9,
+      SpannedKind::Rational(_) => 10,
+      #[cfg(feature = "arbitrary-nums")]
+      SpannedKind::BigInt(_) => 11,
+      #[cfg(feature = "arbitrary-nums")]
+      SpannedKind::BigDec(_) => 12,
+      SpannedKind::Char(_) => 13,
+      SpannedKind::Bool(_) => 14,
+      SpannedKind::Nil => 15,
+    }
+  }
+
+  match (&a.kind, &b.kind) {
+    (SpannedKind::Vector(a), SpannedKind::Vector(b))
+    | (SpannedKind::List(a), SpannedKind::List(b)) => cmp_all(a.iter(), b.iter()),
+    (SpannedKind::Set(a), SpannedKind::Set(b)) => cmp_all(a.sorted(), b.sorted()),
+    (SpannedKind::Map(a), SpannedKind::Map(b)) => cmp_all(
+      a.sorted().flat_map(|(key, value)| [key, value]),
+      b.sorted().flat_map(|(key, value)| [key, value]),
+    ),
+    (SpannedKind::Key(a), SpannedKind::Key(b))
+    | (SpannedKind::Symbol(a), SpannedKind::Symbol(b))
+    | (SpannedKind::Str(a), SpannedKind::Str(b)) => a.cmp(b),
+    (SpannedKind::Int(a), SpannedKind::Int(b)) => a.cmp(b),
+    (SpannedKind::Tagged(a_tag, _, a), SpannedKind::Tagged(b_tag, _, b)) => {
+      a_tag.cmp(b_tag).then_with(|| cmp_values(a, b))
+    }
+    #[cfg(feature = "floats")]
+    (SpannedKind::Double(a), SpannedKind::Double(b)) => a.cmp(b),
+    (SpannedKind::Rational(a), SpannedKind::Rational(b)) => a.cmp(b),
+    #[cfg(feature = "arbitrary-nums")]
+    (SpannedKind::BigInt(a), SpannedKind::BigInt(b)) => a.cmp(b),
+    #[cfg(feature = "arbitrary-nums")]
+    (SpannedKind::BigDec(a), SpannedKind::BigDec(b)) => a.cmp(b),
+    (SpannedKind::Char(a), SpannedKind::Char(b)) => a.cmp(b),
+    (SpannedKind::Bool(a), SpannedKind::Bool(b)) => a.cmp(b),
+    (a, b) => rank(a).cmp(&rank(b)),
+  }
+}
+
+struct SpannedBuilder;
+
+impl<'e> Builder<'e> for SpannedBuilder {
+  type Item = SpannedEdn<'e>;
+  type Discard = ();
+  type VectorContext = Vec<SpannedEdn<'e>>;
+  type ListContext = Vec<SpannedEdn<'e>>;
+  type MapContext = (Vec<(SpannedEdn<'e>, SpannedEdn<'e>)>, Option<SpannedEdn<'e>>);
+  type SetContext = Vec<SpannedEdn<'e>>;
+
+  fn atom(&self, atom: Atom<'e>, span: Span) -> Self::Item {
+    let kind = match atom {
+      Atom::Key(key) => SpannedKind::Key(key),
+      Atom::Symbol(symbol) => SpannedKind::Symbol(symbol),
+      Atom::Str(str) => SpannedKind::Str(str),
+      Atom::Int(int) => SpannedKind::Int(int),
+      #[cfg(feature = "floats")]
+      Atom::Double(double) => SpannedKind::Double(double),
+      Atom::Rational(rational) => SpannedKind::Rational(rational),
+      #[cfg(feature = "arbitrary-nums")]
+      Atom::BigInt(big_int) => SpannedKind::BigInt(big_int),
+      #[cfg(feature = "arbitrary-nums")]
+      Atom::BigDec(big_dec) => SpannedKind::BigDec(big_dec),
+      Atom::Char(ch) => SpannedKind::Char(ch),
+      Atom::Bool(bool) => SpannedKind::Bool(bool),
+      Atom::Nil => SpannedKind::Nil,
+    };
+
+    SpannedEdn { kind, span }
+  }
+
+  fn with_leading_discards(
+    &self,
+    item: Self::Item,
+    _leading_discards: Vec<Self::Discard>,
+  ) -> Self::Item {
+    item
+  }
+
+  fn new_vector_context(&self) -> Self::VectorContext {
+    Vec::new()
+  }
+
+  fn new_list_context(&self) -> Self::ListContext {
+    Vec::new()
+  }
+
+  fn new_map_context(&self) -> Self::MapContext {
+    (Vec::new(), None)
+  }
+
+  fn new_set_context(&self) -> Self::SetContext {
+    Vec::new()
+  }
+
+  fn add_to_vector(
+    &self,
+    ctx: &mut Self::VectorContext,
+    parsed: Parsed<Self::Item>,
+    _leading_discards: Vec<Self::Discard>,
+  ) -> Result<(), Error> {
+    ctx.push(parsed.item);
+    Ok(())
+  }
+
+  fn add_to_list(
+    &self,
+    ctx: &mut Self::ListContext,
+    parsed: Parsed<Self::Item>,
+    _leading_discards: Vec<Self::Discard>,
+  ) -> Result<(), Error> {
+    ctx.push(parsed.item);
+    Ok(())
+  }
+
+  fn add_to_map(
+    &self,
+    ctx: &mut Self::MapContext,
+    parsed: Parsed<Self::Item>,
+    _leading_discards: Vec<Self::Discard>,
+  ) -> Result<(), Error> {
+    let (entries, pending) = ctx;
+    if let Some(key) = pending.take() {
+      entries.push((key, parsed.item));
+    } else {
+      *pending = Some(parsed.item);
+    }
+    Ok(())
+  }
+
+  fn add_to_set(
+    &self,
+    ctx: &mut Self::SetContext,
+    parsed: Parsed<Self::Item>,
+    _leading_discards: Vec<Self::Discard>,
+  ) -> Result<(), Error> {
+    ctx.push(parsed.item);
+    Ok(())
+  }
+
+  fn finish_vector(
+    &self,
+    ctx: Self::VectorContext,
+    _trailing_discards: Vec<Self::Discard>,
+    span: Span,
+  ) -> Result<Parsed<Self::Item>, Error> {
+    Ok(Parsed::new(SpannedEdn { kind: SpannedKind::Vector(ctx), span }, span))
+  }
+
+  fn finish_set(
+    &self,
+    ctx: Self::SetContext,
+    _trailing_discards: Vec<Self::Discard>,
+    validate: bool,
+    span: Span,
+  ) -> Result<Parsed<Self::Item>, Error> {
+    let (set, duplicate) = SpannedSet::new(ctx);
+    if let Some(duplicate) = duplicate.filter(|_| validate) {
+      return Err(Error::from_offset(Code::SetDuplicateKey, duplicate));
+    }
+    Ok(Parsed::new(SpannedEdn { kind: SpannedKind::Set(set), span }, span))
+  }
+
+  fn finish_map(
+    &self,
+    ctx: Self::MapContext,
+    _trailing_discards: Vec<Self::Discard>,
+    validate: bool,
+    close_pos: usize,
+    span: Span,
+  ) -> Result<Parsed<Self::Item>, Error> {
+    if ctx.1.is_some() {
+      return Err(Error::from_offset(Code::UnexpectedEOF, close_pos));
+    }
+    let (map, duplicate) = SpannedMap::new(ctx.0);
+    if let Some(duplicate) = duplicate.filter(|_| validate) {
+      return Err(Error::from_offset(Code::HashMapDuplicateKey, duplicate));
+    }
+    Ok(Parsed::new(SpannedEdn { kind: SpannedKind::Map(map), span }, span))
+  }
+
+  fn finish_list(
+    &self,
+    ctx: Self::ListContext,
+    _trailing_discards: Vec<Self::Discard>,
+    span: Span,
+  ) -> Result<Parsed<Self::Item>, Error> {
+    Ok(Parsed::new(SpannedEdn { kind: SpannedKind::List(ctx), span }, span))
+  }
+
+  fn tag(
+    &self,
+    tag: &'e str,
+    tag_span: Span,
+    value: Parsed<Self::Item>,
+    _leading_discards: Vec<Self::Discard>,
+    span: Span,
+  ) -> Result<Parsed<Self::Item>, Error> {
+    edn::validate_tag(tag, tag_span)?;
+    if tag.starts_with(':') && !matches!(&value.item.kind, SpannedKind::Map(_)) {
+      return Err(Error::from_offset(Code::InvalidTag, tag_span.0));
+    }
+    let kind = SpannedKind::Tagged(tag, tag_span, Box::new(value.item));
+    Ok(Parsed::new(SpannedEdn { kind, span }, span))
+  }
+
+  fn discard(
+    &self,
+    _value: Parsed<Self::Item>,
+    _leading_discards: Vec<Self::Discard>,
+    _discard_span: Span,
+  ) -> Self::Discard {
+  }
+}
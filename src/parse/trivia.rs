@@ -0,0 +1,116 @@
+//! Parsing that keeps the whitespace and comments around.
+
+use alloc::vec::Vec;
+
+use crate::error::Error;
+
+use super::{Discard, Node, NodeKind, SourceReader, Trivia, parse};
+
+/// Parse a single `Node` like [`parse`], recording the whitespace and comments inside it and
+/// before it.
+///
+/// Nothing is lost: the trivia, discards and spans of the tree cover every byte read. The trivia
+/// after the form is left to be read.
+///
+/// # Examples
+///
+/// ```
+/// #[cfg(feature = "unstable")]
+/// {
+///   use clojure_reader::parse::{NodeKind, SourceReader, Trivia, parse_with_trivia};
+///
+///   let mut reader = SourceReader::new("#!/usr/bin/env bb\n[1 ; one\n 2]");
+///   let node = parse_with_trivia(&mut reader).unwrap();
+///
+///   assert_eq!(node.leading_trivia[0], Trivia::Comment("#!/usr/bin/env bb"));
+///   let NodeKind::Vector(items, _) = node.kind else { panic!("unexpected") };
+///   assert_eq!(
+///     items[1].leading_trivia,
+///     [Trivia::Whitespace(" "), Trivia::Comment("; one"), Trivia::Whitespace("\n ")]
+///   );
+/// }
+/// ```
+///
+/// # Errors
+///
+/// See [`crate::error::Error`].
+pub fn parse_with_trivia<'r, 'e: 'r>(reader: &'r mut SourceReader<'e>) -> Result<Node<'e>, Error> {
+  let start = reader.read_pos;
+  let mut node = parse(reader)?;
+  // Tokens are all that's left out of the tree, what's between them is trivia
+  let mut gaps = Gaps { source: reader.slice, pos: start };
+  gaps.node(&mut node);
+  Ok(node)
+}
+
+// Walks a tree in source order, filling in the trivia before each thing read
+struct Gaps<'e> {
+  source: &'e str,
+  pos: usize,
+}
+
+impl<'e> Gaps<'e> {
+  // Trivia from where the last thing ended up to `end`, moving past it
+  fn trivia_until(&mut self, end: usize) -> Vec<Trivia<'e>> {
+    let mut reader =
+      SourceReader { slice: self.source.get(..end).unwrap_or_default(), read_pos: self.pos };
+    let trivia = core::iter::from_fn(|| reader.next_trivia()).collect();
+    self.pos = end;
+    trivia
+  }
+
+  fn discard(&mut self, Discard(node, span, trivia): &mut Discard<'e>) {
+    *trivia = self.trivia_until(span.0);
+    // Past the `#_`
+    self.pos += 2;
+    self.node(node);
+  }
+
+  fn node(&mut self, node: &mut Node<'e>) {
+    for discard in &mut node.leading_discards {
+      self.discard(discard);
+    }
+    node.leading_trivia = self.trivia_until(node.span.0);
+
+    let discards = match &mut node.kind {
+      NodeKind::Vector(items, discards) | NodeKind::List(items, discards) => {
+        self.pos += 1;
+        for item in items {
+          self.node(item);
+        }
+        discards
+      }
+      NodeKind::Set(items, discards) => {
+        self.pos += 2;
+        for item in items {
+          self.node(item);
+        }
+        discards
+      }
+      NodeKind::Map(entries, discards) => {
+        self.pos += 1;
+        for (key, value) in entries {
+          self.node(key);
+          self.node(value);
+        }
+        discards
+      }
+      NodeKind::Tagged(_, tag_span, value) => {
+        self.pos = tag_span.1;
+        self.node(value);
+        self.pos = node.span.1;
+        return;
+      }
+      _ => {
+        self.pos = node.span.1;
+        return;
+      }
+    };
+    for discard in discards {
+      self.discard(discard);
+    }
+    // Up to the closing delimiter
+    node.trailing_trivia = self.trivia_until(node.span.1.saturating_sub(1));
+    self.pos = node.span.1;
+  }
+}
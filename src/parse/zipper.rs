@@ -0,0 +1,348 @@
+//! Moving around and editing the forms of some source, like rewrite-clj's zippers.
+
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{self, Display};
+use core::mem;
+
+use crate::edn::Edn;
+use crate::error::{Code, Error};
+
+use super::format::next_form;
+use super::{Discard, Node, NodeKind, SourceReader, Trivia, print};
+
+/// A cursor over the forms of some source, to move around them and edit them.
+///
+/// The zipper is always at a form, starting at the first one. Going [`down`](Zipper::down) leads
+/// to the first form in a collection, the key of a map's first entry or the form a tag is on,
+/// then [`right`](Zipper::right) goes through the rest of them, keys and values alike. Moving
+/// where nothing is gives back `None` and leaves the zipper where it was.
+///
+/// Edits keep the whitespace and comments of the source around: a replaced form keeps what led
+/// it, and new forms are spaced like the forms next to them. Forms made up for an edit should
+/// have an empty span, since spans are read as places in the source. [`Zipper::to_source`] prints
+/// it all back, spelled like the source everywhere an edit didn't reach.
+///
+/// # Examples
+///
+/// ```
+/// #[cfg(feature = "unstable")]
+/// {
+///   use clojure_reader::{edn::Edn, parse::{Node, NodeKind, Span, Zipper}};
+///
+///   let source = r#"{:deps {org.clojure/clojure {:mvn/version "1.11.1"}
+///         cheshire/cheshire  {:mvn/version "5.12.0"}} ; json
+///  :paths ["src"]}"#;
+///   let mut zipper = Zipper::new(source).unwrap();
+///
+///   let version = Node::no_discards(NodeKind::Str("5.13.0"), Span::default());
+///   zipper.find_next_value(&Edn::Symbol("cheshire/cheshire")).unwrap().right().unwrap();
+///   zipper.down().unwrap().right().unwrap().replace(version);
+///
+///   assert_eq!(
+///     zipper.to_source(),
+///     r#"{:deps {org.clojure/clojure {:mvn/version "1.11.1"}
+///         cheshire/cheshire  {:mvn/version "5.13.0"}} ; json
+///  :paths ["src"]}"#
+///   );
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Zipper<'e> {
+  source: &'e str,
+  forms: Vec<Node<'e>>,
+  // What's left after the last form
+  discards: Vec<Discard<'e>>,
+  trivia: Vec<Trivia<'e>>,
+  // Where the zipper is: which top level form, then which child of it and so on down
+  path: Vec<usize>,
+}
+
+impl<'e> Zipper<'e> {
+  /// Reads every form of `source`, starting at the first one.
+  ///
+  /// # Errors
+  ///
+  /// See [`crate::error::Error`]. A source without any forms has nothing to start at, and is an
+  /// [`Code::UnexpectedEOF`].
+  pub fn new(source: &'e str) -> Result<Self, Error> {
+    let mut reader = SourceReader::new(source);
+    let (mut discards, mut trivia) = (Vec::new(), Vec::new());
+    let mut forms = Vec::new();
+    while let Some(form) = next_form(&mut reader, &mut discards, &mut trivia)? {
+      forms.push(form);
+    }
+    if forms.is_empty() {
+      return Err(Error::from_offset(Code::UnexpectedEOF, source.len()).with_source(source));
+    }
+    Ok(Self { source, forms, discards, trivia, path: vec![0] })
+  }
+
+  /// The form the zipper is at
+  #[expect(clippy::missing_panics_doc)]
+  pub fn node(&self) -> &Node<'e> {
+    node_at(&self.forms, &self.path).expect("the path leads to a form")
+  }
+
+  /// The form the zipper is at, to edit in place
+  #[expect(clippy::missing_panics_doc)]
+  pub fn node_mut(&mut self) -> &mut Node<'e> {
+    node_at_mut(&mut self.forms, &self.path).expect("the path leads to a form")
+  }
+
+  /// Goes to the first form inside this one.
+  pub fn down(&mut self) -> Option<&mut Self> {
+    child(self.node(), 0)?;
+    self.path.push(0);
+    Some(self)
+  }
+
+  /// Goes to the form this one is in.
+  pub fn up(&mut self) -> Option<&mut Self> {
+    if self.path.len() == 1 {
+      return None;
+    }
+    self.path.pop();
+    Some(self)
+  }
+
+  /// Goes to the next form over.
+  pub fn right(&mut self) -> Option<&mut Self> {
+    self.move_to(|index| index.checked_add(1))
+  }
+
+  /// Goes to the previous form over.
+  pub fn left(&mut self) -> Option<&mut Self> {
+    self.move_to(|index| index.checked_sub(1))
+  }
+
+  /// Goes to the next atom after this form equal to `value`, looking inside forms before past
+  /// them. The form the zipper is at isn't compared, so calling it again goes on to the next one.
+  ///
+  /// Only atoms are compared, collections and tagged forms are looked inside of.
+  pub fn find_next_value(&mut self, value: &Edn<'_>) -> Option<&mut Self> {
+    let mut path = self.path.clone();
+    loop {
+      path = self.next_path(path)?;
+      let node = node_at(&self.forms, &path)?;
+      if !is_atom(&node.kind) {
+        continue;
+      }
+      let atom = Edn::try_from(Node::no_discards(node.kind.clone(), node.span));
+      if atom.is_ok_and(|atom| atom == *value) {
+        self.path = path;
+        return Some(self);
+      }
+    }
+  }
+
+  /// Replaces the form the zipper is at. It keeps the discards and trivia that led the old one,
+  /// unless `node` has its own.
+  pub fn replace(&mut self, node: Node<'e>) -> &mut Self {
+    replace(self.node_mut(), node);
+    self
+  }
+
+  /// Inserts `node` after the form the zipper is at, spaced like its neighbours, and stays put.
+  ///
+  /// Returns `None` in maps, where forms come in pairs, and in tagged forms.
+  pub fn insert_right(&mut self, mut node: Node<'e>) -> Option<&mut Self> {
+    let (&index, parent) = self.path.split_last()?;
+    let (items, fallback) = if parent.is_empty() {
+      (&mut self.forms, "\n")
+    } else {
+      match &mut node_at_mut(&mut self.forms, parent)?.kind {
+        NodeKind::Vector(items, _) | NodeKind::List(items, _) | NodeKind::Set(items, _) => {
+          (items, " ")
+        }
+        _ => return None,
+      }
+    };
+    if node.leading_discards.is_empty() && node.leading_trivia.is_empty() {
+      // Like the form before it, unless that's the first one, right after a delimiter
+      let like = if index == 0 { items.get(1) } else { items.get(index) };
+      node.leading_trivia = spacing(like, fallback);
+    }
+    items.insert(index + 1, node);
+    Some(self)
+  }
+
+  /// Removes the form the zipper is at, with the discards and trivia leading it, and goes to the
+  /// form before it, or the one it was in if it was the first.
+  ///
+  /// In a map the whole entry goes, key and value. Returns `None` for the only top level form,
+  /// and in tagged forms.
+  pub fn remove(&mut self) -> Option<&mut Self> {
+    let (&index, parent) = self.path.split_last()?;
+    if parent.is_empty() {
+      if self.forms.len() == 1 {
+        return None;
+      }
+      remove(&mut self.forms, index);
+      self.path = vec![index.saturating_sub(1)];
+      return Some(self);
+    }
+    match &mut node_at_mut(&mut self.forms, parent)?.kind {
+      NodeKind::Vector(items, _) | NodeKind::List(items, _) | NodeKind::Set(items, _) => {
+        remove(items, index);
+        self.path.pop();
+        if let Some(index) = index.checked_sub(1) {
+          self.path.push(index);
+        }
+      }
+      NodeKind::Map(entries, _) => {
+        let entry = index / 2;
+        let (key, _) = entries.remove(entry);
+        if entry == 0
+          && let Some((next, _)) = entries.first_mut()
+        {
+          next.leading_trivia = key.leading_trivia;
+        }
+        self.path.pop();
+        if let Some(entry) = entry.checked_sub(1) {
+          self.path.push(entry * 2 + 1);
+        }
+      }
+      _ => return None,
+    }
+    Some(self)
+  }
+
+  /// Sets the value of `key` in the map the zipper is at, and stays put. A new entry goes at the
+  /// end, spaced like the last one.
+  ///
+  /// Returns `None` if the zipper isn't at a map.
+  pub fn assoc(&mut self, mut key: Node<'e>, mut value: Node<'e>) -> Option<&mut Self> {
+    let NodeKind::Map(entries, _) = &mut self.node_mut().kind else { return None };
+    if let Some((_, old)) = entries.iter_mut().find(|(old, _)| old.kind == key.kind) {
+      replace(old, value);
+      return Some(self);
+    }
+    // The first key is right after the `{`, so it's no good to space a new one like
+    let last = entries.last().filter(|_| entries.len() > 1);
+    if key.leading_discards.is_empty() && key.leading_trivia.is_empty() && !entries.is_empty() {
+      key.leading_trivia = spacing(last.map(|(key, _)| key), " ");
+    }
+    if value.leading_discards.is_empty() && value.leading_trivia.is_empty() {
+      value.leading_trivia = spacing(entries.last().map(|(_, value)| value), " ");
+    }
+    entries.push((key, value));
+    Some(self)
+  }
+
+  /// Prints the forms back with the edits, the way they were read everywhere else.
+  pub fn to_source(&self) -> String {
+    struct Forms<'a, 'e>(&'a Zipper<'e>);
+
+    impl Display for Forms<'_, '_> {
+      fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Zipper { source, forms, discards, trivia, .. } = self.0;
+        print::forms(f, source, forms, discards, trivia)
+      }
+    }
+
+    Forms(self).to_string()
+  }
+
+  // Moves to the form at a sibling index of this one
+  fn move_to(&mut self, sibling: impl FnOnce(usize) -> Option<usize>) -> Option<&mut Self> {
+    let mut path = self.path.clone();
+    let last = path.last_mut()?;
+    *last = sibling(*last)?;
+    node_at(&self.forms, &path)?;
+    self.path = path;
+    Some(self)
+  }
+
+  // The form after the one at `path`, depth first
+  fn next_path(&self, mut path: Vec<usize>) -> Option<Vec<usize>> {
+    if child(node_at(&self.forms, &path)?, 0).is_some() {
+      path.push(0);
+      return Some(path);
+    }
+    loop {
+      *path.last_mut()? += 1;
+      if node_at(&self.forms, &path).is_some() {
+        return Some(path);
+      }
+      path.pop();
+    }
+  }
+}
+
+const fn is_atom(kind: &NodeKind<'_>) -> bool {
+  !matches!(
+    kind,
+    NodeKind::Vector(..)
+      | NodeKind::List(..)
+      | NodeKind::Set(..)
+      | NodeKind::Map(..)
+      | NodeKind::Tagged(..)
+  )
+}
+
+// The forms inside a node, the keys and values of maps in order
+fn child<'a, 'e>(node: &'a Node<'e>, index: usize) -> Option<&'a Node<'e>> {
+  match &node.kind {
+    NodeKind::Vector(items, _) | NodeKind::List(items, _) | NodeKind::Set(items, _) => {
+      items.get(index)
+    }
+    NodeKind::Map(entries, _) => {
+      let (key, value) = entries.get(index / 2)?;
+      Some(if index.is_multiple_of(2) { key } else { value })
+    }
+    NodeKind::Tagged(_, _, value) => (index == 0).then_some(value),
+    _ => None,
+  }
+}
+
+fn child_mut<'a, 'e>(node: &'a mut Node<'e>, index: usize) -> Option<&'a mut Node<'e>> {
+  match &mut node.kind {
+    NodeKind::Vector(items, _) | NodeKind::List(items, _) | NodeKind::Set(items, _) => {
+      items.get_mut(index)
+    }
+    NodeKind::Map(entries, _) => {
+      let (key, value) = entries.get_mut(index / 2)?;
+      Some(if index.is_multiple_of(2) { key } else { value })
+    }
+    NodeKind::Tagged(_, _, value) => (index == 0).then_some(value),
+    _ => None,
+  }
+}
+
+fn node_at<'a, 'e>(forms: &'a [Node<'e>], path: &[usize]) -> Option<&'a Node<'e>> {
+  let (first, rest) = path.split_first()?;
+  rest.iter().try_fold(forms.get(*first)?, |node, index| child(node, *index))
+}
+
+fn node_at_mut<'a, 'e>(forms: &'a mut [Node<'e>], path: &[usize]) -> Option<&'a mut Node<'e>> {
+  let (first, rest) = path.split_first()?;
+  rest.iter().try_fold(forms.get_mut(*first)?, |node, index| child_mut(node, *index))
+}
+
+fn replace<'e>(old: &mut Node<'e>, mut node: Node<'e>) {
+  if node.leading_discards.is_empty() && node.leading_trivia.is_empty() {
+    node.leading_discards = mem::take(&mut old.leading_discards);
+    node.leading_trivia = mem::take(&mut old.leading_trivia);
+  }
+  *old = node;
+}
+
+// Removes a form, the next one taking its place right after the delimiter if it was first
+fn remove(items: &mut Vec<Node<'_>>, index: usize) {
+  let removed = items.remove(index);
+  if index == 0
+    && let Some(next) = items.first_mut()
+  {
+    next.leading_trivia = removed.leading_trivia;
+  }
+}
+
+// What goes before a form spaced `like` another: the whitespace that ends its trivia
+fn spacing<'e>(like: Option<&Node<'e>>, fallback: &'static str) -> Vec<Trivia<'e>> {
+  match like.and_then(|like| like.leading_trivia.last()) {
+    Some(space @ Trivia::Whitespace(_)) => vec![*space],
+    _ => vec![Trivia::Whitespace(fallback)],
+  }
+}
@@ -5,41 +5,269 @@ use core::fmt::{Display, Write};
 
 use serde::{Serialize, ser};
 
+use crate::edn::Edn;
 use crate::error::{Code, Error, Result};
+use crate::types::Marker;
 
-#[derive(Debug)]
+mod value;
+
+/// How enum variants are written, and what [`Deserializer`](crate::de::Deserializer) expects them
+/// in.
+///
+/// Deserializing reads any of these forms, only the namespace of a tag has to be the one expected.
+///
+/// ```
+/// use clojure_reader::ser::{EnumRepr, Serializer};
+/// use serde::Serialize;
+/// use serde_derive::Serialize;
+///
+/// #[derive(Serialize)]
+/// #[serde(rename_all = "kebab-case")]
+/// enum Shape {
+///   Dot,
+///   Circle(u32),
+/// }
+///
+/// let write = |repr, shapes: &[Shape]| {
+///   let mut serializer = Serializer::new().enums(repr);
+///   shapes.serialize(&mut serializer).unwrap();
+///   serializer.into_inner()
+/// };
+/// let shapes = [Shape::Dot, Shape::Circle(2)];
+/// assert_eq!(write(EnumRepr::Tagged, &shapes), "[#Shape/dot nil #Shape/circle 2]");
+/// assert_eq!(write(EnumRepr::Namespaced("geo"), &shapes), "[#geo/dot nil #geo/circle 2]");
+/// assert_eq!(write(EnumRepr::Keyword, &shapes), "[:dot #Shape/circle 2]");
+/// assert_eq!(write(EnumRepr::Map, &shapes), "[:dot {:circle 2}]");
+/// assert_eq!(write(EnumRepr::Vector, &shapes), "[:dot [:circle 2]]");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EnumRepr {
+  /// `#Name/variant value`, tagged with the enum's name, and `#Name/variant nil` for unit variants.
+  #[default]
+  Tagged,
+  /// `#namespace/variant value`, like [`Tagged`](Self::Tagged) but in the given namespace.
+  Namespaced(&'static str),
+  /// `:variant` for unit variants, the others are [`Tagged`](Self::Tagged).
+  Keyword,
+  /// `:variant` for unit variants, and `{:variant value}` for the others.
+  Map,
+  /// `:variant` for unit variants, and `[:variant value]` for the others.
+  Vector,
+}
+
+impl EnumRepr {
+  // The namespace of the tags variants of the enum `name` are written in
+  pub(crate) const fn namespace<'a>(&self, name: &'a str) -> &'a str {
+    match self {
+      Self::Namespaced(namespace) => namespace,
+      _ => name,
+    }
+  }
+}
+
+// The namespace of the fields of a struct named `#:namespace`
+pub(crate) fn struct_namespace(name: &str) -> Option<&str> {
+  name.strip_prefix("#:").filter(|namespace| !namespace.is_empty())
+}
+
+/// Serializer for writing EDN text, see [`to_string`].
+///
+/// A struct renamed to `#:namespace`, with `#[serde(rename = "#:user")]`, has its fields written
+/// as keywords in that namespace, like `:user/name`. Fields that already have a namespace keep it.
+///
+/// ```
+/// use clojure_reader::ser::{Serializer, to_string};
+/// use serde::Serialize;
+/// use serde_derive::Serialize;
+///
+/// #[derive(Serialize)]
+/// #[serde(rename = "#:user")]
+/// struct User {
+///   name: &'static str,
+///   #[serde(rename = "account/id")]
+///   id: u32,
+/// }
+///
+/// let user = User { name: "ada", id: 1 };
+/// assert_eq!(to_string(&user).unwrap(), "{:user/name \"ada\", :account/id 1}");
+///
+/// let mut serializer = Serializer::new().namespaced_maps(true);
+/// user.serialize(&mut serializer).unwrap();
+/// assert_eq!(serializer.into_inner(), "#:user{:name \"ada\", :account/id 1}");
+/// ```
+#[derive(Debug, Default)]
 pub struct Serializer {
   output: String,
-  compound_is_empty: Vec<bool>,
+  compounds: Vec<Compound>,
+  // The next value is one of `crate::types`, whose name it was serialized under
+  marker: Option<Marker>,
+  enums: EnumRepr,
+  // The namespace of the fields of structs that don't name their own
+  namespace: Option<&'static str>,
+  namespaced_maps: bool,
+}
+
+#[derive(Debug)]
+struct Compound {
+  is_empty: bool,
+  closer: &'static str,
+  // Closes the variant the compound is the value of
+  after: &'static str,
+  // The namespace the fields of a struct are written in
+  namespace: Option<&'static str>,
 }
 
 impl Serializer {
-  fn start_compound(&mut self, opener: &str) {
+  /// Creates a serializer writing enums as tags, like [`to_string`].
+  pub fn new() -> Self {
+    Self { output: String::with_capacity(128), ..Self::default() }
+  }
+
+  /// Sets how enum variants are written.
+  #[must_use]
+  pub const fn enums(mut self, enums: EnumRepr) -> Self {
+    self.enums = enums;
+    self
+  }
+
+  /// Writes the fields of structs that aren't renamed to `#:namespace` in `namespace`.
+  #[must_use]
+  pub const fn namespace(mut self, namespace: &'static str) -> Self {
+    self.namespace = Some(namespace);
+    self
+  }
+
+  /// Writes structs with namespaced fields as namespaced maps, like `#:user{:name "ada"}`, instead
+  /// of `{:user/name "ada"}`.
+  #[must_use]
+  pub const fn namespaced_maps(mut self, namespaced_maps: bool) -> Self {
+    self.namespaced_maps = namespaced_maps;
+    self
+  }
+
+  /// The EDN written so far.
+  pub fn into_inner(self) -> String {
+    self.output
+  }
+
+  /// Serializes `value` into an [`Edn`] like [`to_edn`], writing enums and structs the way this
+  /// serializer writes them as text. Nothing is written to the serializer itself.
+  ///
+  /// ```
+  /// use clojure_reader::ser::{EnumRepr, Serializer};
+  /// use serde_derive::Serialize;
+  ///
+  /// #[derive(Serialize)]
+  /// enum Shape {
+  ///   Dot,
+  ///   Circle(u32),
+  /// }
+  ///
+  /// let serializer = Serializer::new().enums(EnumRepr::Map);
+  /// let mut buffer = String::new();
+  /// let edn = serializer.to_edn(&[Shape::Dot, Shape::Circle(2)], &mut buffer).unwrap();
+  /// assert_eq!(edn.to_string(), "[:Dot {:Circle 2}]");
+  /// ```
+  ///
+  /// # Errors
+  ///
+  /// See [`to_edn`].
+  pub fn to_edn<'e, T>(&self, value: &T, buffer: &'e mut String) -> Result<Edn<'e>>
+  where
+    T: Serialize + ?Sized,
+  {
+    let value = value.serialize(&mut value::ValueSerializer {
+      buffer: &mut *buffer,
+      marker: None,
+      enums: self.enums,
+      namespace: self.namespace,
+      namespaced_maps: self.namespaced_maps,
+    })?;
+    value.into_edn(buffer)
+  }
+
+  fn start_compound(&mut self, opener: &str, closer: &'static str) {
     self.output += opener;
-    self.compound_is_empty.push(true);
+    self.compounds.push(Compound { is_empty: true, closer, after: "", namespace: None });
+  }
+
+  fn write_field(&mut self, key: &str) -> Result<()> {
+    self.write_separator(", ")?;
+    self.output += ":";
+    if let Some(namespace) = self.compounds.last().and_then(|compound| compound.namespace)
+      && !key.contains('/')
+    {
+      self.output += namespace;
+      self.output += "/";
+    }
+    self.output += key;
+    self.output += " ";
+    Ok(())
   }
 
   fn write_separator(&mut self, separator: &str) -> Result<()> {
-    let compound_is_empty = self
-      .compound_is_empty
+    let compound = self
+      .compounds
       .last_mut()
       .ok_or_else(|| ser::Error::custom("serializer compound state missing"))?;
-    if *compound_is_empty {
-      *compound_is_empty = false;
+    if compound.is_empty {
+      compound.is_empty = false;
     } else {
       self.output += separator;
     }
     Ok(())
   }
 
-  fn end_compound(&mut self, closer: &str) -> Result<()> {
-    self
-      .compound_is_empty
+  fn end_compound(&mut self) -> Result<()> {
+    let compound = self
+      .compounds
       .pop()
       .ok_or_else(|| ser::Error::custom("serializer compound state missing"))?;
-    self.output += closer;
+    self.output += compound.closer;
+    self.output += compound.after;
     Ok(())
   }
+
+  // Writes what comes before the value of a variant, returning what comes after it
+  fn start_variant(&mut self, name: &str, variant: &str) -> &'static str {
+    let (opener, closer) = match self.enums {
+      EnumRepr::Map => ("{:", "}"),
+      EnumRepr::Vector => ("[:", "]"),
+      EnumRepr::Tagged | EnumRepr::Namespaced(_) | EnumRepr::Keyword => {
+        self.output += "#";
+        self.output += self.enums.namespace(name);
+        ("/", "")
+      }
+    };
+    self.output += opener;
+    self.output += variant;
+    self.output += " ";
+    closer
+  }
+
+  // Starts a compound that is the value of a variant
+  fn start_variant_compound(
+    &mut self,
+    name: &str,
+    variant: &str,
+    opener: &str,
+    closer: &'static str,
+  ) {
+    let after = self.start_variant(name, variant);
+    self.start_compound(opener, closer);
+    if let Some(compound) = self.compounds.last_mut() {
+      compound.after = after;
+    }
+  }
+
+  // Sequences are vectors, unless they're a set or a tagged value's tag and value
+  fn start_seq(&mut self) {
+    match self.marker.take_if(|marker| matches!(marker, Marker::Set | Marker::Tagged)) {
+      Some(Marker::Set) => self.start_compound("#{", "}"),
+      Some(Marker::Tagged) => self.start_compound("#", ""),
+      _ => self.start_compound("[", "]"),
+    }
+  }
 }
 
 impl ser::Error for Error {
@@ -59,12 +287,46 @@ pub fn to_string<T>(value: &T) -> Result<String>
 where
   T: Serialize,
 {
-  let mut serializer =
-    Serializer { output: String::with_capacity(128), compound_is_empty: Vec::new() };
+  let mut serializer = Serializer::new();
   value.serialize(&mut serializer)?;
   Ok(serializer.output)
 }
 
+/// Serializer for creating an [`Edn`], like [`to_string`] writes it.
+///
+/// There's no string in between, but strings, tags and [`RawEdn`](crate::de::RawEdn) sources are
+/// appended to `buffer`, which the [`Edn`] borrows them from.
+///
+/// ```
+/// use clojure_reader::edn::{self, Edn};
+/// use clojure_reader::ser::to_edn;
+/// use serde_derive::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Config {
+///   host: String,
+///   port: u16,
+/// }
+///
+/// let mut buffer = String::new();
+/// let config = Config { host: "localhost".to_string(), port: 8080 };
+/// let mut edn = to_edn(&config, &mut buffer).unwrap();
+/// edn.merge(edn::read_string("{:port 80}").unwrap()).unwrap();
+/// assert_eq!(edn.to_string(), "{:host \"localhost\", :port 80}");
+/// ```
+///
+/// # Errors
+///
+/// See [`crate::error::Error`].
+/// Returns `Code::Serde` for what can't be serialized, and `Code::NoFloatFeature` for floats
+/// without the `floats` feature.
+pub fn to_edn<'e, T>(value: &T, buffer: &'e mut String) -> Result<Edn<'e>>
+where
+  T: Serialize,
+{
+  Serializer::default().to_edn(value, buffer)
+}
+
 impl ser::Serializer for &mut Serializer {
   type Ok = ();
   type Error = Error;
@@ -163,6 +425,10 @@ impl ser::Serializer for &mut Serializer {
   }
 
   fn serialize_str(self, v: &str) -> Result<()> {
+    if self.marker.take_if(|marker| *marker == Marker::Raw).is_some() {
+      self.output += v;
+      return Ok(());
+    }
     self.output += "\"";
     self.output += v;
     self.output += "\"";
@@ -207,19 +473,34 @@ impl ser::Serializer for &mut Serializer {
     _variant_index: u32,
     variant: &'static str,
   ) -> Result<()> {
-    self.output += "#";
-    self.output += name;
-    self.output += "/";
+    if let EnumRepr::Tagged | EnumRepr::Namespaced(_) = self.enums {
+      self.start_variant(name, variant);
+      return self.serialize_unit();
+    }
+    self.output += ":";
     self.output += variant;
-    self.output += " ";
-    self.serialize_unit()
+    Ok(())
   }
 
-  fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<()>
+  fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<()>
   where
     T: ?Sized + Serialize,
   {
-    value.serialize(self)
+    match Marker::of(name) {
+      Some(marker @ (Marker::Keyword | Marker::Symbol)) => {
+        if marker == Marker::Keyword {
+          self.output += ":";
+        }
+        self.output += &value.serialize(NameSerializer)?;
+        Ok(())
+      }
+      Some(marker) => {
+        self.marker = Some(marker);
+        value.serialize(&mut *self)?;
+        self.marker.take().map_or(Ok(()), |marker| Err(unserialized(marker)))
+      }
+      None => value.serialize(self),
+    }
   }
 
   fn serialize_newtype_variant<T>(
@@ -232,24 +513,22 @@ impl ser::Serializer for &mut Serializer {
   where
     T: ?Sized + Serialize,
   {
-    self.output += "#";
-    self.output += name;
-    self.output += "/";
-    self.output += variant;
-    self.output += " ";
-    value.serialize(self)
+    let after = self.start_variant(name, variant);
+    value.serialize(&mut *self)?;
+    self.output += after;
+    Ok(())
   }
 
   fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
     if let Some(len) = len {
       self.output.reserve(len * 16);
     }
-    self.start_compound("[");
+    self.start_seq();
     Ok(self)
   }
 
   fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
-    self.start_compound("[");
+    self.start_seq();
     Ok(self)
   }
 
@@ -268,12 +547,7 @@ impl ser::Serializer for &mut Serializer {
     variant: &'static str,
     _len: usize,
   ) -> Result<Self::SerializeTupleVariant> {
-    self.output += "#";
-    self.output += name;
-    self.output += "/";
-    self.output += variant;
-    self.output += " ";
-    self.start_compound("[");
+    self.start_variant_compound(name, variant, "[", "]");
     Ok(self)
   }
 
@@ -281,12 +555,25 @@ impl ser::Serializer for &mut Serializer {
     if let Some(len) = len {
       self.output.reserve(len * 32);
     }
-    self.start_compound("{");
+    self.start_compound("{", "}");
     Ok(self)
   }
 
-  fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeStruct> {
-    self.serialize_map(Some(len))
+  fn serialize_struct(self, name: &'static str, len: usize) -> Result<Self::SerializeStruct> {
+    let namespace = struct_namespace(name).or(self.namespace);
+    if let Some(namespace) = namespace
+      && self.namespaced_maps
+    {
+      self.output += "#:";
+      self.output += namespace;
+      self.start_compound("{", "}");
+      return Ok(self);
+    }
+    self.serialize_map(Some(len))?;
+    if let Some(compound) = self.compounds.last_mut() {
+      compound.namespace = namespace;
+    }
+    Ok(self)
   }
 
   fn serialize_struct_variant(
@@ -296,12 +583,7 @@ impl ser::Serializer for &mut Serializer {
     variant: &'static str,
     _len: usize,
   ) -> Result<Self::SerializeStructVariant> {
-    self.output += "#";
-    self.output += name;
-    self.output += "/";
-    self.output += variant;
-    self.output += " ";
-    self.start_compound("{");
+    self.start_variant_compound(name, variant, "{", "}");
     Ok(self)
   }
 }
@@ -319,7 +601,7 @@ impl ser::SerializeSeq for &mut Serializer {
   }
 
   fn end(self) -> Result<()> {
-    self.end_compound("]")
+    self.end_compound()
   }
 }
 
@@ -336,7 +618,7 @@ impl ser::SerializeTuple for &mut Serializer {
   }
 
   fn end(self) -> Result<()> {
-    self.end_compound("]")
+    self.end_compound()
   }
 }
 
@@ -353,7 +635,7 @@ impl ser::SerializeTupleStruct for &mut Serializer {
   }
 
   fn end(self) -> Result<()> {
-    self.end_compound("]")
+    self.end_compound()
   }
 }
 
@@ -370,7 +652,7 @@ impl ser::SerializeTupleVariant for &mut Serializer {
   }
 
   fn end(self) -> Result<()> {
-    self.end_compound("]")
+    self.end_compound()
   }
 }
 
@@ -396,7 +678,7 @@ impl ser::SerializeMap for &mut Serializer {
   }
 
   fn end(self) -> Result<()> {
-    self.end_compound("}")
+    self.end_compound()
   }
 }
 
@@ -408,15 +690,12 @@ impl ser::SerializeStruct for &mut Serializer {
   where
     T: ?Sized + Serialize,
   {
-    self.write_separator(", ")?;
-    self.output += ":";
-    self.output += key;
-    self.output += " ";
+    self.write_field(key)?;
     value.serialize(&mut **self)
   }
 
   fn end(self) -> Result<()> {
-    self.end_compound("}")
+    self.end_compound()
   }
 }
 
@@ -428,14 +707,108 @@ impl ser::SerializeStructVariant for &mut Serializer {
   where
     T: ?Sized + Serialize,
   {
-    self.write_separator(", ")?;
-    self.output += ":";
-    self.output += key;
-    self.output += " ";
+    self.write_field(key)?;
     value.serialize(&mut **self)
   }
 
   fn end(self) -> Result<()> {
-    self.end_compound("}")
+    self.end_compound()
+  }
+}
+
+// For a marker whose value didn't serialize as what it's marking
+fn unserialized(marker: Marker) -> Error {
+  ser::Error::custom(format!("can't serialize {marker} from something else"))
+}
+
+// Serializes the name of a keyword or a symbol, which can only be a string
+struct NameSerializer;
+
+macro_rules! not_a_name {
+  ($($method:ident($($arg:ty),*) -> $ok:ty;)*) => {
+    $(
+      fn $method(self, $(_: $arg),*) -> Result<$ok> {
+        Err(ser::Error::custom("a keyword or a symbol has to be named by a string"))
+      }
+    )*
+  };
+}
+
+impl ser::Serializer for NameSerializer {
+  type Ok = String;
+  type Error = Error;
+
+  type SerializeSeq = ser::Impossible<String, Error>;
+  type SerializeTuple = ser::Impossible<String, Error>;
+  type SerializeTupleStruct = ser::Impossible<String, Error>;
+  type SerializeTupleVariant = ser::Impossible<String, Error>;
+  type SerializeMap = ser::Impossible<String, Error>;
+  type SerializeStruct = ser::Impossible<String, Error>;
+  type SerializeStructVariant = ser::Impossible<String, Error>;
+
+  fn serialize_str(self, v: &str) -> Result<String> {
+    Ok(v.to_string())
+  }
+
+  fn serialize_unit_variant(
+    self,
+    _name: &'static str,
+    _variant_index: u32,
+    variant: &'static str,
+  ) -> Result<String> {
+    Ok(variant.to_string())
+  }
+
+  fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<String>
+  where
+    T: ?Sized + Serialize,
+  {
+    value.serialize(self)
+  }
+
+  fn serialize_some<T>(self, _value: &T) -> Result<String>
+  where
+    T: ?Sized + Serialize,
+  {
+    self.serialize_unit()
+  }
+
+  fn serialize_newtype_variant<T>(
+    self,
+    _name: &'static str,
+    _variant_index: u32,
+    _variant: &'static str,
+    _value: &T,
+  ) -> Result<String>
+  where
+    T: ?Sized + Serialize,
+  {
+    self.serialize_unit()
+  }
+
+  not_a_name! {
+    serialize_bool(bool) -> String;
+    serialize_i8(i8) -> String;
+    serialize_i16(i16) -> String;
+    serialize_i32(i32) -> String;
+    serialize_i64(i64) -> String;
+    serialize_u8(u8) -> String;
+    serialize_u16(u16) -> String;
+    serialize_u32(u32) -> String;
+    serialize_u64(u64) -> String;
+    serialize_f32(f32) -> String;
+    serialize_f64(f64) -> String;
+    serialize_char(char) -> String;
+    serialize_bytes(&[u8]) -> String;
+    serialize_none() -> String;
+    serialize_unit() -> String;
+    serialize_unit_struct(&'static str) -> String;
+    serialize_seq(Option<usize>) -> Self::SerializeSeq;
+    serialize_tuple(usize) -> Self::SerializeTuple;
+    serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct;
+    serialize_tuple_variant(&'static str, u32, &'static str, usize) -> Self::SerializeTupleVariant;
+    serialize_map(Option<usize>) -> Self::SerializeMap;
+    serialize_struct(&'static str, usize) -> Self::SerializeStruct;
+    serialize_struct_variant(&'static str, u32, &'static str, usize) -> Self::SerializeStructVariant;
   }
 }
@@ -0,0 +1,516 @@
+//! Serializing straight into an [`Edn`], see [`to_edn`](super::to_edn).
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+#[cfg(feature = "arbitrary-nums")]
+use num_bigint::BigInt;
+#[cfg(feature = "floats")]
+use ordered_float::OrderedFloat;
+use serde::{Serialize, ser};
+
+use crate::edn::{self, Edn, Map};
+use crate::error::{Error, Result};
+use crate::types::Marker;
+
+use super::{EnumRepr, NameSerializer, struct_namespace, unserialized};
+
+/// A serialized value whose text is in the buffer, which can only be borrowed once it's all
+/// written.
+pub(super) enum Value {
+  Edn(Edn<'static>),
+  Str(Range<usize>),
+  Key(Range<usize>),
+  Symbol(Range<usize>),
+  // A `RawEdn`'s source, read once the buffer is done
+  Raw(Range<usize>),
+  Vector(Vec<Self>),
+  Set(Vec<Self>),
+  Map(Vec<(Self, Self)>),
+  Tagged(Range<usize>, Box<Self>),
+}
+
+impl Value {
+  /// The [`Edn`] this is, borrowing its text from `buffer`.
+  pub(super) fn into_edn(self, buffer: &str) -> Result<Edn<'_>> {
+    let text = |range: Range<usize>| {
+      buffer.get(range).ok_or_else(|| ser::Error::custom("serialized text missing from buffer"))
+    };
+    Ok(match self {
+      Self::Edn(edn) => edn,
+      Self::Str(range) => Edn::Str(text(range)?),
+      Self::Key(range) => Edn::Key(text(range)?),
+      Self::Symbol(range) => Edn::Symbol(text(range)?),
+      Self::Raw(range) => edn::read_string(text(range)?)?,
+      Self::Vector(items) => {
+        Edn::Vector(items.into_iter().map(|item| item.into_edn(buffer)).collect::<Result<_>>()?)
+      }
+      Self::Set(items) => {
+        Edn::Set(items.into_iter().map(|item| item.into_edn(buffer)).collect::<Result<_>>()?)
+      }
+      Self::Map(entries) => Edn::Map(
+        entries
+          .into_iter()
+          .map(|(key, value)| Ok((key.into_edn(buffer)?, value.into_edn(buffer)?)))
+          .collect::<Result<Map<_, _>>>()?,
+      ),
+      Self::Tagged(tag, value) => Edn::Tagged(text(tag)?, Box::new(value.into_edn(buffer)?)),
+    })
+  }
+}
+
+/// Serializes into a [`Value`], writing its text to `buffer`, with the conventions of the
+/// [`Serializer`](super::Serializer) it's made from.
+pub(super) struct ValueSerializer<'b> {
+  pub(super) buffer: &'b mut String,
+  // The next value is one of `crate::types`, whose name it was serialized under
+  pub(super) marker: Option<Marker>,
+  pub(super) enums: EnumRepr,
+  // The namespace of the fields of structs that don't name their own
+  pub(super) namespace: Option<&'static str>,
+  pub(super) namespaced_maps: bool,
+}
+
+impl ValueSerializer<'_> {
+  fn push(&mut self, text: &str) -> Range<usize> {
+    let start = self.buffer.len();
+    self.buffer.push_str(text);
+    start..self.buffer.len()
+  }
+
+  fn tag(&mut self, name: &str, variant: &str) -> Range<usize> {
+    let start = self.buffer.len();
+    self.buffer.push_str(name);
+    self.buffer.push('/');
+    self.buffer.push_str(variant);
+    start..self.buffer.len()
+  }
+
+  // The value of a variant of the enum `name`, written like `enums` says
+  fn variant(&mut self, name: &str, variant: &'static str, value: Value) -> Value {
+    match self.enums {
+      EnumRepr::Map => Value::Map(alloc::vec![(Value::Edn(Edn::Key(variant)), value)]),
+      EnumRepr::Vector => Value::Vector(alloc::vec![Value::Edn(Edn::Key(variant)), value]),
+      EnumRepr::Tagged | EnumRepr::Namespaced(_) | EnumRepr::Keyword => {
+        Value::Tagged(self.tag(self.enums.namespace(name), variant), Box::new(value))
+      }
+    }
+  }
+}
+
+impl<'a, 'b> ser::Serializer for &'a mut ValueSerializer<'b> {
+  type Ok = Value;
+  type Error = Error;
+
+  type SerializeSeq = SeqSerializer<'a, 'b>;
+  type SerializeTuple = SeqSerializer<'a, 'b>;
+  type SerializeTupleStruct = SeqSerializer<'a, 'b>;
+  type SerializeTupleVariant = SeqSerializer<'a, 'b>;
+  type SerializeMap = MapSerializer<'a, 'b>;
+  type SerializeStruct = MapSerializer<'a, 'b>;
+  type SerializeStructVariant = MapSerializer<'a, 'b>;
+
+  fn serialize_bool(self, v: bool) -> Result<Value> {
+    Ok(Value::Edn(Edn::Bool(v)))
+  }
+
+  // EDN is always an i64 for integers, so all integers will be serialized as i64.
+  fn serialize_i8(self, v: i8) -> Result<Value> {
+    self.serialize_i64(i64::from(v))
+  }
+
+  fn serialize_i16(self, v: i16) -> Result<Value> {
+    self.serialize_i64(i64::from(v))
+  }
+
+  fn serialize_i32(self, v: i32) -> Result<Value> {
+    self.serialize_i64(i64::from(v))
+  }
+
+  fn serialize_i64(self, v: i64) -> Result<Value> {
+    Ok(Value::Edn(Edn::Int(v)))
+  }
+
+  fn serialize_u8(self, v: u8) -> Result<Value> {
+    self.serialize_i64(i64::from(v))
+  }
+
+  fn serialize_u16(self, v: u16) -> Result<Value> {
+    self.serialize_i64(i64::from(v))
+  }
+
+  fn serialize_u32(self, v: u32) -> Result<Value> {
+    self.serialize_i64(i64::from(v))
+  }
+
+  fn serialize_u64(self, v: u64) -> Result<Value> {
+    if let Ok(v) = i64::try_from(v) {
+      return self.serialize_i64(v);
+    }
+
+    #[cfg(not(feature = "arbitrary-nums"))]
+    {
+      Err(ser::Error::custom(alloc::format!(
+        "can't serialize {v} as a round-trippable EDN integer without arbitrary-nums"
+      )))
+    }
+
+    #[cfg(feature = "arbitrary-nums")]
+    {
+      Ok(Value::Edn(Edn::BigInt(BigInt::from(v))))
+    }
+  }
+
+  fn serialize_f32(self, v: f32) -> Result<Value> {
+    self.serialize_f64(f64::from(v))
+  }
+
+  fn serialize_f64(self, v: f64) -> Result<Value> {
+    #[cfg(feature = "floats")]
+    {
+      Ok(Value::Edn(Edn::Double(OrderedFloat(v))))
+    }
+
+    #[cfg(not(feature = "floats"))]
+    {
+      let _ = v;
+      Err(Error::new(crate::error::Code::NoFloatFeature))
+    }
+  }
+
+  fn serialize_char(self, v: char) -> Result<Value> {
+    Ok(Value::Edn(Edn::Char(v)))
+  }
+
+  fn serialize_str(self, v: &str) -> Result<Value> {
+    let range = self.push(v);
+    if self.marker.take_if(|marker| *marker == Marker::Raw).is_some() {
+      return Ok(Value::Raw(range));
+    }
+    Ok(Value::Str(range))
+  }
+
+  fn serialize_bytes(self, v: &[u8]) -> Result<Value> {
+    Ok(Value::Vector(v.iter().map(|byte| Value::Edn(Edn::Int(i64::from(*byte)))).collect()))
+  }
+
+  fn serialize_none(self) -> Result<Value> {
+    self.serialize_unit()
+  }
+
+  fn serialize_some<T>(self, value: &T) -> Result<Value>
+  where
+    T: ?Sized + Serialize,
+  {
+    value.serialize(self)
+  }
+
+  fn serialize_unit(self) -> Result<Value> {
+    Ok(Value::Edn(Edn::Nil))
+  }
+
+  fn serialize_unit_struct(self, _name: &'static str) -> Result<Value> {
+    self.serialize_unit()
+  }
+
+  fn serialize_unit_variant(
+    self,
+    name: &'static str,
+    _variant_index: u32,
+    variant: &'static str,
+  ) -> Result<Value> {
+    if let EnumRepr::Tagged | EnumRepr::Namespaced(_) = self.enums {
+      return Ok(self.variant(name, variant, Value::Edn(Edn::Nil)));
+    }
+    Ok(Value::Edn(Edn::Key(variant)))
+  }
+
+  fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<Value>
+  where
+    T: ?Sized + Serialize,
+  {
+    match Marker::of(name) {
+      Some(Marker::Keyword) => Ok(Value::Key(self.push(&value.serialize(NameSerializer)?))),
+      Some(Marker::Symbol) => Ok(Value::Symbol(self.push(&value.serialize(NameSerializer)?))),
+      Some(marker) => {
+        self.marker = Some(marker);
+        let value = value.serialize(&mut *self)?;
+        self.marker.take().map_or(Ok(value), |marker| Err(unserialized(marker)))
+      }
+      None => value.serialize(self),
+    }
+  }
+
+  fn serialize_newtype_variant<T>(
+    self,
+    name: &'static str,
+    _variant_index: u32,
+    variant: &'static str,
+    value: &T,
+  ) -> Result<Value>
+  where
+    T: ?Sized + Serialize,
+  {
+    let value = value.serialize(&mut *self)?;
+    Ok(self.variant(name, variant, value))
+  }
+
+  fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
+    let marker = self.marker.take_if(|marker| matches!(marker, Marker::Set | Marker::Tagged));
+    let items = Vec::with_capacity(len.unwrap_or(0));
+    Ok(SeqSerializer { ser: self, variant: None, marker, items })
+  }
+
+  fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple> {
+    self.serialize_seq(Some(len))
+  }
+
+  fn serialize_tuple_struct(
+    self,
+    _name: &'static str,
+    len: usize,
+  ) -> Result<Self::SerializeTupleStruct> {
+    self.serialize_seq(Some(len))
+  }
+
+  fn serialize_tuple_variant(
+    self,
+    name: &'static str,
+    _variant_index: u32,
+    variant: &'static str,
+    len: usize,
+  ) -> Result<Self::SerializeTupleVariant> {
+    let variant = Some((name, variant));
+    Ok(SeqSerializer { ser: self, variant, marker: None, items: Vec::with_capacity(len) })
+  }
+
+  fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap> {
+    let entries = Vec::with_capacity(len.unwrap_or(0));
+    Ok(MapSerializer { ser: self, variant: None, entries, key: None, namespace: None })
+  }
+
+  fn serialize_struct(self, name: &'static str, len: usize) -> Result<Self::SerializeStruct> {
+    let namespace = struct_namespace(name).or(self.namespace);
+    let mut map = self.serialize_map(Some(len))?;
+    map.namespace = namespace;
+    Ok(map)
+  }
+
+  fn serialize_struct_variant(
+    self,
+    name: &'static str,
+    _variant_index: u32,
+    variant: &'static str,
+    len: usize,
+  ) -> Result<Self::SerializeStructVariant> {
+    let variant = Some((name, variant));
+    let entries = Vec::with_capacity(len);
+    Ok(MapSerializer { ser: self, variant, entries, key: None, namespace: None })
+  }
+}
+
+pub(super) struct SeqSerializer<'a, 'b> {
+  ser: &'a mut ValueSerializer<'b>,
+  // The enum and variant it's the value of
+  variant: Option<(&'static str, &'static str)>,
+  // A set, or a tagged value's tag and value, instead of a vector
+  marker: Option<Marker>,
+  items: Vec<Value>,
+}
+
+impl SeqSerializer<'_, '_> {
+  fn push<T>(&mut self, value: &T) -> Result<()>
+  where
+    T: ?Sized + Serialize,
+  {
+    self.items.push(value.serialize(&mut *self.ser)?);
+    Ok(())
+  }
+
+  fn end(self) -> Result<Value> {
+    let value = match self.marker {
+      Some(Marker::Set) => Value::Set(self.items),
+      Some(Marker::Tagged) => match <[Value; 2]>::try_from(self.items) {
+        Ok([Value::Symbol(tag), value]) => Value::Tagged(tag, Box::new(value)),
+        _ => return Err(unserialized(Marker::Tagged)),
+      },
+      _ => Value::Vector(self.items),
+    };
+    Ok(match self.variant {
+      Some((name, variant)) => self.ser.variant(name, variant, value),
+      None => value,
+    })
+  }
+}
+
+impl ser::SerializeSeq for SeqSerializer<'_, '_> {
+  type Ok = Value;
+  type Error = Error;
+
+  fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+  where
+    T: ?Sized + Serialize,
+  {
+    self.push(value)
+  }
+
+  fn end(self) -> Result<Value> {
+    self.end()
+  }
+}
+
+impl ser::SerializeTuple for SeqSerializer<'_, '_> {
+  type Ok = Value;
+  type Error = Error;
+
+  fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+  where
+    T: ?Sized + Serialize,
+  {
+    self.push(value)
+  }
+
+  fn end(self) -> Result<Value> {
+    self.end()
+  }
+}
+
+impl ser::SerializeTupleStruct for SeqSerializer<'_, '_> {
+  type Ok = Value;
+  type Error = Error;
+
+  fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+  where
+    T: ?Sized + Serialize,
+  {
+    self.push(value)
+  }
+
+  fn end(self) -> Result<Value> {
+    self.end()
+  }
+}
+
+impl ser::SerializeTupleVariant for SeqSerializer<'_, '_> {
+  type Ok = Value;
+  type Error = Error;
+
+  fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+  where
+    T: ?Sized + Serialize,
+  {
+    self.push(value)
+  }
+
+  fn end(self) -> Result<Value> {
+    self.end()
+  }
+}
+
+pub(super) struct MapSerializer<'a, 'b> {
+  ser: &'a mut ValueSerializer<'b>,
+  // The enum and variant it's the value of
+  variant: Option<(&'static str, &'static str)>,
+  entries: Vec<(Value, Value)>,
+  // Waiting for its value
+  key: Option<Value>,
+  // The namespace the fields of a struct are in
+  namespace: Option<&'static str>,
+}
+
+impl MapSerializer<'_, '_> {
+  fn field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+  where
+    T: ?Sized + Serialize,
+  {
+    let value = value.serialize(&mut *self.ser)?;
+    let key = match self.namespace {
+      Some(namespace) if !key.contains('/') && !self.ser.namespaced_maps => {
+        Value::Key(self.ser.tag(namespace, key))
+      }
+      _ => Value::Edn(Edn::Key(key)),
+    };
+    self.entries.push((key, value));
+    Ok(())
+  }
+
+  fn end(self) -> Value {
+    let mut value = Value::Map(self.entries);
+    // Written `#:namespace{}`, with the keys in it written without the namespace
+    if let Some(namespace) = self.namespace
+      && self.ser.namespaced_maps
+    {
+      let start = self.ser.buffer.len();
+      self.ser.buffer.push(':');
+      self.ser.buffer.push_str(namespace);
+      value = Value::Tagged(start..self.ser.buffer.len(), Box::new(value));
+    }
+    match self.variant {
+      Some((name, variant)) => self.ser.variant(name, variant, value),
+      None => value,
+    }
+  }
+}
+
+impl ser::SerializeMap for MapSerializer<'_, '_> {
+  type Ok = Value;
+  type Error = Error;
+
+  fn serialize_key<T>(&mut self, key: &T) -> Result<()>
+  where
+    T: ?Sized + Serialize,
+  {
+    self.key = Some(key.serialize(&mut *self.ser)?);
+    Ok(())
+  }
+
+  fn serialize_value<T>(&mut self, value: &T) -> Result<()>
+  where
+    T: ?Sized + Serialize,
+  {
+    // Infallible: serde always calls serialize_key before serialize_value.
+    let key =
+      self.key.take().ok_or_else(|| ser::Error::custom("serialize_value called before its key"))?;
+    let value = value.serialize(&mut *self.ser)?;
+    self.entries.push((key, value));
+    Ok(())
+  }
+
+  fn end(self) -> Result<Value> {
+    Ok(self.end())
+  }
+}
+
+impl ser::SerializeStruct for MapSerializer<'_, '_> {
+  type Ok = Value;
+  type Error = Error;
+
+  fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+  where
+    T: ?Sized + Serialize,
+  {
+    self.field(key, value)
+  }
+
+  fn end(self) -> Result<Value> {
+    Ok(self.end())
+  }
+}
+
+impl ser::SerializeStructVariant for MapSerializer<'_, '_> {
+  type Ok = Value;
+  type Error = Error;
+
+  fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+  where
+    T: ?Sized + Serialize,
+  {
+    self.field(key, value)
+  }
+
+  fn end(self) -> Result<Value> {
+    Ok(self.end())
+  }
+}
@@ -0,0 +1,382 @@
+//! Types for the parts of EDN that Rust doesn't have, for serde.
+//!
+//! [`ser`](crate::ser) writes these as keywords, symbols, sets, tagged values and numbers, and
+//! [`de`](crate::de) reads them back from those. Other serde formats see a keyword or a symbol as
+//! its name, a set as a sequence, a tagged value as a `[tag value]` pair, and a number as its EDN
+//! text. Only [`Tagged`] and [`Rational`] take tagged values and ratios apart, [`de`](crate::de)
+//! reading one into anything else is an invalid type error.
+//!
+//! ```
+//! use std::collections::BTreeSet;
+//!
+//! use clojure_reader::types::{Keyword, Set, Symbol, Tagged};
+//! use clojure_reader::{from_str, to_string};
+//! use serde_derive::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, PartialEq, Deserialize, Serialize)]
+//! #[serde(rename_all = "kebab-case")]
+//! enum Status {
+//!   Active,
+//!   OnHold,
+//! }
+//!
+//! #[derive(Debug, PartialEq, Deserialize, Serialize)]
+//! struct Account {
+//!   status: Keyword<Status>,
+//!   owner: Symbol,
+//!   roles: Set<BTreeSet<Keyword>>,
+//!   opened: Tagged<String>,
+//! }
+//!
+//! let source = r#"{:status :on-hold, :owner ada, :roles #{:admin}, :opened #inst "2024-01-01"}"#;
+//! let account: Account = from_str(source).unwrap();
+//! assert_eq!(account.status, Keyword(Status::OnHold));
+//! assert_eq!(account.opened.tag, "inst");
+//! assert_eq!(to_string(&account).unwrap(), source);
+//! ```
+
+use alloc::format;
+use alloc::string::String;
+use core::fmt::{self, Display};
+use core::marker::PhantomData;
+
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::ser::{self, Serializer};
+use serde::{Deserialize, Serialize};
+
+use crate::de::RAW_EDN_NAME;
+
+const KEYWORD_NAME: &str = "$__clojure_reader_private_Keyword";
+const SYMBOL_NAME: &str = "$__clojure_reader_private_Symbol";
+const SET_NAME: &str = "$__clojure_reader_private_Set";
+const TAGGED_NAME: &str = "$__clojure_reader_private_Tagged";
+const RATIONAL_NAME: &str = "$__clojure_reader_private_Rational";
+#[cfg(feature = "arbitrary-nums")]
+const BIG_INT_NAME: &str = "$__clojure_reader_private_BigInt";
+#[cfg(feature = "arbitrary-nums")]
+const BIG_DEC_NAME: &str = "$__clojure_reader_private_BigDec";
+
+/// The types the serializer and the deserializers recognize by their newtype struct names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Marker {
+  Raw,
+  Keyword,
+  Symbol,
+  Set,
+  Tagged,
+  Rational,
+  #[cfg(feature = "arbitrary-nums")]
+  BigInt,
+  #[cfg(feature = "arbitrary-nums")]
+  BigDec,
+}
+
+impl Marker {
+  /// The marker a newtype struct `name` is for, if it's one of ours
+  pub(crate) fn of(name: &str) -> Option<Self> {
+    Some(match name {
+      RAW_EDN_NAME => Self::Raw,
+      KEYWORD_NAME => Self::Keyword,
+      SYMBOL_NAME => Self::Symbol,
+      SET_NAME => Self::Set,
+      TAGGED_NAME => Self::Tagged,
+      RATIONAL_NAME => Self::Rational,
+      #[cfg(feature = "arbitrary-nums")]
+      BIG_INT_NAME => Self::BigInt,
+      #[cfg(feature = "arbitrary-nums")]
+      BIG_DEC_NAME => Self::BigDec,
+      _ => return None,
+    })
+  }
+}
+
+impl Display for Marker {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(match self {
+      Self::Raw => "raw EDN",
+      Self::Keyword => "a keyword",
+      Self::Symbol => "a symbol",
+      Self::Set => "a set",
+      Self::Tagged => "a tagged value",
+      Self::Rational => "a rational",
+      #[cfg(feature = "arbitrary-nums")]
+      Self::BigInt => "a big int",
+      #[cfg(feature = "arbitrary-nums")]
+      Self::BigDec => "a big decimal",
+    })
+  }
+}
+
+/// A keyword, `:name`.
+///
+/// The name is anything that serializes as a string, like a `&str`, or a unit variant for a
+/// keyword out of a known few.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Keyword<T = String>(pub T);
+
+/// A symbol, `name`.
+///
+/// Like a [`Keyword`], the name is anything that serializes as a string.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol<T = String>(pub T);
+
+/// A set, `#{...}`, of the items of any `T` that serializes as a sequence.
+///
+/// Only a set deserializes into it, not a vector or a list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Set<T>(pub T);
+
+/// A tagged value, `#tag value`, with a tag of any name.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Tagged<T> {
+  /// The tag, without its `#`
+  pub tag: String,
+  pub value: T,
+}
+
+/// A rational number, `numerator/denominator`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Rational(pub i64, pub i64);
+
+/// An arbitrary precision integer, `1N`.
+#[cfg(feature = "arbitrary-nums")]
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BigInt(pub num_bigint::BigInt);
+
+/// An arbitrary precision decimal, `1.5M`.
+#[cfg(feature = "arbitrary-nums")]
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BigDec(pub bigdecimal::BigDecimal);
+
+// Keywords, symbols and sets are newtype structs holding what they're made of
+macro_rules! newtype_marker {
+  ($($marker:ident $name:ident $expecting:literal),*) => {
+    $(
+      impl<T: Serialize> Serialize for $marker<T> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+          S: Serializer,
+        {
+          serializer.serialize_newtype_struct($name, &self.0)
+        }
+      }
+
+      impl<'de, T: Deserialize<'de>> Deserialize<'de> for $marker<T> {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+          D: Deserializer<'de>,
+        {
+          struct MarkerVisitor<T>(PhantomData<T>);
+
+          impl<'de, T: Deserialize<'de>> Visitor<'de> for MarkerVisitor<T> {
+            type Value = $marker<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+              f.write_str($expecting)
+            }
+
+            fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+              D: Deserializer<'de>,
+            {
+              T::deserialize(deserializer).map($marker)
+            }
+          }
+
+          deserializer.deserialize_newtype_struct($name, MarkerVisitor(PhantomData))
+        }
+      }
+    )*
+  };
+}
+
+newtype_marker!(Keyword KEYWORD_NAME "a keyword", Symbol SYMBOL_NAME "a symbol", Set SET_NAME "a set");
+
+impl<T: Serialize> Serialize for Tagged<T> {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    if crate::edn::validate_tag(&self.tag, crate::parse::Span(0, 0)).is_err() {
+      return Err(ser::Error::custom(format!("invalid tag {}", self.tag)));
+    }
+    serializer.serialize_newtype_struct(TAGGED_NAME, &(Symbol(self.tag.as_str()), &self.value))
+  }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Tagged<T> {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    struct TaggedVisitor<T>(PhantomData<T>);
+
+    impl<'de, T: Deserialize<'de>> Visitor<'de> for TaggedVisitor<T> {
+      type Value = Tagged<T>;
+
+      fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a tagged value")
+      }
+
+      fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+      where
+        D: Deserializer<'de>,
+      {
+        deserializer.deserialize_tuple(2, self)
+      }
+
+      fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+      where
+        A: SeqAccess<'de>,
+      {
+        let tag = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let value = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Ok(Tagged { tag, value })
+      }
+    }
+
+    deserializer.deserialize_newtype_struct(TAGGED_NAME, TaggedVisitor(PhantomData))
+  }
+}
+
+// Numbers serialize as their EDN text, which is what other formats get them from too
+fn serialize_text<S: Serializer>(serializer: S, text: &str) -> Result<S::Ok, S::Error> {
+  serializer.serialize_newtype_struct(RAW_EDN_NAME, text)
+}
+
+impl Serialize for Rational {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    serialize_text(serializer, &format!("{}/{}", self.0, self.1))
+  }
+}
+
+impl<'de> Deserialize<'de> for Rational {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    struct RationalVisitor;
+
+    impl<'de> Visitor<'de> for RationalVisitor {
+      type Value = Rational;
+
+      fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a rational")
+      }
+
+      fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+      where
+        D: Deserializer<'de>,
+      {
+        deserializer.deserialize_any(self)
+      }
+
+      fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+      where
+        A: SeqAccess<'de>,
+      {
+        let numerator = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let denominator = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Ok(Rational(numerator, denominator))
+      }
+
+      fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+      where
+        E: de::Error,
+      {
+        v.split_once('/')
+          .and_then(|(numerator, denominator)| {
+            Some(Rational(numerator.parse().ok()?, denominator.parse().ok()?))
+          })
+          .ok_or_else(|| de::Error::invalid_value(de::Unexpected::Str(v), &self))
+      }
+    }
+
+    deserializer.deserialize_newtype_struct(RATIONAL_NAME, RationalVisitor)
+  }
+}
+
+#[cfg(feature = "arbitrary-nums")]
+impl Serialize for BigInt {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    serialize_text(serializer, &format!("{}N", self.0))
+  }
+}
+
+#[cfg(feature = "arbitrary-nums")]
+impl Serialize for BigDec {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    serialize_text(serializer, &format!("{}M", self.0))
+  }
+}
+
+// Big numbers are read from their text, with or without its suffix, or from any integer
+#[cfg(feature = "arbitrary-nums")]
+macro_rules! big_number {
+  ($($big:ident $name:ident $suffix:literal $expecting:literal),*) => {
+    $(
+      impl<'de> Deserialize<'de> for $big {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+          D: Deserializer<'de>,
+        {
+          struct BigVisitor;
+
+          impl<'de> Visitor<'de> for BigVisitor {
+            type Value = $big;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+              f.write_str($expecting)
+            }
+
+            fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+              D: Deserializer<'de>,
+            {
+              deserializer.deserialize_any(self)
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+            where
+              E: de::Error,
+            {
+              Ok($big(v.into()))
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+            where
+              E: de::Error,
+            {
+              Ok($big(v.into()))
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+              E: de::Error,
+            {
+              let digits = v.strip_suffix($suffix).unwrap_or(v);
+              digits
+                .parse()
+                .map($big)
+                .map_err(|_| de::Error::invalid_value(de::Unexpected::Str(v), &self))
+            }
+          }
+
+          deserializer.deserialize_newtype_struct($name, BigVisitor)
+        }
+      }
+    )*
+  };
+}
+
+#[cfg(feature = "arbitrary-nums")]
+big_number!(BigInt BIG_INT_NAME 'N' "a big int", BigDec BIG_DEC_NAME 'M' "a big decimal");
@@ -0,0 +1,128 @@
+extern crate alloc;
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use std::collections::HashMap;
+
+use clojure_reader::edn::{self, Edn};
+use clojure_reader::error::Code;
+
+#[test]
+fn accessors() {
+  let e = edn::read_string(r#"[:k sym "str" 1 2/3 true \c #foo nil {:a 1} (1) #{1} nil]"#).unwrap();
+
+  assert_eq!(e[0].as_keyword(), Some("k"));
+  assert_eq!(e[1].as_symbol(), Some("sym"));
+  assert_eq!(e[2].as_str(), Some("str"));
+  assert_eq!(e[3].as_i64(), Some(1));
+  assert_eq!(e[4].as_rational(), Some((2, 3)));
+  assert_eq!(e[5].as_bool(), Some(true));
+  assert_eq!(e[6].as_char(), Some('c'));
+  assert_eq!(e[7].as_tagged(), Some(("foo", &Edn::Nil)));
+  assert_eq!(e[8].as_map().map(edn::Map::len), Some(1));
+  assert_eq!(e[9].as_list(), Some(&vec![Edn::Int(1)]));
+  assert_eq!(e[10].as_set().map(edn::Set::len), Some(1));
+  assert!(e[11].is_nil());
+  assert_eq!(e.as_vec().map(Vec::len), Some(12));
+
+  assert_eq!(e[0].as_str(), None);
+  assert_eq!(e[2].as_keyword(), None);
+  assert_eq!(e[3].as_bool(), None);
+  assert_eq!(e[8].as_vec(), None);
+  assert!(!e[5].is_nil());
+  assert!(e[8].is_collection());
+  assert!(!e[7].is_collection());
+
+  let mut e = e;
+  e.as_vec_mut().unwrap().truncate(1);
+  assert_eq!(e, edn::read_string("[:k]").unwrap());
+}
+
+#[cfg(feature = "floats")]
+#[test]
+fn floats() {
+  let e = edn::read_string("1.5").unwrap();
+  assert_eq!(e.as_f64(), Some(1.5));
+  assert_eq!(Edn::Int(1).as_f64(), None);
+  assert_eq!(f64::try_from(e.clone()).unwrap(), 1.5);
+  assert_eq!(Edn::from(1.5), e);
+}
+
+#[test]
+fn index() {
+  let e = edn::read_string("{:a [1 {:b 2}] :c (3) 4 5}").unwrap();
+
+  assert_eq!(e[&Edn::Key("a")][1][&Edn::Key("b")], Edn::Int(2));
+  assert_eq!(e[&Edn::Key("c")][0], Edn::Int(3));
+  assert_eq!(e[&Edn::Int(4)], Edn::Int(5));
+  assert_eq!(e[&Edn::Key("a")][2], Edn::Nil);
+  assert_eq!(e[&Edn::Key("x")][&Edn::Key("y")], Edn::Nil);
+  assert_eq!(e[0], Edn::Nil);
+
+  let e = edn::read_string("#:n{:a 1}").unwrap();
+  assert_eq!(e[&Edn::Key("n/a")], Edn::Int(1));
+}
+
+#[test]
+fn into_iter() {
+  let sum: i64 = edn::read_string("[1 2 3]").unwrap().into_iter().filter_map(|e| e.as_i64()).sum();
+  assert_eq!(sum, 6);
+
+  let items: Vec<_> = edn::read_string("#{1 2 3}").unwrap().into_iter().collect();
+  assert_eq!(items, [Edn::Int(1), Edn::Int(2), Edn::Int(3)]);
+
+  let entries: Vec<_> = edn::read_string("{:a 1}").unwrap().into_iter().collect();
+  assert_eq!(entries, [edn::read_string("[:a 1]").unwrap()]);
+
+  assert_eq!(Edn::Int(1).into_iter().count(), 0);
+  assert_eq!(Edn::Nil.into_iter().count(), 0);
+}
+
+#[test]
+fn from() {
+  assert_eq!(Edn::from("str"), Edn::Str("str"));
+  assert_eq!(Edn::from(true), Edn::Bool(true));
+  assert_eq!(Edn::from('c'), Edn::Char('c'));
+  assert_eq!(Edn::from(42_i64), Edn::Int(42));
+  assert_eq!(Edn::from(42_u8), Edn::Int(42));
+  assert_eq!(Edn::from(None::<i64>), Edn::Nil);
+  assert_eq!(Edn::from(Some("str")), Edn::Str("str"));
+  assert_eq!(Edn::from(vec![1, 2]), edn::read_string("[1 2]").unwrap());
+  assert_eq!(Edn::from(BTreeSet::from([1, 2])), edn::read_string("#{1 2}").unwrap());
+  assert_eq!(
+    Edn::from(BTreeMap::from([("a", vec![Some(1), None])])),
+    edn::read_string(r#"{"a" [1 nil]}"#).unwrap()
+  );
+  assert_eq!(
+    Edn::from(HashMap::from([(Edn::Key("a"), 1), (Edn::Key("b"), 2)])),
+    edn::read_string("{:a 1 :b 2}").unwrap()
+  );
+}
+
+#[test]
+fn try_from() {
+  assert_eq!(<&str>::try_from(Edn::Str("str")).unwrap(), "str");
+  assert!(bool::try_from(Edn::Bool(false)).is_ok_and(|b| !b));
+  assert_eq!(char::try_from(Edn::Char('c')).unwrap(), 'c');
+  assert_eq!(i64::try_from(Edn::Int(42)).unwrap(), 42);
+  assert_eq!(Option::<i64>::try_from(Edn::Nil).unwrap(), None);
+  assert_eq!(Option::<i64>::try_from(Edn::Int(1)).unwrap(), Some(1));
+
+  let v: Vec<i64> = edn::read_string("(1 2 3)").unwrap().try_into().unwrap();
+  assert_eq!(v, [1, 2, 3]);
+  let s: BTreeSet<&str> = edn::read_string(r#"#{"a" "b"}"#).unwrap().try_into().unwrap();
+  assert_eq!(s, BTreeSet::from(["a", "b"]));
+  let m: BTreeMap<&str, Vec<Option<bool>>> =
+    edn::read_string(r#"{"a" [true nil]}"#).unwrap().try_into().unwrap();
+  assert_eq!(m, BTreeMap::from([("a", vec![Some(true), None])]));
+  let m: HashMap<i64, &str> = edn::read_string(r#"{1 "one"}"#).unwrap().try_into().unwrap();
+  assert_eq!(m, HashMap::from([(1, "one")]));
+
+  assert_eq!(i64::try_from(Edn::Str("1")).unwrap_err().code, Code::UnexpectedType);
+  assert_eq!(<&str>::try_from(Edn::Key("a")).unwrap_err().code, Code::UnexpectedType);
+  let err = Vec::<i64>::try_from(edn::read_string("[1 :a]").unwrap()).unwrap_err();
+  assert_eq!(err.code, Code::UnexpectedType);
+  let err = Vec::<i64>::try_from(edn::read_string("#{1}").unwrap()).unwrap_err();
+  assert_eq!(err.code, Code::UnexpectedType);
+  let err = BTreeMap::<i64, i64>::try_from(Edn::Nil).unwrap_err();
+  assert_eq!(err.code, Code::UnexpectedType);
+}
@@ -8,7 +8,13 @@ mod test {
   use alloc::vec::Vec;
   use core::fmt;
 
-  use clojure_reader::de::from_str;
+  use clojure_reader::de::{
+    Deserializer, Unused, from_edn, from_str, from_str_lenient, from_str_strict,
+  };
+  use clojure_reader::edn;
+  use clojure_reader::error::Code;
+  use clojure_reader::ser::EnumRepr;
+  use serde::Deserialize as _;
   use serde::de::{self, Visitor};
   use serde_derive::Deserialize;
 
@@ -20,7 +26,7 @@ mod test {
 
     let res = from_str::<u8>("424242");
     let Err(res) = res else { panic!() };
-    let expected = "EdnError { code: Serde(\"can't convert 424242 into u8\"), line: None, column: None, ptr: None }";
+    let expected = "EdnError { code: Serde(\"can't convert 424242 into u8\"), line: Some(1), column: Some(1), ptr: Some(0) }";
     assert_eq!(format!("{res}"), expected);
 
     assert_eq!("lol cats", from_str::<String>(r#""lol cats""#).unwrap());
@@ -38,7 +44,7 @@ mod test {
       maybe_str: Option<String>,
     }
 
-    let res = from_str::<Test>(r#"{:maybe-int 42, 42 "neko", :maybe-str "gato"}"#).unwrap();
+    let res = from_str::<Test>(r#"{:maybe-int 42, :neko "neko", :maybe-str "gato"}"#).unwrap();
     assert_eq!(res, Test { maybe_int: Some(42), maybe_str: Some("gato".to_string()) });
   }
 
@@ -49,6 +55,10 @@ mod test {
     assert_eq!(42, from_str::<u8>("42 ; comment\n").unwrap());
     assert!(from_str::<u8>("42 43").is_err());
     assert!(from_str::<u8>("42 ; comment\n43").is_err());
+
+    let err = from_str::<i64>("1\n\n   )").unwrap_err();
+    assert_eq!(err.code, Code::Serde("trailing input".to_string()));
+    assert_eq!((err.line, err.column, err.ptr), (Some(3), Some(4), Some(6)));
   }
 
   #[test]
@@ -58,7 +68,31 @@ mod test {
       known: u8,
     }
 
-    assert_eq!(Cat { known: 3 }, from_str::<Cat>(r#"{1 #foo/bar 2, :known 3}"#).unwrap());
+    assert_eq!(Cat { known: 3 }, from_str::<Cat>(r#"{:other #foo/bar 2, :known 3}"#).unwrap());
+  }
+
+  #[test]
+  fn struct_keys_that_cant_be_field_names_are_errors() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Cat {
+      foo: u8,
+    }
+
+    assert_eq!(
+      format!("{:?}", from_str::<Cat>(r#"{:foo 42, 42 "bar"}"#).unwrap_err()),
+      "EdnError { code: Serde(\"invalid type: integer `42`, expected field identifier at [42]\"), line: Some(1), column: Some(11), ptr: Some(10) }"
+    );
+    assert_eq!(
+      format!("{:?}", from_str::<Cat>(r#"{:foo 42, [:bar] "bar"}"#).unwrap_err()),
+      "EdnError { code: Serde(\"invalid type: sequence, expected field identifier at [[:bar]]\"), line: Some(1), column: Some(11), ptr: Some(10) }"
+    );
+    assert_eq!(
+      format!(
+        "{:?}",
+        from_edn::<Cat>(edn::read_string("{:foo 42, 42 \"bar\"}").unwrap()).unwrap_err()
+      ),
+      "EdnError { code: Serde(\"invalid type: integer `42`, expected field identifier\"), line: None, column: None, ptr: None }"
+    );
   }
 
   #[test]
@@ -70,6 +104,34 @@ mod test {
     );
   }
 
+  #[test]
+  fn maps_with_keys_that_arent_strings() {
+    assert_eq!(
+      from_str::<BTreeMap<u32, bool>>("{1 true 2 false}").unwrap(),
+      BTreeMap::from([(1, true), (2, false)])
+    );
+    assert_eq!(
+      from_str::<BTreeMap<bool, i64>>("{true 1 false 0}").unwrap(),
+      BTreeMap::from([(true, 1), (false, 0)])
+    );
+    assert_eq!(
+      from_str::<BTreeMap<Vec<u8>, i64>>("{[1 2] 3, [] 0}").unwrap(),
+      BTreeMap::from([(vec![1, 2], 3), (vec![], 0)])
+    );
+    assert_eq!(
+      from_str::<BTreeMap<(String, u32), i64>>(r#"{["a" 1] 2, [:b 3] 4}"#).unwrap(),
+      BTreeMap::from([(("a".to_owned(), 1), 2), (("b".to_owned(), 3), 4)])
+    );
+    assert_eq!(
+      from_str::<BTreeMap<BTreeMap<String, i64>, char>>(r#"{{:a 1} \a}"#).unwrap(),
+      BTreeMap::from([(BTreeMap::from([("a".to_owned(), 1)]), 'a')])
+    );
+    assert_eq!(
+      format!("{:?}", from_str::<BTreeMap<u8, i64>>("{1 1, 256 2}").unwrap_err()),
+      "EdnError { code: Serde(\"can't convert 256 into u8 at [256]\"), line: Some(1), column: Some(7), ptr: Some(6) }"
+    );
+  }
+
   #[test]
   fn large_unsigned_values() {
     #[cfg(feature = "arbitrary-nums")]
@@ -77,7 +139,7 @@ mod test {
       assert_eq!(from_str::<u64>("18446744073709551615N").unwrap(), u64::MAX);
       assert_eq!(
         format!("{:?}", from_str::<u64>("18446744073709551616N")),
-        "Err(EdnError { code: Serde(\"can't convert 18446744073709551616 into u64\"), line: None, column: None, ptr: None })"
+        "Err(EdnError { code: Serde(\"can't convert 18446744073709551616 into u64\"), line: Some(1), column: Some(1), ptr: Some(0) })"
       );
     }
 
@@ -89,7 +151,7 @@ mod test {
   fn errors() {
     let edn_str = r"cat in your nums";
     let res = from_str::<u8>(edn_str);
-    let expected = r#"Err(EdnError { code: Serde("cannot convert Symbol(\"cat\") to i64"), line: None, column: None, ptr: None })"#;
+    let expected = r#"Err(EdnError { code: Serde("cannot convert Symbol(\"cat\") to i64"), line: Some(1), column: Some(1), ptr: Some(0) })"#;
     assert!(res.is_err());
     assert_eq!(format!("{res:?}"), expected);
 
@@ -101,27 +163,27 @@ mod test {
   fn integer_overflow_errors() {
     assert_eq!(
       format!("{:?}", from_str::<i8>("128")),
-      "Err(EdnError { code: Serde(\"can't convert 128 into i8\"), line: None, column: None, ptr: None })"
+      "Err(EdnError { code: Serde(\"can't convert 128 into i8\"), line: Some(1), column: Some(1), ptr: Some(0) })"
     );
     assert_eq!(
       format!("{:?}", from_str::<i16>("32768")),
-      "Err(EdnError { code: Serde(\"can't convert 32768 into i16\"), line: None, column: None, ptr: None })"
+      "Err(EdnError { code: Serde(\"can't convert 32768 into i16\"), line: Some(1), column: Some(1), ptr: Some(0) })"
     );
     assert_eq!(
       format!("{:?}", from_str::<i32>("2147483648")),
-      "Err(EdnError { code: Serde(\"can't convert 2147483648 into i32\"), line: None, column: None, ptr: None })"
+      "Err(EdnError { code: Serde(\"can't convert 2147483648 into i32\"), line: Some(1), column: Some(1), ptr: Some(0) })"
     );
     assert_eq!(
       format!("{:?}", from_str::<u16>("65536")),
-      "Err(EdnError { code: Serde(\"can't convert 65536 into u16\"), line: None, column: None, ptr: None })"
+      "Err(EdnError { code: Serde(\"can't convert 65536 into u16\"), line: Some(1), column: Some(1), ptr: Some(0) })"
     );
     assert_eq!(
       format!("{:?}", from_str::<u32>("4294967296")),
-      "Err(EdnError { code: Serde(\"can't convert 4294967296 into u32\"), line: None, column: None, ptr: None })"
+      "Err(EdnError { code: Serde(\"can't convert 4294967296 into u32\"), line: Some(1), column: Some(1), ptr: Some(0) })"
     );
     assert_eq!(
       format!("{:?}", from_str::<u64>("-1")),
-      "Err(EdnError { code: Serde(\"can't convert -1 into u64\"), line: None, column: None, ptr: None })"
+      "Err(EdnError { code: Serde(\"can't convert -1 into u64\"), line: Some(1), column: Some(1), ptr: Some(0) })"
     );
   }
 
@@ -199,7 +261,7 @@ mod test {
     assert_eq!(from_str::<Vec<u8>>("(1 2 3)").unwrap(), vec![1, 2, 3]);
     assert_eq!(
       format!("{:?}", from_str::<Vec<u8>>("[256]")),
-      "Err(EdnError { code: Serde(\"can't convert 256 into u8\"), line: None, column: None, ptr: None })"
+      "Err(EdnError { code: Serde(\"can't convert 256 into u8 at [0]\"), line: Some(1), column: Some(2), ptr: Some(1) })"
     );
   }
 
@@ -212,11 +274,11 @@ mod test {
 
     assert_eq!(
       format!("{:?}", from_str::<SomeBytes<'_>>(r#"{:data [1 2 3]}"#)),
-      "Err(EdnError { code: Serde(\"invalid type: byte array, expected a borrowed byte array\"), line: None, column: None, ptr: None })"
+      "Err(EdnError { code: Serde(\"invalid type: byte array, expected a borrowed byte array at [:data]\"), line: Some(1), column: Some(8), ptr: Some(7) })"
     );
     assert_eq!(
       format!("{:?}", from_str::<SomeBytes<'_>>(r#"{:data (1 2 3)}"#)),
-      "Err(EdnError { code: Serde(\"invalid type: byte array, expected a borrowed byte array\"), line: None, column: None, ptr: None })"
+      "Err(EdnError { code: Serde(\"invalid type: byte array, expected a borrowed byte array at [:data]\"), line: Some(1), column: Some(8), ptr: Some(7) })"
     );
   }
 
@@ -386,18 +448,109 @@ mod test {
 
     assert_eq!(
       format!("{:?}", from_str::<E>(r#"#B/Unit sillycat"#)),
-      "Err(EdnError { code: Serde(\"namespace in B/Unit can't be matched to E\"), line: None, column: None, ptr: None })"
+      "Err(EdnError { code: Serde(\"namespace in B/Unit can't be matched to E\"), line: Some(1), column: Some(1), ptr: Some(0) })"
     );
     assert_eq!(
       format!("{:?}", from_str::<E>(r#""#)),
-      "Err(EdnError { code: Serde(\"can't convert Nil into Tagged for enum\"), line: None, column: None, ptr: None })"
+      "Err(EdnError { code: Serde(\"can't convert Nil into an enum\"), line: Some(1), column: Some(1), ptr: Some(0) })"
     );
     assert_eq!(
       format!("{:?}", from_str::<E>(r#"#BUnit sillycat"#)),
-      "Err(EdnError { code: Serde(\"Expected namespace in BUnit for Tagged for enum\"), line: None, column: None, ptr: None })"
+      "Err(EdnError { code: Serde(\"Expected namespace in BUnit for Tagged for enum\"), line: Some(1), column: Some(1), ptr: Some(0) })"
     );
   }
 
+  #[test]
+  fn enum_reprs() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    enum E {
+      Unit,
+      Newtype(u32),
+      Tuple(u32, u32),
+      Struct { a: u32, b: usize },
+    }
+
+    let all = vec![E::Unit, E::Newtype(1), E::Tuple(1, 2), E::Struct { a: 1, b: 42 }];
+    assert_eq!(
+      from_str::<Vec<E>>("[:Unit {:Newtype 1} [:Tuple [1 2]] #E/Struct {:a 1, :b 42}]").unwrap(),
+      all
+    );
+
+    let source = "[#my.app/Unit nil {:Newtype 1} [:Tuple [1 2]] #my.app/Struct {:a 1, :b 42}]";
+    let mut de = Deserializer::from_str(source).enums(EnumRepr::Namespaced("my.app"));
+    assert_eq!(Vec::<E>::deserialize(&mut de).unwrap(), all);
+    assert_eq!(
+      format!("{:?}", from_str::<E>("#my.app/Unit nil")),
+      "Err(EdnError { code: Serde(\"namespace in my.app/Unit can't be matched to E\"), line: Some(1), column: Some(1), ptr: Some(0) })"
+    );
+    assert_eq!(
+      format!("{:?}", from_str::<E>("{:Newtype 1 :Unit nil}")),
+      "Err(EdnError { code: Serde(\"can't convert Map({Key(\\\"Newtype\\\"): Int(1), Key(\\\"Unit\\\"): Nil}) into an enum\"), line: Some(1), column: Some(1), ptr: Some(0) })"
+    );
+    assert_eq!(
+      format!("{:?}", from_str::<E>("[1 2]")),
+      "Err(EdnError { code: Serde(\"expected a keyword for the variant of an enum\"), line: Some(1), column: Some(1), ptr: Some(0) })"
+    );
+  }
+
+  #[test]
+  fn namespaced_fields() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    #[serde(rename = "#:user")]
+    struct User {
+      name: String,
+      #[serde(rename = "account/id")]
+      id: u32,
+    }
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Plain {
+      name: String,
+    }
+
+    let ada = User { name: "ada".to_owned(), id: 1 };
+    assert_eq!(from_str::<User>("{:user/name \"ada\" :account/id 1}").unwrap(), ada);
+    assert_eq!(from_str::<User>("#:user{:name \"ada\" :account/id 1}").unwrap(), ada);
+    assert_eq!(from_str::<User>("{:name \"ada\" :account/id 1}").unwrap(), ada);
+    assert_eq!(
+      from_edn::<User>(edn::read_string("#:user{:name \"ada\" :account/id 1}").unwrap()).unwrap(),
+      ada
+    );
+    assert_eq!(
+      from_str::<Plain>("#:user{:_/name \"bob\" :user/id 2}").unwrap(),
+      Plain { name: "bob".to_owned() }
+    );
+    assert_eq!(
+      format!("{:?}", from_str_strict::<User>("{:user/name \"ada\" :account/id 1 :admin/id 2}")),
+      "Err(EdnError { code: Serde(\"ignored value at [:admin/id]\"), line: Some(1), column: Some(43), ptr: Some(42) })"
+    );
+
+    let mut de =
+      Deserializer::from_str("[{:app/name \"bob\"} #:app{:name \"eve\"}]").namespace("app");
+    assert_eq!(
+      Vec::<Plain>::deserialize(&mut de).unwrap(),
+      [Plain { name: "bob".to_owned() }, Plain { name: "eve".to_owned() }]
+    );
+
+    let map = from_str::<BTreeMap<String, u32>>("#:a{:x 1 :b/y 2 :_/z 3 \"s\" 4}").unwrap();
+    assert_eq!(
+      map,
+      BTreeMap::from([("a/x".into(), 1), ("b/y".into(), 2), ("z".into(), 3), ("s".into(), 4)])
+    );
+    let edn = edn::read_string("#:a{:x 1 :_/z 3}").unwrap();
+    let map = from_edn::<BTreeMap<String, u32>>(edn).unwrap();
+    assert_eq!(map, BTreeMap::from([("a/x".into(), 1), ("z".into(), 3)]));
+
+    // The same key twice, once in the namespace of the map
+    let err = from_str::<BTreeMap<String, u32>>("#:a{:b 1 :a/b 2}").unwrap_err();
+    assert_eq!((err.code, err.ptr), (Code::HashMapDuplicateKey, Some(9)));
+    let edn = edn::read_string("#:a{:b 1 :a/b 2}").unwrap();
+    let err = from_edn::<BTreeMap<String, u32>>(edn).unwrap_err();
+    assert_eq!(err.code, Code::HashMapDuplicateKey);
+    let err = from_str::<Vec<BTreeMap<String, u32>>>("#{#:a{:b 1 :a/b 2}}").unwrap_err();
+    assert_eq!(err.code, Code::HashMapDuplicateKey);
+  }
+
   #[test]
   fn internally_tagged_enum() {
     #[derive(Deserialize, PartialEq, Debug)]
@@ -429,8 +582,23 @@ mod test {
   fn serde_errors() {
     assert_eq!(
       format!("{:?}", from_str::<String>(r#"#E/Tuple [4/2]"#)),
-      "Err(EdnError { code: Serde(\"Don't know how to convert Tagged(\\\"E/Tuple\\\", Vector([Rational((4, 2))])) into any\"), line: None, column: None, ptr: None })"
+      "Err(EdnError { code: Serde(\"invalid type: tagged value, expected a string\"), line: Some(1), column: Some(1), ptr: Some(0) })"
+    );
+    // Tags and ratios only come apart for `types::Tagged` and `types::Rational`
+    let err = from_str::<(String, i64)>("#foo 1").unwrap_err();
+    assert_eq!(
+      err.code,
+      Code::Serde("invalid type: tagged value, expected a tuple of size 2".into())
     );
+    let err = from_str::<Vec<i64>>("1/2").unwrap_err();
+    assert_eq!(err.code, Code::Serde("invalid type: ratio, expected a sequence".into()));
+    #[derive(Deserialize, Debug)]
+    struct A {
+      #[expect(dead_code)]
+      a: i64,
+    }
+    let err = from_str::<A>("#foo {:a 1}").unwrap_err();
+    assert_eq!(err.code, Code::Serde("invalid type: tagged value, expected struct A".into()));
 
     #[derive(Deserialize, PartialEq, Debug)]
     struct SomeBytes<'a> {
@@ -438,7 +606,267 @@ mod test {
     }
     assert_eq!(
       format!("{:?}", from_str::<SomeBytes<'_>>(r#"[4/2]"#)),
-      "Err(EdnError { code: Serde(\"can't convert Rational((4, 2)) into bytes\"), line: None, column: None, ptr: None })"
+      "Err(EdnError { code: Serde(\"can't convert Rational((4, 2)) into bytes at [0]\"), line: Some(1), column: Some(2), ptr: Some(1) })"
     );
   }
+
+  #[test]
+  fn errors_point_at_the_failing_field() {
+    #[derive(Deserialize, Debug)]
+    struct Server {
+      #[expect(dead_code)]
+      port: u16,
+    }
+
+    #[derive(Deserialize, Debug)]
+    struct Config {
+      #[expect(dead_code)]
+      servers: Vec<Server>,
+    }
+
+    let config = r#"{:servers [{:port 80}
+           {:port 443}
+           {:port "8080"}]}"#;
+    assert_eq!(
+      format!("{:?}", from_str::<Config>(config)),
+      "Err(EdnError { code: Serde(\"cannot convert Str(\\\"8080\\\") to i64 at [:servers 2 :port]\"), line: Some(3), column: Some(19), ptr: Some(63) })"
+    );
+
+    assert_eq!(
+      format!("{:?}", from_str::<Config>("{:servers [{:port 80} {}]}")),
+      "Err(EdnError { code: Serde(\"missing field `port` at [:servers 1]\"), line: Some(1), column: Some(23), ptr: Some(22) })"
+    );
+
+    assert_eq!(
+      format!("{:?}", from_str::<BTreeMap<i64, u8>>("{1 2, :cat 3}")),
+      "Err(EdnError { code: Serde(\"invalid type: string \\\"cat\\\", expected i64 at [:cat]\"), line: Some(1), column: Some(7), ptr: Some(6) })"
+    );
+  }
+
+  #[test]
+  fn skipped_values_are_still_validated() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Cat {
+      known: u8,
+    }
+
+    let err = from_str::<Cat>("{:known 3, :unknown {:a 1 :a 2}}").unwrap_err();
+    assert_eq!(err.code, clojure_reader::error::Code::HashMapDuplicateKey);
+    assert_eq!((err.line, err.column, err.ptr), (Some(1), Some(31), Some(30)));
+  }
+
+  #[derive(Deserialize, PartialEq, Debug)]
+  #[serde(rename_all = "kebab-case")]
+  enum Shape {
+    Dot,
+    Circle { r: f32 },
+  }
+
+  #[derive(Deserialize, PartialEq, Debug)]
+  struct Drawing {
+    shapes: Vec<Shape>,
+    scale: Option<f64>,
+  }
+
+  #[test]
+  fn strict_fails_on_what_is_ignored() {
+    let drawing = from_str_strict::<Drawing>("{:shapes [:dot {:circle {:r 0.5}}] :scale 2}");
+    assert_eq!(
+      drawing.unwrap(),
+      Drawing { shapes: vec![Shape::Dot, Shape::Circle { r: 0.5 }], scale: Some(2.0) }
+    );
+
+    let errors = [
+      ("{:shapes [] :color :red}", "ignored value at [:color]", 1, 20),
+      ("{:shapes [{:circle {:r 1.0 :fill :red}}]}", "ignored value at [:shapes 0 :fill]", 1, 11),
+      ("{:shapes [[:dot :big]]}", "ignored value at [:shapes 0]", 1, 11),
+      ("{:shapes [#Shape/dot 1]}", "ignored value at [:shapes 0]", 1, 22),
+      (
+        "{:shapes [{:circle {:r 0.123456789}}]}",
+        "lossy conversion of 0.123456789 into f32 at [:shapes 0 :r]",
+        1,
+        11,
+      ),
+      (
+        "{:shapes [] :scale 9007199254740993}",
+        "lossy conversion of 9007199254740993 into f64 at [:scale]",
+        1,
+        20,
+      ),
+      ("{:shapes #{:dot} :note #inst \"2024\"}", "ignored #inst value at [:note]", 1, 24),
+    ];
+    for (source, message, line, column) in errors {
+      let err = from_str_strict::<Drawing>(source).unwrap_err();
+      assert_eq!(err.code, clojure_reader::error::Code::Serde(message.to_string()), "{source}");
+      assert_eq!((err.line, err.column), (Some(line), Some(column)), "{source}");
+      assert!(from_str::<Drawing>(source).is_ok(), "{source}");
+    }
+
+    let err = from_str_strict::<Vec<(String, i64)>>("[#foo 1]").unwrap_err();
+    assert_eq!(err.code, Code::Serde("dropped #foo tag at [0]".into()));
+    assert_eq!((err.line, err.column), (Some(1), Some(2)));
+
+    let err = from_str_strict::<(u8, u8)>("[1 2 3]").unwrap_err();
+    assert_eq!(
+      format!("{err:?}"),
+      "EdnError { code: Serde(\"ignored value at [2]\"), line: Some(1), column: Some(6), ptr: Some(5) }"
+    );
+  }
+
+  #[test]
+  fn lenient_lists_what_is_ignored() {
+    let source = "{:shapes [:dot {:circle {:r 1.0, :fill :red}}]\n :color :red, :scale 2.5}";
+    let (drawing, warnings) = from_str_lenient::<Drawing>(source).unwrap();
+    assert_eq!(
+      drawing,
+      Drawing { shapes: vec![Shape::Dot, Shape::Circle { r: 1.0 }], scale: Some(2.5) }
+    );
+    let warnings: Vec<_> = warnings.iter().map(ToString::to_string).collect();
+    assert_eq!(
+      warnings,
+      [
+        "ignored value at [:shapes 1 :fill] on line 1, column 16",
+        "ignored value at [:color] on line 2, column 9",
+      ]
+    );
+
+    let mut de = Deserializer::from_str("[1 2] {:a 1}").unused(Unused::Warn);
+    assert_eq!(<(u8,)>::deserialize(&mut de).unwrap(), (1,));
+    assert_eq!(de.warnings()[0].path, "[1]");
+    assert_eq!(de.warnings()[0].ptr, 3);
+    let _ = de::IgnoredAny::deserialize(&mut de).unwrap();
+    assert_eq!(de.warnings()[1].path, "[]");
+    assert!(from_str_lenient::<Drawing>("{:shapes []}").unwrap().1.is_empty());
+  }
+
+  #[test]
+  fn spanned_values_know_where_they_came_from() {
+    use clojure_reader::de::Spanned;
+
+    #[derive(Deserialize, Debug)]
+    struct Server<'a> {
+      name: Spanned<String>,
+      port: Spanned<u32>,
+      #[serde(borrow)]
+      tags: Option<Spanned<Vec<Spanned<&'a str>>>>,
+    }
+
+    let source = "{:name \"cat\"\n :port 70000\n :tags [\"猫\" \"gato\"]}";
+    let server = from_str::<Server<'_>>(source).unwrap();
+
+    let index = clojure_reader::line_index::LineIndex::new(source);
+    let located = |span: std::ops::Range<usize>| {
+      let start = index.line_col(span.start);
+      (start.line, start.column, span.start, span.end)
+    };
+
+    assert_eq!(server.name.get_ref(), "cat");
+    assert_eq!(located(server.name.span()), (1, 8, 7, 12));
+    assert_eq!(located(server.port.span()), (2, 8, 20, 25));
+    assert_eq!(server.port.into_inner(), 70000);
+
+    let tags = server.tags.unwrap();
+    assert_eq!(located(tags.span()), (3, 8, 33, 47));
+    let tags = tags.into_inner();
+    assert_eq!(tags[0], tags[0].clone());
+    assert_eq!(*tags[1].get_ref(), "gato");
+    assert_eq!(located(tags[1].span()), (3, 13, 40, 46));
+
+    assert_eq!(clojure_reader::ser::to_string(&tags).unwrap(), r#"["猫" "gato"]"#);
+
+    // Set elements are read before they're deserialized, and still know where they came from
+    let elements = from_str::<Vec<Spanned<Spanned<u8>>>>("#{1 #_0 22}").unwrap();
+    let spans: Vec<_> =
+      elements.iter().map(|spanned| (spanned.span(), spanned.get_ref().span())).collect();
+    assert_eq!(spans, [(2..3, 2..3), (8..10, 8..10)]);
+  }
+
+  #[test]
+  fn spanned_values_need_a_source() {
+    use clojure_reader::de::Spanned;
+    use clojure_reader::edn::Edn;
+    use serde::Deserialize;
+
+    assert_eq!(
+      format!("{:?}", Spanned::<i64>::deserialize(Edn::Int(42))),
+      "Err(EdnError { code: Serde(\"invalid type: integer `42`, expected a spanned value\"), line: None, column: None, ptr: None })"
+    );
+
+    // Only the span of the whole map is known
+    #[derive(Deserialize, Debug)]
+    enum E {
+      #[expect(dead_code)]
+      Newtype(Spanned<i64>),
+    }
+    assert_eq!(
+      format!("{:?}", from_str::<E>("{:Newtype 42}")),
+      "Err(EdnError { code: Serde(\"invalid type: integer `42`, expected a spanned value\"), line: Some(1), column: Some(1), ptr: Some(0) })"
+    );
+  }
+
+  #[test]
+  fn deserializer_streams_values() {
+    use clojure_reader::de::Deserializer;
+    use clojure_reader::error::Code;
+    use serde::Deserialize;
+
+    let mut de = Deserializer::from_str("[1 #_ (ignored) 2] ; done");
+    assert_eq!(Vec::<u8>::deserialize(&mut de).unwrap(), [1, 2]);
+    de.end().unwrap();
+
+    let mut de = Deserializer::from_str("[1 2] 3");
+    assert_eq!(Vec::<u8>::deserialize(&mut de).unwrap(), [1, 2]);
+    let err = de.end().unwrap_err();
+    assert_eq!(err.code, Code::Serde("trailing input".to_string()));
+    assert_eq!((err.line, err.column, err.ptr), (Some(1), Some(7), Some(6)));
+
+    // Elements past the end of a tuple are read all the same
+    let err = from_str::<(u8,)>("[1 #{2 2}]").unwrap_err();
+    assert_eq!(err.code, Code::SetDuplicateKey);
+    assert_eq!(err.ptr, Some(8));
+
+    // Duplicates point at the second key
+    let err = from_str::<BTreeMap<String, u8>>("{:a 1\n :a 2}").unwrap_err();
+    assert_eq!(err.code, Code::HashMapDuplicateKey);
+    assert_eq!((err.line, err.column, err.ptr), (Some(2), Some(2), Some(7)));
+  }
+
+  #[test]
+  fn raw_edn_keeps_the_source() {
+    use clojure_reader::de::RawEdn;
+    use clojure_reader::edn::Edn;
+    use clojure_reader::error::Code;
+    use serde::Deserialize;
+
+    #[derive(Deserialize, Debug)]
+    struct Message<'a> {
+      id: u8,
+      #[serde(borrow)]
+      payload: RawEdn<'a>,
+    }
+
+    let source = "{:payload #_ :old #inst \"2024\" ; when\n :id 1}";
+    let message = from_str::<Message<'_>>(source).unwrap();
+    assert_eq!((message.id, message.payload.get()), (1, "#inst \"2024\""));
+
+    let source = "{:payload {:a [1 2] ; one and two\n  :b #{}}, :id 2}";
+    let message = from_str::<Message<'_>>(source).unwrap();
+    assert_eq!(message.payload.to_string(), "{:a [1 2] ; one and two\n  :b #{}}");
+
+    assert_eq!(from_str::<RawEdn<'_>>("  (+ 1 2) ").unwrap().get(), "(+ 1 2)");
+    assert_eq!(from_str::<Vec<RawEdn<'_>>>("[nil,:a]").unwrap()[1].get(), ":a");
+    let set = from_str::<Vec<RawEdn<'_>>>("#{[1 2] #_0 {:a  1}}").unwrap();
+    assert_eq!(set.iter().map(RawEdn::get).collect::<Vec<_>>(), ["[1 2]", "{:a  1}"]);
+    let err = from_str::<BTreeMap<RawEdn<'_>, u8>>("{[1 2] 3}").unwrap_err();
+    assert!(err.to_string().contains("expected raw EDN"), "{err}");
+
+    // It is still read
+    let err = from_str::<Message<'_>>("{:id 3 :payload {:a 1 :a 2}}").unwrap_err();
+    assert_eq!(err.code, Code::HashMapDuplicateKey);
+    let err = from_str::<Message<'_>>("{:id 3 :payload [1 2}}").unwrap_err();
+    assert_eq!(err.code, Code::UnmatchedDelimiter('}'));
+
+    // Without a source there's nothing to keep
+    assert!(RawEdn::deserialize(Edn::Key("a")).is_err());
+  }
 }
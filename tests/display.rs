@@ -44,7 +44,10 @@ fn collections() {
 
   display_diff!("(-66 [false true] 4/2 \"space cat\")", "(-0x42 [false true] 4/2 \"space cat\")");
   display_diff!("{:cat [1 2 3], :猫 \"cat\"}", "{:cat [1 2 3] :猫　\"cat\"}");
+  #[cfg(not(feature = "preserve-order"))]
   display_diff!("#{[1 2 3] :cat}", "#{:cat [1 2 3]}");
+  #[cfg(feature = "preserve-order")]
+  display!("#{:cat [1 2 3]}");
 }
 
 #[test]
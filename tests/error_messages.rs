@@ -179,3 +179,19 @@ fn test_unexpected_eof_in_discard() {
     "EdnError { code: UnexpectedEOF, line: Some(1), column: Some(3), ptr: Some(2) }"
   );
 }
+
+#[test]
+fn invalid_escapes() {
+  assert_eq!(
+    err_as_string(r#""foo \"bar\" \x""#),
+    "EdnError { code: InvalidEscape, line: Some(1), column: Some(16), ptr: Some(15) }"
+  );
+  assert_eq!(
+    err_as_string(r#""猫\猫""#),
+    "EdnError { code: InvalidEscape, line: Some(1), column: Some(5), ptr: Some(8) }"
+  );
+  assert_eq!(
+    err_as_string(r#""foo\"#),
+    "EdnError { code: UnexpectedEOF, line: Some(1), column: Some(6), ptr: Some(5) }"
+  );
+}
@@ -0,0 +1,52 @@
+use clojure_reader::edn;
+use clojure_reader::line_index::{LineCol, LineIndex};
+
+#[test]
+fn lines() {
+  let index = LineIndex::new("");
+  assert_eq!(index.line_count(), 1);
+  assert_eq!(index.line_col(0), LineCol { line: 1, column: 1 });
+
+  let source = "[1\n2\r\n3]\n";
+  let index = LineIndex::new(source);
+  assert_eq!(index.line_count(), 4);
+  assert_eq!(index.line_col(3), LineCol { line: 2, column: 1 });
+  assert_eq!(index.line_col(6), LineCol { line: 3, column: 1 });
+  assert_eq!(index.line_col(source.len()), LineCol { line: 4, column: 1 });
+  // Past the end is clamped
+  assert_eq!(index.line_col(42), LineCol { line: 4, column: 1 });
+}
+
+#[test]
+fn offsets() {
+  let source = "(猫 𝄞)\n:cat";
+  let index = LineIndex::new(source);
+
+  assert_eq!(index.offset(LineCol { line: 1, column: 4 }), Some(5));
+  assert_eq!(index.offset_utf16(LineCol { line: 1, column: 4 }), Some(5));
+  assert_eq!(index.offset(LineCol { line: 1, column: 5 }), Some(9));
+  assert_eq!(index.offset_utf16(LineCol { line: 1, column: 6 }), Some(9));
+  // The end of a line is still on it, the newline isn't
+  assert_eq!(index.offset(LineCol { line: 1, column: 6 }), Some(10));
+  assert_eq!(index.offset(LineCol { line: 1, column: 7 }), None);
+  // Inside the surrogate pair
+  assert_eq!(index.offset_utf16(LineCol { line: 1, column: 5 }), None);
+  assert_eq!(index.offset(LineCol { line: 2, column: 5 }), Some(source.len()));
+  assert_eq!(index.offset(LineCol { line: 3, column: 1 }), None);
+  assert_eq!(index.offset(LineCol { line: 0, column: 1 }), None);
+
+  for ptr in source.char_indices().map(|(ptr, _)| ptr) {
+    assert_eq!(index.offset(index.line_col(ptr)), Some(ptr));
+    assert_eq!(index.offset_utf16(index.line_col_utf16(ptr)), Some(ptr));
+  }
+}
+
+#[test]
+fn matches_errors() {
+  let source = "{:猫 42\n :gato [1 2\n  #{:a :a}]}";
+  let err = edn::read_string(source).unwrap_err();
+  let line_col = LineIndex::new(source).line_col(err.ptr.unwrap());
+
+  assert_eq!((err.line, err.column), (Some(line_col.line), Some(line_col.column)));
+  assert_eq!(line_col, LineCol { line: 3, column: 10 });
+}
@@ -1,8 +1,5 @@
-extern crate alloc;
-
-use alloc::collections::BTreeMap;
-
 use clojure_reader::edn::{self, Edn};
+use clojure_reader::error::Code;
 
 #[test]
 fn get() {
@@ -44,7 +41,7 @@ fn default_map_namespace_syntax() {
       cfg.get(&Edn::Key("thingy")),
       Some(&Edn::Tagged(
         ":foo",
-        Box::new(Edn::Map(BTreeMap::from([(Edn::Key("bar"), Edn::Str("baz"))])))
+        Box::new(Edn::Map(edn::Map::from([(Edn::Key("bar"), Edn::Str("baz"))])))
       ))
     );
     assert_eq!(cfg.get(&Edn::Key("more")), Some(&Edn::Str("stuff")));
@@ -65,7 +62,7 @@ fn default_map_namespace_syntax() {
       cfg.get(&Edn::Key("thingy")),
       Some(&Edn::Tagged(
         "foo",
-        Box::new(Edn::Map(BTreeMap::from([(Edn::Key("bar"), Edn::Str("baz"))])))
+        Box::new(Edn::Map(edn::Map::from([(Edn::Key("bar"), Edn::Str("baz"))])))
       ))
     );
     assert_eq!(cfg.get(&Edn::Key("more")), Some(&Edn::Str("stuff")));
@@ -122,3 +119,257 @@ fn get_contains() {
   assert_eq!(edn_data.contains(&Edn::Int(42)), false);
   assert_eq!(edn_data.contains(&Edn::Key("foo")), false);
 }
+
+#[test]
+fn walk() {
+  let edn = edn::read_string("[1 (2 #{3 4}) {:a 5}]").unwrap();
+
+  let mut order = vec![];
+  let _ = edn.clone().prewalk(|edn| {
+    order.push(edn.to_string());
+    edn
+  });
+  assert_eq!(
+    order,
+    ["[1 (2 #{3 4}) {:a 5}]", "1", "(2 #{3 4})", "2", "#{3 4}", "3", "4", "{:a 5}", ":a", "5"]
+  );
+
+  let mut order = vec![];
+  let walked = edn.postwalk(|edn| {
+    order.push(edn.to_string());
+    match edn {
+      Edn::Int(i) => Edn::Int(i / 2),
+      edn => edn,
+    }
+  });
+  assert_eq!(
+    order,
+    ["1", "2", "3", "4", "#{1 2}", "(1 #{1 2})", ":a", "5", "{:a 2}", "[0 (1 #{1 2}) {:a 2}]"]
+  );
+  // Equal elements merge
+  assert_eq!(walked, edn::read_string("[0 (1 #{1 2}) {:a 2}]").unwrap());
+
+  let walked = edn::read_string("#inst \"x\"").unwrap().postwalk(|edn| match edn {
+    Edn::Str(_) => Edn::Str("y"),
+    edn => edn,
+  });
+  assert_eq!(walked.to_string(), "#inst \"y\"");
+}
+
+#[test]
+fn visit() {
+  // Sums the numbers outside of lists
+  struct Sum {
+    total: i64,
+    depth: usize,
+  }
+
+  impl<'e> edn::Visitor<'e> for Sum {
+    fn enter(&mut self, edn: &Edn<'e>) -> bool {
+      if let Edn::Int(i) = edn {
+        self.total += i;
+      }
+      self.depth += 1;
+      !matches!(edn, Edn::List(_))
+    }
+
+    fn leave(&mut self, _edn: &Edn<'e>) {
+      self.depth -= 1;
+    }
+  }
+
+  let mut sum = Sum { total: 0, depth: 0 };
+  edn::read_string("[1 (2 3) {4 [5]} #tag 6]").unwrap().visit(&mut sum);
+  assert_eq!((sum.total, sum.depth), (16, 0));
+
+  let mut count = 0;
+  edn::read_string("{:a [1 2]}").unwrap().visit(&mut |_: &Edn<'_>| count += 1);
+  assert_eq!(count, 5);
+}
+
+#[test]
+fn zipper() {
+  use edn::Zipper;
+
+  let mut zipper = Zipper::new(edn::read_string("[1 {:a 2 :b 3} #tag 4]").unwrap());
+  assert!(zipper.up().is_none() && zipper.right().is_none());
+  assert!(zipper.insert_right(Edn::Nil).is_none() && zipper.remove().is_none());
+
+  zipper.down().unwrap().right().unwrap().down().unwrap().right().unwrap();
+  assert_eq!(zipper.node(), &Edn::Int(2));
+  assert!(zipper.insert_right(Edn::Nil).is_none());
+  // Goes with its key, on to the value before it
+  zipper.right().unwrap().right().unwrap().remove().unwrap();
+  assert_eq!(zipper.node(), &Edn::Int(2));
+  zipper.edit(|edn| Edn::Vector(vec![edn]));
+
+  zipper.up().unwrap().right().unwrap().down().unwrap();
+  assert!(zipper.remove().is_none() && zipper.insert_left(Edn::Nil).is_none());
+  zipper.replace(Edn::Int(5)).up().unwrap().left().unwrap().left().unwrap();
+  zipper.insert_left(Edn::Int(0)).unwrap().insert_right(Edn::Int(9)).unwrap();
+  assert_eq!(zipper.node(), &Edn::Int(1));
+
+  // Where it was when there's nothing to find
+  assert!(zipper.find_next_value(&Edn::Int(6)).is_none());
+  assert_eq!(zipper.node(), &Edn::Int(1));
+  zipper.find_next(|edn| matches!(edn, Edn::Vector(_))).unwrap();
+  // Not the vector it's at
+  assert!(zipper.find_next(|edn| matches!(edn, Edn::Vector(_))).is_none());
+  zipper.down().unwrap();
+  assert_eq!(zipper.node(), &Edn::Int(2));
+  // The first one goes up
+  zipper.remove().unwrap();
+  assert_eq!(zipper.node(), &Edn::Vector(vec![]));
+
+  assert_eq!(zipper.root(), edn::read_string("[0 1 9 {:a []} #tag 5]").unwrap());
+}
+
+#[test]
+fn get_in() {
+  let e = edn::read_string("{:a [{:b #{1 2}} (3)] :c #:n{:d 4}}").unwrap();
+
+  assert_eq!(e.get_in(&[]), Some(&e));
+  assert_eq!(
+    e.get_in(&[Edn::Key("a"), Edn::Int(0), Edn::Key("b"), Edn::Int(2)]),
+    Some(&Edn::Int(2))
+  );
+  assert_eq!(e.get_in(&[Edn::Key("a"), Edn::Int(2)]), None);
+  assert_eq!(e.get_in(&[Edn::Key("a"), Edn::Int(-1)]), None);
+  assert_eq!(e.get_in(&[Edn::Key("a"), Edn::Int(1), Edn::Int(0)]), None);
+  assert_eq!(e.get_in(&[Edn::Key("c"), Edn::Key("n/d")]), Some(&Edn::Int(4)));
+  assert_eq!(e.get_in(&[Edn::Key("x"), Edn::Key("y")]), None);
+
+  let mut e = e;
+  *e.get_in_mut(&[Edn::Key("a"), Edn::Int(0), Edn::Key("b")]).unwrap() = Edn::Nil;
+  *e.get_in_mut(&[Edn::Key("c"), Edn::Key("n/d")]).unwrap() = Edn::Int(5);
+  assert_eq!(e.get_in_mut(&[Edn::Key("a"), Edn::Int(9)]), None);
+  assert_eq!(e, edn::read_string("{:a [{:b nil} (3)] :c #:n{:d 5}}").unwrap());
+}
+
+#[test]
+fn assoc() {
+  let mut e = Edn::Nil;
+  e.assoc(&Edn::Key("a"), Edn::Int(1)).unwrap();
+  assert_eq!(e, edn::read_string("{:a 1}").unwrap());
+
+  let mut e = edn::read_string("[1 2]").unwrap();
+  e.assoc(&Edn::Int(0), Edn::Int(0)).unwrap();
+  e.assoc(&Edn::Int(2), Edn::Int(3)).unwrap();
+  assert_eq!(e, edn::read_string("[0 2 3]").unwrap());
+  assert_eq!(e.assoc(&Edn::Int(4), Edn::Nil).unwrap_err().code, Code::InvalidIndex);
+  assert_eq!(e.assoc(&Edn::Key("a"), Edn::Nil).unwrap_err().code, Code::InvalidIndex);
+
+  let mut e = edn::read_string("(1 2)").unwrap();
+  assert_eq!(e.assoc(&Edn::Int(0), Edn::Nil).unwrap_err().code, Code::NotAssociative);
+  let mut e = Edn::Int(1);
+  assert_eq!(e.assoc(&Edn::Int(0), Edn::Nil).unwrap_err().code, Code::NotAssociative);
+}
+
+#[test]
+fn assoc_update_in() {
+  let mut e = edn::read_string("{:a [{:b 1}]}").unwrap();
+  e.assoc_in(&[Edn::Key("a"), Edn::Int(0), Edn::Key("b")], Edn::Int(2)).unwrap();
+  e.assoc_in(&[Edn::Key("a"), Edn::Int(1), Edn::Key("c")], Edn::Int(3)).unwrap();
+  e.assoc_in(&[Edn::Key("d"), Edn::Key("e")], Edn::Int(4)).unwrap();
+  assert_eq!(e, edn::read_string("{:a [{:b 2} {:c 3}] :d {:e 4}}").unwrap());
+
+  let err = e.assoc_in(&[Edn::Key("d"), Edn::Key("e"), Edn::Key("f")], Edn::Nil).unwrap_err();
+  assert_eq!(err.code, Code::NotAssociative);
+  let err = e.assoc_in(&[Edn::Key("a"), Edn::Int(5)], Edn::Nil).unwrap_err();
+  assert_eq!(err.code, Code::InvalidIndex);
+
+  e.update_in(&[Edn::Key("d"), Edn::Key("e")], |e| match e {
+    Edn::Int(i) => Edn::Int(i * 10),
+    e => e,
+  })
+  .unwrap();
+  e.update_in(&[Edn::Key("g")], |e| {
+    assert_eq!(e, Edn::Nil);
+    Edn::Int(5)
+  })
+  .unwrap();
+  assert_eq!(e, edn::read_string("{:a [{:b 2} {:c 3}] :d {:e 40} :g 5}").unwrap());
+
+  e.assoc_in(&[], Edn::Nil).unwrap();
+  assert_eq!(e, Edn::Nil);
+
+  let mut e = edn::read_string("#:n{:a 1}").unwrap();
+  e.assoc_in(&[Edn::Key("n/a")], Edn::Int(2)).unwrap();
+  assert_eq!(e, edn::read_string("#:n{:a 2}").unwrap());
+  e.assoc(&Edn::Key("m/b"), Edn::Int(3)).unwrap();
+  assert_eq!(e, edn::read_string("#:n{:a 2 :m/b 3}").unwrap());
+  assert_eq!(e.assoc(&Edn::Key("c"), Edn::Nil).unwrap_err().code, Code::NamespacedMapKey);
+  let err = e.assoc_in(&[Edn::Key("c"), Edn::Key("d")], Edn::Nil).unwrap_err();
+  assert_eq!(err.code, Code::NamespacedMapKey);
+  let mut e = edn::read_string("#inst {:a 1}").unwrap();
+  assert_eq!(e.assoc(&Edn::Key("b/c"), Edn::Nil).unwrap_err().code, Code::NotAssociative);
+}
+
+#[test]
+fn dissoc() {
+  let mut e = edn::read_string("{:a 1 :b 2}").unwrap();
+  assert_eq!(e.dissoc(&Edn::Key("a")).unwrap(), Some(Edn::Int(1)));
+  assert_eq!(e.dissoc(&Edn::Key("a")).unwrap(), None);
+  assert_eq!(e, edn::read_string("{:b 2}").unwrap());
+
+  let mut e = edn::read_string("#:n{:a 1 :b 2}").unwrap();
+  assert_eq!(e.dissoc(&Edn::Key("n/a")).unwrap(), Some(Edn::Int(1)));
+  assert_eq!(e, edn::read_string("#:n{:b 2}").unwrap());
+
+  assert_eq!(Edn::Nil.dissoc(&Edn::Key("a")).unwrap(), None);
+  assert_eq!(Edn::Vector(vec![]).dissoc(&Edn::Int(0)).unwrap_err().code, Code::NotAssociative);
+}
+
+#[test]
+fn conj_merge_into() {
+  let mut e = edn::read_string("[1]").unwrap();
+  e.conj(Edn::Int(2)).unwrap();
+  assert_eq!(e, edn::read_string("[1 2]").unwrap());
+
+  let mut e = edn::read_string("(1)").unwrap();
+  e.conj(Edn::Int(2)).unwrap();
+  assert_eq!(e, edn::read_string("(2 1)").unwrap());
+
+  let mut e = edn::read_string("#{1}").unwrap();
+  e.conj(Edn::Int(1)).unwrap();
+  e.conj(Edn::Int(2)).unwrap();
+  assert_eq!(e, edn::read_string("#{1 2}").unwrap());
+
+  let mut e = Edn::Nil;
+  e.conj(Edn::Int(1)).unwrap();
+  assert_eq!(e, edn::read_string("(1)").unwrap());
+
+  let mut e = edn::read_string("{:a 1}").unwrap();
+  e.conj(edn::read_string("[:b 2]").unwrap()).unwrap();
+  e.conj(edn::read_string("{:a 3}").unwrap()).unwrap();
+  assert_eq!(e, edn::read_string("{:a 3 :b 2}").unwrap());
+  assert_eq!(e.conj(edn::read_string("[:c]").unwrap()).unwrap_err().code, Code::InvalidMapEntry);
+  assert_eq!(e.conj(Edn::Int(1)).unwrap_err().code, Code::InvalidMapEntry);
+  assert_eq!(Edn::Int(1).conj(Edn::Int(1)).unwrap_err().code, Code::NotCollection);
+
+  let mut e = Edn::Nil;
+  e.merge(Edn::Nil).unwrap();
+  assert_eq!(e, Edn::Nil);
+  e.merge(edn::read_string("{:a 1}").unwrap()).unwrap();
+  e.merge(edn::read_string("{:a 2 :b 3}").unwrap()).unwrap();
+  assert_eq!(e, edn::read_string("{:a 2 :b 3}").unwrap());
+
+  let e =
+    Edn::Map(edn::Map::new()).into_coll(edn::read_string("[[:a 1] [:b 2]]").unwrap()).unwrap();
+  assert_eq!(e, edn::read_string("{:a 1 :b 2}").unwrap());
+  let e = edn::read_string("[]").unwrap().into_coll(edn::read_string("{:a 1}").unwrap()).unwrap();
+  assert_eq!(e, edn::read_string("[[:a 1]]").unwrap());
+  let e = edn::read_string("#{}").unwrap().into_coll(edn::read_string("(1 2 1)").unwrap()).unwrap();
+  assert_eq!(e, edn::read_string("#{1 2}").unwrap());
+  assert_eq!(Edn::Nil.into_coll(Edn::Int(1)).unwrap_err().code, Code::NotCollection);
+
+  // `Into::into` still works for types made from an `Edn`
+  struct Wrapper(bool);
+  impl From<Edn<'_>> for Wrapper {
+    fn from(edn: Edn<'_>) -> Self {
+      Self(edn.is_collection())
+    }
+  }
+  let wrapper: Wrapper = edn::read_string("[1]").unwrap().into();
+  assert!(wrapper.0);
+}
@@ -3,42 +3,27 @@
 mod test {
   use clojure_reader::parse::{self, Discard, Node, NodeKind, SourceReader, Span};
 
-  // Position
-  macro_rules! p {
-    ($line:expr, $column:expr, $ptr:expr) => {
-      clojure_reader::parse::Position { line: $line, column: $column, ptr: $ptr }
-    };
-    // helpful if single-line
-    ($column:expr, $ptr:expr) => {
-      clojure_reader::parse::Position { line: 1, column: $column, ptr: $ptr }
-    };
-    // helpful if single-line and no wide characters
-    ($column:expr) => {
-      clojure_reader::parse::Position { line: 1, column: $column, ptr: const { $column - 1 } }
-    };
-  }
-
   #[test]
   fn parse_empty() {
     assert_eq!(
       parse::parse(&mut SourceReader::new("")).unwrap(),
-      Node::no_discards(NodeKind::Nil, Span(p!(1), p!(1)))
+      Node::no_discards(NodeKind::Nil, Span(0, 0))
     );
     assert_eq!(
       parse::parse(&mut SourceReader::new("#_42")).unwrap(),
-      Node::no_discards(NodeKind::Nil, Span(p!(1), p!(5)))
+      Node::no_discards(NodeKind::Nil, Span(0, 4))
     );
     assert_eq!(
       parse::parse(&mut SourceReader::new("[]")).unwrap(),
-      Node::no_discards(NodeKind::Vector(vec![], vec![]), Span(p!(1), p!(3)))
+      Node::no_discards(NodeKind::Vector(vec![], vec![]), Span(0, 2))
     );
     assert_eq!(
       parse::parse(&mut SourceReader::new("()")).unwrap(),
-      Node::no_discards(NodeKind::List(vec![], vec![]), Span(p!(1), p!(3)))
+      Node::no_discards(NodeKind::List(vec![], vec![]), Span(0, 2))
     );
     assert_eq!(
       parse::parse(&mut SourceReader::new("{}")).unwrap(),
-      Node::no_discards(NodeKind::Map(vec![], vec![]), Span(p!(1), p!(3)))
+      Node::no_discards(NodeKind::Map(vec![], vec![]), Span(0, 2))
     );
   }
 
@@ -46,15 +31,12 @@ mod test {
   fn strings() {
     assert_eq!(
       parse::parse(&mut SourceReader::new("\"猫 are 猫\"")).unwrap(),
-      Node::no_discards(
-        NodeKind::Str("猫 are 猫"),
-        Span(p!(1, 1, 0), p!(1, 10, /* 猫 is 3 bytes wide */ 13))
-      )
+      Node::no_discards(NodeKind::Str("猫 are 猫"), Span(0, /* 猫 is 3 bytes wide */ 13))
     );
 
     assert_eq!(
       parse::parse(&mut SourceReader::new(r#""foo\rbar""#)).unwrap(),
-      Node::no_discards(NodeKind::Str("foo\\rbar"), Span(p!(1), p!(11)))
+      Node::no_discards(NodeKind::Str("foo\\rbar"), Span(0, 10))
     );
   }
 
@@ -62,22 +44,22 @@ mod test {
   fn unicode_literals() {
     assert_eq!(
       parse::parse(&mut SourceReader::new("猫gatoキャット")).unwrap(),
-      Node::no_discards(NodeKind::Symbol("猫gatoキャット"), Span(p!(1, 1, 0), p!(1, 10, 19)))
+      Node::no_discards(NodeKind::Symbol("猫gatoキャット"), Span(0, 19))
     );
 
     assert_eq!(
       parse::parse(&mut SourceReader::new(":猫gatoキャット")).unwrap(),
-      Node::no_discards(NodeKind::Key("猫gatoキャット"), Span(p!(1, 1, 0), p!(1, 11, 20)))
+      Node::no_discards(NodeKind::Key("猫gatoキャット"), Span(0, 20))
     );
 
     let mut reader = SourceReader::new("猫gatoキャット 42");
     assert_eq!(
       parse::parse(&mut reader).unwrap(),
-      Node::no_discards(NodeKind::Symbol("猫gatoキャット"), Span(p!(1, 1, 0), p!(1, 10, 19)))
+      Node::no_discards(NodeKind::Symbol("猫gatoキャット"), Span(0, 19))
     );
     assert_eq!(
       parse::parse(&mut reader).unwrap(),
-      Node::no_discards(NodeKind::Int(42), Span(p!(1, 11, 20), p!(1, 13, 22)))
+      Node::no_discards(NodeKind::Int(42), Span(20, 22))
     );
   }
 
@@ -99,62 +81,68 @@ mod test {
         NodeKind::Map(
           Vec::from([
             (
-              Node::no_discards(NodeKind::Key("cat"), Span(p!(2, 9, 10), p!(2, 13, 14))),
-              Node::no_discards(NodeKind::Str("猫"), Span(p!(2, 14, 15), p!(2, 17, 20))),
+              Node::no_discards(NodeKind::Key("cat"), Span(10, 14)),
+              Node::no_discards(NodeKind::Str("猫"), Span(15, 20)),
             ),
             (
-              Node::no_discards(NodeKind::Key("num"), Span(p!(3, 9, 45), p!(3, 13, 49))),
-              Node::no_discards(NodeKind::Int(-36930), Span(p!(3, 14, 50), p!(3, 21, 57))),
+              Node::no_discards(NodeKind::Key("num"), Span(45, 49)),
+              Node::no_discards(NodeKind::Int(-36930), Span(50, 57)),
             ),
             (
-              Node::no_discards(NodeKind::Key("r"), Span(p!(4, 9, 66), p!(4, 11, 68))),
-              Node::no_discards(NodeKind::Rational((42, 4242)), Span(p!(4, 12, 69), p!(4, 19, 76))),
+              Node::no_discards(NodeKind::Key("r"), Span(66, 68)),
+              Node::no_discards(NodeKind::Rational((42, 4242)), Span(69, 76)),
             ),
             (
               Node {
                 kind: NodeKind::Map(
                   Vec::from([(
-                    Node::no_discards(NodeKind::Key("foo"), Span(p!(6, 10, 108), p!(6, 14, 112))),
-                    Node::no_discards(NodeKind::Str("bar"), Span(p!(6, 15, 113), p!(6, 20, 118))),
+                    Node::no_discards(NodeKind::Key("foo"), Span(108, 112)),
+                    Node::no_discards(NodeKind::Str("bar"), Span(113, 118)),
                   )]),
                   vec![],
                 ),
-                span: Span(p!(6, 9, 107), p!(6, 21, 119)),
+                span: Span(107, 119),
                 leading_discards: vec![Discard(
                   Node {
                     kind: NodeKind::Int(9042),
-                    span: Span(p!(5, 18, 94), p!(5, 22, 98)),
+                    span: Span(94, 98),
                     leading_discards: vec![Discard(
-                      Node::no_discards(NodeKind::Key("num"), Span(p!(5, 13, 89), p!(5, 17, 93))),
-                      Span(p!(5, 11, 87), p!(5, 17, 93))
-                    )]
+                      Node::no_discards(NodeKind::Key("num"), Span(89, 93)),
+                      Span(87, 93),
+                      vec![]
+                    )],
+                    ..Default::default()
                   },
-                  Span(p!(5, 9, 85), p!(5, 22, 98))
-                )]
+                  Span(85, 98),
+                  vec![]
+                )],
+                ..Default::default()
               },
-              Node::no_discards(NodeKind::Str("foobar"), Span(p!(6, 22, 120), p!(6, 30, 128))),
+              Node::no_discards(NodeKind::Str("foobar"), Span(120, 128)),
             ),
             (
-              Node::no_discards(NodeKind::Key("lisp"), Span(p!(8, 9, 157), p!(8, 14, 162))),
+              Node::no_discards(NodeKind::Key("lisp"), Span(157, 162)),
               Node::no_discards(
                 NodeKind::List(
                   vec![Node {
                     kind: NodeKind::List(vec![], vec![]),
-                    span: Span(p!(8, 16, 164), p!(8, 18, 166)),
-                    leading_discards: vec![]
+                    span: Span(164, 166),
+                    leading_discards: vec![],
+                    ..Default::default()
                   }],
                   vec![]
                 ),
-                Span(p!(8, 15, 163), p!(8, 19, 167)),
+                Span(163, 167),
               ),
             ),
           ]),
           vec![Discard(
-            Node::no_discards(NodeKind::Str("a map"), Span(p!(9, 11, 178), p!(9, 18, 185))),
-            Span(p!(9, 9, 176), p!(9, 18, 185))
+            Node::no_discards(NodeKind::Str("a map"), Span(178, 185)),
+            Span(176, 185),
+            vec![]
           )]
         ),
-        Span(p!(1, 1, 0), p!(10, 6, 191)),
+        Span(0, 191),
       ),
     );
   }
@@ -203,12 +191,12 @@ mod test {
     assert_eq!(
       parse::parse(&mut SourceReader::new(e)).unwrap(),
       expected_result(SpanMap {
-        outer_map: Span(p!(1, 1, 0), p!(3, 6, 31)),
-        inner_vec: Span(p!(2, 2, 11), p!(2, 16, 25)),
-        inner_map: Span(p!(2, 3, 12), p!(2, 14, 23)),
-        key_somevec: Span(p!(1, 2, 1), p!(1, 10, 9)),
-        key_value: Span(p!(2, 4, 13), p!(2, 10, 19)),
-        int_42: Span(p!(2, 11, 20), p!(2, 13, 22))
+        outer_map: Span(0, 31),
+        inner_vec: Span(11, 25),
+        inner_map: Span(12, 23),
+        key_somevec: Span(1, 9),
+        key_value: Span(13, 19),
+        int_42: Span(20, 22)
       })
     );
 
@@ -219,12 +207,12 @@ mod test {
     assert_eq!(
       parse::parse(&mut SourceReader::new(e)).unwrap(),
       expected_result(SpanMap {
-        outer_map: Span(p!(1, 1, 0), p!(4, 6, 31)),
-        inner_vec: Span(p!(2, 2, 11), p!(3, 2, 25)),
-        inner_map: Span(p!(2, 3, 12), p!(2, 14, 23)),
-        key_somevec: Span(p!(1, 2, 1), p!(1, 10, 9)),
-        key_value: Span(p!(2, 4, 13), p!(2, 10, 19)),
-        int_42: Span(p!(2, 11, 20), p!(2, 13, 22))
+        outer_map: Span(0, 31),
+        inner_vec: Span(11, 25),
+        inner_map: Span(12, 23),
+        key_somevec: Span(1, 9),
+        key_value: Span(13, 19),
+        int_42: Span(20, 22)
       })
     );
 
@@ -235,12 +223,12 @@ mod test {
     assert_eq!(
       parse::parse(&mut SourceReader::new(e)).unwrap(),
       expected_result(SpanMap {
-        outer_map: Span(p!(1, 1, 0), p!(4, 6, 38)),
-        inner_vec: Span(p!(2, 2, 11), p!(3, 2, 32)),
-        inner_map: Span(p!(2, 4, 13), p!(2, 15, 24)),
-        key_somevec: Span(p!(1, 2, 1), p!(1, 10, 9)),
-        key_value: Span(p!(2, 5, 14), p!(2, 11, 20)),
-        int_42: Span(p!(2, 12, 21), p!(2, 14, 23))
+        outer_map: Span(0, 38),
+        inner_vec: Span(11, 32),
+        inner_map: Span(13, 24),
+        key_somevec: Span(1, 9),
+        key_value: Span(14, 20),
+        int_42: Span(21, 23)
       })
     );
 
@@ -248,12 +236,12 @@ mod test {
     assert_eq!(
       parse::parse(&mut SourceReader::new(e)).unwrap(),
       expected_result(SpanMap {
-        outer_map: Span(p!(1, 1, 0), p!(1, 25, 24)),
-        inner_vec: Span(p!(1, 11, 10), p!(1, 24, 23)),
-        inner_map: Span(p!(1, 12, 11), p!(1, 23, 22)),
-        key_somevec: Span(p!(1, 2, 1), p!(1, 10, 9)),
-        key_value: Span(p!(1, 13, 12), p!(1, 19, 18)),
-        int_42: Span(p!(1, 20, 19), p!(1, 22, 21))
+        outer_map: Span(0, 24),
+        inner_vec: Span(10, 23),
+        inner_map: Span(11, 22),
+        key_somevec: Span(1, 9),
+        key_value: Span(12, 18),
+        int_42: Span(19, 21)
       })
     );
   }
@@ -266,31 +254,28 @@ mod test {
       Node::no_discards(
         NodeKind::Set(
           Vec::from([
-            Node::no_discards(NodeKind::Key("cat"), Span(p!(3), p!(7))),
-            Node::no_discards(NodeKind::Int(1), Span(p!(8), p!(9))),
-            Node::no_discards(NodeKind::Bool(true), Span(p!(10), p!(14))),
+            Node::no_discards(NodeKind::Key("cat"), Span(2, 6)),
+            Node::no_discards(NodeKind::Int(1), Span(7, 8)),
+            Node::no_discards(NodeKind::Bool(true), Span(9, 13)),
             Node::no_discards(
               NodeKind::Set(
                 Vec::from([
-                  Node::no_discards(NodeKind::Key("cat"), Span(p!(17), p!(21))),
-                  Node::no_discards(NodeKind::Bool(true), Span(p!(22), p!(26)))
+                  Node::no_discards(NodeKind::Key("cat"), Span(16, 20)),
+                  Node::no_discards(NodeKind::Bool(true), Span(21, 25))
                 ]),
                 vec![]
               ),
-              Span(p!(15), p!(27))
+              Span(14, 26)
             ),
-            Node::no_discards(NodeKind::Int(2), Span(p!(28), p!(29))),
+            Node::no_discards(NodeKind::Int(2), Span(27, 28)),
             Node::no_discards(
-              NodeKind::Vector(
-                vec![Node::no_discards(NodeKind::Int(42), Span(p!(31), p!(33)))],
-                vec![]
-              ),
-              Span(p!(30), p!(34))
+              NodeKind::Vector(vec![Node::no_discards(NodeKind::Int(42), Span(30, 32))], vec![]),
+              Span(29, 33)
             ),
           ]),
           vec![]
         ),
-        Span(p!(1), p!(35))
+        Span(0, 34)
       )
     );
   }
@@ -299,17 +284,17 @@ mod test {
   fn numbers() {
     assert_eq!(
       parse::parse(&mut SourceReader::new("43/5143")).unwrap(),
-      Node::no_discards(NodeKind::Rational((43, 5143)), Span(p!(1), p!(8)))
+      Node::no_discards(NodeKind::Rational((43, 5143)), Span(0, 7))
     );
     assert_eq!(
       parse::parse(&mut SourceReader::new("-9223372036854775808")).unwrap(),
-      Node::no_discards(NodeKind::Int(i64::MIN), Span(p!(1), p!(21)))
+      Node::no_discards(NodeKind::Int(i64::MIN), Span(0, 20))
     );
     assert_eq!(
       parse::parse(&mut SourceReader::new("-1190128294822145183/3023870813131455535")).unwrap(),
       Node::no_discards(
         NodeKind::Rational((-1190128294822145183, 3023870813131455535)),
-        Span(p!(1), p!(41))
+        Span(0, 40)
       )
     );
     assert!(
@@ -321,20 +306,20 @@ mod test {
   fn parse_0x_ints() {
     assert_eq!(
       parse::parse(&mut SourceReader::new("0x2a")).unwrap(),
-      Node::no_discards(NodeKind::Int(42), Span(p!(1), p!(5)))
+      Node::no_discards(NodeKind::Int(42), Span(0, 4))
     );
     assert_eq!(
       parse::parse(&mut SourceReader::new("-0X2A")).unwrap(),
-      Node::no_discards(NodeKind::Int(-42), Span(p!(1), p!(6)))
+      Node::no_discards(NodeKind::Int(-42), Span(0, 5))
     );
     // leading plus character
     assert_eq!(
       parse::parse(&mut SourceReader::new("+42")).unwrap(),
-      Node::no_discards(NodeKind::Int(42), Span(p!(1), p!(4)))
+      Node::no_discards(NodeKind::Int(42), Span(0, 3))
     );
     assert_eq!(
       parse::parse(&mut SourceReader::new("+0x2a")).unwrap(),
-      Node::no_discards(NodeKind::Int(42), Span(p!(1), p!(6)))
+      Node::no_discards(NodeKind::Int(42), Span(0, 5))
     );
   }
 
@@ -348,13 +333,13 @@ mod test {
 
     assert_eq!(
       parse::parse(&mut reader).unwrap(),
-      Node::no_discards(NodeKind::BigInt(BigInt::from(42)), Span(p!(1), p!(4)))
+      Node::no_discards(NodeKind::BigInt(BigInt::from(42)), Span(0, 3))
     );
     assert_eq!(
       parse::parse(&mut reader).unwrap(),
       Node::no_discards(
         NodeKind::BigDec(BigDecimal::parse_bytes(b"1.25", 10).unwrap()),
-        Span(p!(5), p!(10))
+        Span(4, 9)
       )
     );
   }
@@ -363,23 +348,23 @@ mod test {
   fn parse_radix_ints() {
     assert_eq!(
       parse::parse(&mut SourceReader::new("16r2a")).unwrap(),
-      Node::no_discards(NodeKind::Int(42), Span(p!(1), p!(6)))
+      Node::no_discards(NodeKind::Int(42), Span(0, 5))
     );
     assert_eq!(
       parse::parse(&mut SourceReader::new("8r63")).unwrap(),
-      Node::no_discards(NodeKind::Int(51), Span(p!(1), p!(5)))
+      Node::no_discards(NodeKind::Int(51), Span(0, 4))
     );
     assert_eq!(
       parse::parse(&mut SourceReader::new("36rabcxyz")).unwrap(),
-      Node::no_discards(NodeKind::Int(623_741_435), Span(p!(1), p!(10)))
+      Node::no_discards(NodeKind::Int(623_741_435), Span(0, 9))
     );
     assert_eq!(
       parse::parse(&mut SourceReader::new("-16r2a")).unwrap(),
-      Node::no_discards(NodeKind::Int(-42), Span(p!(1), p!(7)))
+      Node::no_discards(NodeKind::Int(-42), Span(0, 6))
     );
     assert_eq!(
       parse::parse(&mut SourceReader::new("-32rFOObar")).unwrap(),
-      Node::no_discards(NodeKind::Int(-529_280_347), Span(p!(1), p!(11)))
+      Node::no_discards(NodeKind::Int(-529_280_347), Span(0, 10))
     );
   }
 
@@ -390,18 +375,18 @@ mod test {
       Node::no_discards(
         NodeKind::List(
           vec![
-            Node::no_discards(NodeKind::Symbol("'"), Span(p!(2), p!(3))),
+            Node::no_discards(NodeKind::Symbol("'"), Span(1, 2)),
             Node::no_discards(
               NodeKind::List(
-                vec![Node::no_discards(NodeKind::Symbol("symbol"), Span(p!(4), p!(10)))],
+                vec![Node::no_discards(NodeKind::Symbol("symbol"), Span(3, 9))],
                 vec![]
               ),
-              Span(p!(3), p!(11))
+              Span(2, 10)
             )
           ],
           vec![]
         ),
-        Span(p!(1), p!(12))
+        Span(0, 11)
       ),
     );
 
@@ -410,24 +395,24 @@ mod test {
       Node::no_discards(
         NodeKind::List(
           vec![
-            Node::no_discards(NodeKind::Symbol("apply"), Span(p!(2), p!(7))),
-            Node::no_discards(NodeKind::Symbol("+"), Span(p!(8), p!(9))),
-            Node::no_discards(NodeKind::Symbol("'"), Span(p!(10), p!(11))),
+            Node::no_discards(NodeKind::Symbol("apply"), Span(1, 6)),
+            Node::no_discards(NodeKind::Symbol("+"), Span(7, 8)),
+            Node::no_discards(NodeKind::Symbol("'"), Span(9, 10)),
             Node::no_discards(
               NodeKind::List(
                 vec![
-                  Node::no_discards(NodeKind::Int(1), Span(p!(12), p!(13))),
-                  Node::no_discards(NodeKind::Int(2), Span(p!(14), p!(15))),
-                  Node::no_discards(NodeKind::Int(3), Span(p!(16), p!(17)))
+                  Node::no_discards(NodeKind::Int(1), Span(11, 12)),
+                  Node::no_discards(NodeKind::Int(2), Span(13, 14)),
+                  Node::no_discards(NodeKind::Int(3), Span(15, 16))
                 ],
                 vec![]
               ),
-              Span(p!(11), p!(18))
+              Span(10, 17)
             ),
           ],
           vec![]
         ),
-        Span(p!(1), p!(19))
+        Span(0, 18)
       )
     );
 
@@ -436,21 +421,18 @@ mod test {
       Node::no_discards(
         NodeKind::List(
           vec![
-            Node::no_discards(NodeKind::Symbol("'"), Span(p!(2), p!(3))),
+            Node::no_discards(NodeKind::Symbol("'"), Span(1, 2)),
             Node::no_discards(
               NodeKind::List(
-                vec![Node::no_discards(
-                  NodeKind::Symbol("''symbol'foo''bar''"),
-                  Span(p!(4), p!(23))
-                ),],
+                vec![Node::no_discards(NodeKind::Symbol("''symbol'foo''bar''"), Span(3, 22)),],
                 vec![]
               ),
-              Span(p!(3), p!(24))
+              Span(2, 23)
             )
           ],
           vec![]
         ),
-        Span(p!(1), p!(25))
+        Span(0, 24)
       )
     );
   }
@@ -459,15 +441,15 @@ mod test {
   fn numeric_like_symbols_keywords() {
     assert_eq!(
       parse::parse(&mut SourceReader::new("-foobar")).unwrap(),
-      Node::no_discards(NodeKind::Symbol("-foobar"), Span(p!(1), p!(8)))
+      Node::no_discards(NodeKind::Symbol("-foobar"), Span(0, 7))
     );
     assert_eq!(
       parse::parse(&mut SourceReader::new("-:thi#n=g")).unwrap(),
-      Node::no_discards(NodeKind::Symbol("-:thi#n=g"), Span(p!(1), p!(10)))
+      Node::no_discards(NodeKind::Symbol("-:thi#n=g"), Span(0, 9))
     );
     assert_eq!(
       parse::parse(&mut SourceReader::new(":thi#n=g")).unwrap(),
-      Node::no_discards(NodeKind::Key("thi#n=g"), Span(p!(1), p!(9)))
+      Node::no_discards(NodeKind::Key("thi#n=g"), Span(0, 8))
     );
 
     assert_eq!(
@@ -475,14 +457,14 @@ mod test {
       Node::no_discards(
         NodeKind::List(
           vec![
-            Node::no_discards(NodeKind::Symbol("+foobar"), Span(p!(2), p!(9))),
-            Node::no_discards(NodeKind::Symbol("+foo+bar+"), Span(p!(10), p!(19))),
-            Node::no_discards(NodeKind::Symbol("+'-"), Span(p!(20), p!(23))),
-            Node::no_discards(NodeKind::Symbol("'-+"), Span(p!(24), p!(27))),
+            Node::no_discards(NodeKind::Symbol("+foobar"), Span(1, 8)),
+            Node::no_discards(NodeKind::Symbol("+foo+bar+"), Span(9, 18)),
+            Node::no_discards(NodeKind::Symbol("+'-"), Span(19, 22)),
+            Node::no_discards(NodeKind::Symbol("'-+"), Span(23, 26)),
           ],
           vec![]
         ),
-        Span(p!(1), p!(28))
+        Span(0, 27)
       )
     );
 
@@ -494,13 +476,13 @@ mod test {
     let mut reader = SourceReader::new("\\c[lolthisisvalidedn");
     assert_eq!(
       parse::parse(&mut reader).unwrap(),
-      Node::no_discards(NodeKind::Char('c'), Span(p!(1), p!(3)))
+      Node::no_discards(NodeKind::Char('c'), Span(0, 2))
     );
     assert!(parse::parse(&mut reader).is_err());
 
     assert_eq!(
       parse::parse(&mut SourceReader::new("\\猫")).unwrap(),
-      Node::no_discards(NodeKind::Char('猫'), Span(p!(1, 1, 0), p!(1, 3, 4)))
+      Node::no_discards(NodeKind::Char('猫'), Span(0, 4))
     );
 
     let edn = "[\\space \\@ \\` \\tab \\return \\newline \\# \\% \\' \\g \\( \\* \\j \\+ \\, \\l \\- \\. \\/ \\0 \\2 \\r \\: \\; \\< \\\\ \\] \\} \\~ \\? \\_]";
@@ -510,41 +492,41 @@ mod test {
       Node::no_discards(
         NodeKind::Vector(
           vec![
-            Node::no_discards(NodeKind::Char(' '), Span(p!(2), p!(8))),
-            Node::no_discards(NodeKind::Char('@'), Span(p!(9), p!(11))),
-            Node::no_discards(NodeKind::Char('`'), Span(p!(12), p!(14))),
-            Node::no_discards(NodeKind::Char('\t'), Span(p!(15), p!(19))),
-            Node::no_discards(NodeKind::Char('\r'), Span(p!(20), p!(27))),
-            Node::no_discards(NodeKind::Char('\n'), Span(p!(28), p!(36))),
-            Node::no_discards(NodeKind::Char('#'), Span(p!(37), p!(39))),
-            Node::no_discards(NodeKind::Char('%'), Span(p!(40), p!(42))),
-            Node::no_discards(NodeKind::Char('\''), Span(p!(43), p!(45))),
-            Node::no_discards(NodeKind::Char('g'), Span(p!(46), p!(48))),
-            Node::no_discards(NodeKind::Char('('), Span(p!(49), p!(51))),
-            Node::no_discards(NodeKind::Char('*'), Span(p!(52), p!(54))),
-            Node::no_discards(NodeKind::Char('j'), Span(p!(55), p!(57))),
-            Node::no_discards(NodeKind::Char('+'), Span(p!(58), p!(60))),
-            Node::no_discards(NodeKind::Char(','), Span(p!(61), p!(63))),
-            Node::no_discards(NodeKind::Char('l'), Span(p!(64), p!(66))),
-            Node::no_discards(NodeKind::Char('-'), Span(p!(67), p!(69))),
-            Node::no_discards(NodeKind::Char('.'), Span(p!(70), p!(72))),
-            Node::no_discards(NodeKind::Char('/'), Span(p!(73), p!(75))),
-            Node::no_discards(NodeKind::Char('0'), Span(p!(76), p!(78))),
-            Node::no_discards(NodeKind::Char('2'), Span(p!(79), p!(81))),
-            Node::no_discards(NodeKind::Char('r'), Span(p!(82), p!(84))),
-            Node::no_discards(NodeKind::Char(':'), Span(p!(85), p!(87))),
-            Node::no_discards(NodeKind::Char(';'), Span(p!(88), p!(90))),
-            Node::no_discards(NodeKind::Char('<'), Span(p!(91), p!(93))),
-            Node::no_discards(NodeKind::Char('\\'), Span(p!(94), p!(96))),
-            Node::no_discards(NodeKind::Char(']'), Span(p!(97), p!(99))),
-            Node::no_discards(NodeKind::Char('}'), Span(p!(100), p!(102))),
-            Node::no_discards(NodeKind::Char('~'), Span(p!(103), p!(105))),
-            Node::no_discards(NodeKind::Char('?'), Span(p!(106), p!(108))),
-            Node::no_discards(NodeKind::Char('_'), Span(p!(109), p!(111))),
+            Node::no_discards(NodeKind::Char(' '), Span(1, 7)),
+            Node::no_discards(NodeKind::Char('@'), Span(8, 10)),
+            Node::no_discards(NodeKind::Char('`'), Span(11, 13)),
+            Node::no_discards(NodeKind::Char('\t'), Span(14, 18)),
+            Node::no_discards(NodeKind::Char('\r'), Span(19, 26)),
+            Node::no_discards(NodeKind::Char('\n'), Span(27, 35)),
+            Node::no_discards(NodeKind::Char('#'), Span(36, 38)),
+            Node::no_discards(NodeKind::Char('%'), Span(39, 41)),
+            Node::no_discards(NodeKind::Char('\''), Span(42, 44)),
+            Node::no_discards(NodeKind::Char('g'), Span(45, 47)),
+            Node::no_discards(NodeKind::Char('('), Span(48, 50)),
+            Node::no_discards(NodeKind::Char('*'), Span(51, 53)),
+            Node::no_discards(NodeKind::Char('j'), Span(54, 56)),
+            Node::no_discards(NodeKind::Char('+'), Span(57, 59)),
+            Node::no_discards(NodeKind::Char(','), Span(60, 62)),
+            Node::no_discards(NodeKind::Char('l'), Span(63, 65)),
+            Node::no_discards(NodeKind::Char('-'), Span(66, 68)),
+            Node::no_discards(NodeKind::Char('.'), Span(69, 71)),
+            Node::no_discards(NodeKind::Char('/'), Span(72, 74)),
+            Node::no_discards(NodeKind::Char('0'), Span(75, 77)),
+            Node::no_discards(NodeKind::Char('2'), Span(78, 80)),
+            Node::no_discards(NodeKind::Char('r'), Span(81, 83)),
+            Node::no_discards(NodeKind::Char(':'), Span(84, 86)),
+            Node::no_discards(NodeKind::Char(';'), Span(87, 89)),
+            Node::no_discards(NodeKind::Char('<'), Span(90, 92)),
+            Node::no_discards(NodeKind::Char('\\'), Span(93, 95)),
+            Node::no_discards(NodeKind::Char(']'), Span(96, 98)),
+            Node::no_discards(NodeKind::Char('}'), Span(99, 101)),
+            Node::no_discards(NodeKind::Char('~'), Span(102, 104)),
+            Node::no_discards(NodeKind::Char('?'), Span(105, 107)),
+            Node::no_discards(NodeKind::Char('_'), Span(108, 110)),
           ],
           vec![]
         ),
-        Span(p!(1), p!(112))
+        Span(0, 111)
       )
     );
   }
@@ -559,13 +541,13 @@ mod test {
       Node::no_discards(
         NodeKind::List(
           vec![
-            Node::no_discards(NodeKind::Symbol("def"), Span(p!(2), p!(5))),
-            Node::no_discards(NodeKind::Symbol("foo"), Span(p!(6), p!(9))),
-            Node::no_discards(NodeKind::Int(42), Span(p!(10), p!(12)))
+            Node::no_discards(NodeKind::Symbol("def"), Span(1, 4)),
+            Node::no_discards(NodeKind::Symbol("foo"), Span(5, 8)),
+            Node::no_discards(NodeKind::Int(42), Span(9, 11))
           ],
           vec![]
         ),
-        Span(p!(1), p!(13))
+        Span(0, 12)
       )
     );
 
@@ -575,23 +557,23 @@ mod test {
       Node::no_discards(
         NodeKind::List(
           vec![
-            Node::no_discards(NodeKind::Symbol("sum"), Span(p!(14), p!(17))),
-            Node::no_discards(NodeKind::Symbol("'"), Span(p!(18), p!(19))),
+            Node::no_discards(NodeKind::Symbol("sum"), Span(13, 16)),
+            Node::no_discards(NodeKind::Symbol("'"), Span(17, 18)),
             Node::no_discards(
               NodeKind::List(
                 vec![
-                  Node::no_discards(NodeKind::Int(1), Span(p!(20), p!(21))),
-                  Node::no_discards(NodeKind::Int(2), Span(p!(22), p!(23))),
-                  Node::no_discards(NodeKind::Int(3), Span(p!(24), p!(25)))
+                  Node::no_discards(NodeKind::Int(1), Span(19, 20)),
+                  Node::no_discards(NodeKind::Int(2), Span(21, 22)),
+                  Node::no_discards(NodeKind::Int(3), Span(23, 24))
                 ],
                 vec![]
               ),
-              Span(p!(19), p!(26))
+              Span(18, 25)
             )
           ],
           vec![]
         ),
-        Span(p!(13), p!(27))
+        Span(12, 26)
       )
     );
 
@@ -600,36 +582,38 @@ mod test {
       n,
       Node {
         kind: NodeKind::Int(42),
-        span: Span(p!(50), p!(52)),
+        span: Span(49, 51),
         leading_discards: vec![Discard(
           Node::no_discards(
             NodeKind::List(
               vec![
-                Node::no_discards(NodeKind::Symbol("foo"), Span(p!(31), p!(34))),
-                Node::no_discards(NodeKind::Symbol("the"), Span(p!(35), p!(38))),
-                Node::no_discards(NodeKind::Symbol("bar"), Span(p!(39), p!(42))),
+                Node::no_discards(NodeKind::Symbol("foo"), Span(30, 33)),
+                Node::no_discards(NodeKind::Symbol("the"), Span(34, 37)),
+                Node::no_discards(NodeKind::Symbol("bar"), Span(38, 41)),
                 Node::no_discards(
                   NodeKind::List(
-                    vec![Node::no_discards(NodeKind::Symbol("cat"), Span(p!(44), p!(47)))],
+                    vec![Node::no_discards(NodeKind::Symbol("cat"), Span(43, 46))],
                     vec![]
                   ),
-                  Span(p!(43), p!(48))
+                  Span(42, 47)
                 ),
               ],
               vec![]
             ),
-            Span(p!(30), p!(49))
+            Span(29, 48)
           ),
-          Span(p!(28), p!(49))
-        )]
+          Span(27, 48),
+          vec![]
+        )],
+        ..Default::default()
       }
     );
 
     let n = parse::parse(&mut reader).unwrap();
-    assert_eq!(n, Node::no_discards(NodeKind::Nil, Span(p!(53), p!(56))));
+    assert_eq!(n, Node::no_discards(NodeKind::Nil, Span(52, 55)));
 
     let n = parse::parse(&mut reader).unwrap();
-    assert_eq!(n, Node::no_discards(NodeKind::Int(2), Span(p!(57), p!(58))));
+    assert_eq!(n, Node::no_discards(NodeKind::Int(2), Span(56, 57)));
 
     // EOF
     assert!(
@@ -644,13 +628,10 @@ mod test {
       Node::no_discards(
         NodeKind::Tagged(
           "inst",
-          Span(p!(2), p!(6)),
-          Box::new(Node::no_discards(
-            NodeKind::Str("1985-04-12T23:20:50.52Z"),
-            Span(p!(7), p!(32))
-          ))
+          Span(1, 5),
+          Box::new(Node::no_discards(NodeKind::Str("1985-04-12T23:20:50.52Z"), Span(6, 31)))
         ),
-        Span(p!(1), p!(32))
+        Span(0, 31)
       ),
     );
     assert_eq!(
@@ -658,10 +639,10 @@ mod test {
       Node::no_discards(
         NodeKind::Tagged(
           "Unit",
-          Span(p!(2), p!(6)),
-          Box::new(Node::no_discards(NodeKind::Nil, Span(p!(7), p!(10))))
+          Span(1, 5),
+          Box::new(Node::no_discards(NodeKind::Nil, Span(6, 9)))
         ),
-        Span(p!(1), p!(10))
+        Span(0, 9)
       )
     );
 
@@ -670,17 +651,17 @@ mod test {
       Node::no_discards(
         NodeKind::Tagged(
           "pow2",
-          Span(p!(2), p!(6)),
+          Span(1, 5),
           Box::new(Node::no_discards(
             NodeKind::Tagged(
               "pow3",
-              Span(p!(8), p!(12)),
-              Box::new(Node::no_discards(NodeKind::Int(2), Span(p!(13), p!(14))))
+              Span(7, 11),
+              Box::new(Node::no_discards(NodeKind::Int(2), Span(12, 13)))
             ),
-            Span(p!(7), p!(14))
+            Span(6, 13)
           ))
         ),
-        Span(p!(1), p!(14))
+        Span(0, 13)
       )
     );
 
@@ -689,10 +670,10 @@ mod test {
       Node::no_discards(
         NodeKind::Tagged(
           "4",
-          Span(p!(2), p!(3)),
-          Box::new(Node::no_discards(NodeKind::Int(2), Span(p!(4), p!(5))))
+          Span(1, 2),
+          Box::new(Node::no_discards(NodeKind::Int(2), Span(3, 4)))
         ),
-        Span(p!(1), p!(5))
+        Span(0, 4)
       )
     );
 
@@ -701,10 +682,10 @@ mod test {
       Node::no_discards(
         NodeKind::Tagged(
           "foo/bar/baz",
-          Span(p!(2), p!(13)),
-          Box::new(Node::no_discards(NodeKind::Nil, Span(p!(14), p!(17))))
+          Span(1, 12),
+          Box::new(Node::no_discards(NodeKind::Nil, Span(13, 16)))
         ),
-        Span(p!(1), p!(17))
+        Span(0, 16)
       )
     );
 
@@ -713,43 +694,40 @@ mod test {
       Node::no_discards(
         NodeKind::Tagged(
           "foo",
-          Span(p!(2), p!(5)),
+          Span(1, 4),
           Box::new(Node::no_discards(
             NodeKind::Tagged(
               "bar",
-              Span(p!(7), p!(10)),
+              Span(6, 9),
               Box::new(Node::no_discards(
                 NodeKind::Tagged(
                   "ニャンキャット",
-                  Span(p!(12), p!(19, 32)),
+                  Span(11, 32),
                   Box::new(Node::no_discards(
                     NodeKind::Map(
                       Vec::from([(
-                        Node::no_discards(NodeKind::Key("baz"), Span(p!(21, 34), p!(25, 38))),
+                        Node::no_discards(NodeKind::Key("baz"), Span(34, 38)),
                         Node::no_discards(
                           NodeKind::Tagged(
                             "42",
-                            Span(p!(27, 40), p!(29, 42)),
-                            Box::new(Node::no_discards(
-                              NodeKind::Str("wut"),
-                              Span(p!(30, 43), p!(35, 48))
-                            ))
+                            Span(40, 42),
+                            Box::new(Node::no_discards(NodeKind::Str("wut"), Span(43, 48)))
                           ),
-                          Span(p!(26, 39), p!(35, 48))
+                          Span(39, 48)
                         )
                       )]),
                       vec![]
                     ),
-                    Span(p!(20, 33), p!(36, 49))
+                    Span(33, 49)
                   ))
                 ),
-                Span(p!(11, 10), p!(36, 49))
+                Span(10, 49)
               ))
             ),
-            Span(p!(6, 5), p!(36, 49))
+            Span(5, 49)
           ))
         ),
-        Span(p!(1, 0), p!(36, 49))
+        Span(0, 49)
       )
     );
   }
@@ -765,37 +743,41 @@ mod test {
       parse::parse(&mut SourceReader::new(e)).unwrap(),
       Node {
         leading_discards: vec![Discard(
-          Node::no_discards(NodeKind::Str("m"), Span(p!(3), p!(6))),
-          Span(p!(1), p!(6))
+          Node::no_discards(NodeKind::Str("m"), Span(2, 5)),
+          Span(0, 5),
+          vec![]
         )],
         kind: NodeKind::Map(
           vec![
             (
               Node {
                 leading_discards: vec![Discard(
-                  Node::no_discards(NodeKind::Symbol("key1"), Span(p!(2, 11, 18), p!(2, 15, 22))),
-                  Span(p!(2, 9, 16), p!(2, 15, 22))
+                  Node::no_discards(NodeKind::Symbol("key1"), Span(18, 22)),
+                  Span(16, 22),
+                  vec![]
                 )],
                 kind: NodeKind::Key("cat"),
-                span: Span(p!(2, 16, 23), p!(2, 20, 27))
+                span: Span(23, 27),
+                ..Default::default()
               },
               Node {
                 leading_discards: vec![Discard(
                   Node {
                     leading_discards: vec![Discard(
-                      Node::no_discards(
-                        NodeKind::Symbol("discard-of-val1"),
-                        Span(p!(2, 25, 32), p!(2, 40, 47))
-                      ),
-                      Span(p!(2, 23, 30), p!(2, 40, 47))
+                      Node::no_discards(NodeKind::Symbol("discard-of-val1"), Span(32, 47)),
+                      Span(30, 47),
+                      vec![]
                     )],
                     kind: NodeKind::Symbol("val1"),
-                    span: Span(p!(2, 41, 48), p!(2, 45, 52))
+                    span: Span(48, 52),
+                    ..Default::default()
                   },
-                  Span(p!(2, 21, 28), p!(2, 45, 52))
+                  Span(28, 52),
+                  vec![]
                 )],
                 kind: NodeKind::Str("猫"),
-                span: Span(p!(2, 46, 53), p!(2, 49, 58))
+                span: Span(53, 58),
+                ..Default::default()
               }
             ),
             (
@@ -805,41 +787,44 @@ mod test {
                     leading_discards: vec![Discard(
                       Node {
                         leading_discards: vec![Discard(
-                          Node::no_discards(
-                            NodeKind::Symbol("foo"),
-                            Span(p!(3, 15, 73), p!(3, 18, 76))
-                          ),
-                          Span(p!(3, 13, 71), p!(3, 18, 76))
+                          Node::no_discards(NodeKind::Symbol("foo"), Span(73, 76)),
+                          Span(71, 76),
+                          vec![]
                         )],
                         kind: NodeKind::Symbol("bar"),
-                        span: Span(p!(3, 19, 77), p!(3, 22, 80))
+                        span: Span(77, 80),
+                        ..Default::default()
                       },
-                      Span(p!(3, 11, 69), p!(3, 22, 80))
+                      Span(69, 80),
+                      vec![]
                     )],
                     kind: NodeKind::Symbol("baz"),
-                    span: Span(p!(3, 23, 81), p!(3, 26, 84))
+                    span: Span(81, 84),
+                    ..Default::default()
                   },
-                  Span(p!(3, 9, 67), p!(3, 26, 84))
+                  Span(67, 84),
+                  vec![]
                 )],
                 kind: NodeKind::Key("r"),
-                span: Span(p!(3, 27, 85), p!(3, 29, 87))
+                span: Span(85, 87),
+                ..Default::default()
               },
               Node {
                 leading_discards: vec![
                   Discard(
-                    Node::no_discards(NodeKind::Symbol("val3"), Span(p!(3, 32, 90), p!(3, 36, 94))),
-                    Span(p!(3, 30, 88), p!(3, 36, 94))
+                    Node::no_discards(NodeKind::Symbol("val3"), Span(90, 94)),
+                    Span(88, 94),
+                    vec![]
                   ),
                   Discard(
-                    Node::no_discards(
-                      NodeKind::Symbol("val3"),
-                      Span(p!(3, 39, 97), p!(3, 43, 101))
-                    ),
-                    Span(p!(3, 37, 95), p!(3, 43, 101))
+                    Node::no_discards(NodeKind::Symbol("val3"), Span(97, 101)),
+                    Span(95, 101),
+                    vec![]
                   )
                 ],
                 kind: NodeKind::Rational((42, 4242)),
-                span: Span(p!(3, 44, 102), p!(3, 51, 109))
+                span: Span(102, 109),
+                ..Default::default()
               }
             )
           ],
@@ -847,34 +832,35 @@ mod test {
             Discard(
               Node {
                 leading_discards: vec![Discard(
-                  Node::no_discards(
-                    NodeKind::Symbol("trailing"),
-                    Span(p!(3, 56, 114), p!(3, 64, 122))
-                  ),
-                  Span(p!(3, 54, 112), p!(3, 64, 122))
+                  Node::no_discards(NodeKind::Symbol("trailing"), Span(114, 122)),
+                  Span(112, 122),
+                  vec![]
                 )],
                 kind: NodeKind::Symbol("discard"),
-                span: Span(p!(3, 65, 123), p!(3, 72, 130))
+                span: Span(123, 130),
+                ..Default::default()
               },
-              Span(p!(3, 52, 110), p!(3, 72, 130))
+              Span(110, 130),
+              vec![]
             ),
             Discard(
               Node {
                 leading_discards: vec![Discard(
-                  Node::no_discards(
-                    NodeKind::Symbol("trailing"),
-                    Span(p!(3, 77, 135), p!(3, 85, 143))
-                  ),
-                  Span(p!(3, 75, 133), p!(3, 85, 143))
+                  Node::no_discards(NodeKind::Symbol("trailing"), Span(135, 143)),
+                  Span(133, 143),
+                  vec![]
                 )],
                 kind: NodeKind::Symbol("discard"),
-                span: Span(p!(3, 86, 144), p!(3, 93, 151))
+                span: Span(144, 151),
+                ..Default::default()
               },
-              Span(p!(3, 73, 131), p!(3, 93, 151))
+              Span(131, 151),
+              vec![]
             ),
           ]
         ),
-        span: Span(p!(7), p!(4, 6, 157)),
+        span: Span(6, 157),
+        ..Default::default()
       }
     );
 
@@ -884,56 +870,66 @@ mod test {
       parse::parse(&mut SourceReader::new(e)).unwrap(),
       Node {
         leading_discards: vec![Discard(
-          Node::no_discards(NodeKind::Str("v"), Span(p!(4), p!(7))),
-          Span(p!(1), p!(7))
+          Node::no_discards(NodeKind::Str("v"), Span(3, 6)),
+          Span(0, 6),
+          vec![]
         )],
-        span: Span(p!(8), p!(72)),
+        span: Span(7, 71),
         kind: NodeKind::Vector(
           vec![
             Node {
               leading_discards: vec![Discard(
                 Node {
                   leading_discards: vec![Discard(
-                    Node::no_discards(NodeKind::Key("key"), Span(p!(15), p!(19))),
-                    Span(p!(12), p!(19))
+                    Node::no_discards(NodeKind::Key("key"), Span(14, 18)),
+                    Span(11, 18),
+                    vec![]
                   )],
                   kind: NodeKind::Int(0),
-                  span: Span(p!(20), p!(21))
+                  span: Span(19, 20),
+                  ..Default::default()
                 },
-                Span(p!(9), p!(21))
+                Span(8, 20),
+                vec![]
               )],
               kind: NodeKind::Str("foo"),
-              span: Span(p!(22), p!(27))
+              span: Span(21, 26),
+              ..Default::default()
             },
             Node {
               leading_discards: vec![Discard(
                 Node::no_discards(
                   NodeKind::Vector(
                     vec![
-                      Node::no_discards(NodeKind::Key("key"), Span(p!(33), p!(37))),
-                      Node::no_discards(NodeKind::Int(1), Span(p!(38), p!(39)))
+                      Node::no_discards(NodeKind::Key("key"), Span(32, 36)),
+                      Node::no_discards(NodeKind::Int(1), Span(37, 38))
                     ],
                     vec![]
                   ),
-                  Span(p!(32), p!(40))
+                  Span(31, 39)
                 ),
-                Span(p!(30), p!(40))
+                Span(29, 39),
+                vec![]
               )],
               kind: NodeKind::Symbol("bar"),
-              span: Span(p!(41), p!(44))
+              span: Span(40, 43),
+              ..Default::default()
             }
           ],
           vec![
             Discard(
-              Node::no_discards(NodeKind::Symbol("trailing"), Span(p!(48), p!(56))),
-              Span(p!(45), p!(56))
+              Node::no_discards(NodeKind::Symbol("trailing"), Span(47, 55)),
+              Span(44, 55),
+              vec![]
             ),
             Discard(
-              Node::no_discards(NodeKind::Symbol("discards"), Span(p!(62), p!(70))),
-              Span(p!(58), p!(70))
+              Node::no_discards(NodeKind::Symbol("discards"), Span(61, 69)),
+              Span(57, 69),
+              vec![]
             )
           ]
         ),
+        ..Default::default()
       }
     );
 
@@ -943,45 +939,52 @@ mod test {
       parse::parse(&mut SourceReader::new(e)).unwrap(),
       Node {
         leading_discards: vec![Discard(
-          Node::no_discards(NodeKind::Str("l"), Span(p!(4), p!(7))),
-          Span(p!(1), p!(7))
+          Node::no_discards(NodeKind::Str("l"), Span(3, 6)),
+          Span(0, 6),
+          vec![]
         )],
-        span: Span(p!(8), p!(69)),
+        span: Span(7, 68),
         kind: NodeKind::List(
           vec![
             Node {
               leading_discards: vec![Discard(
-                Node::no_discards(NodeKind::Key("fn"), Span(p!(11), p!(14))),
-                Span(p!(9), p!(14))
+                Node::no_discards(NodeKind::Key("fn"), Span(10, 13)),
+                Span(8, 13),
+                vec![]
               )],
               kind: NodeKind::Symbol("println"),
-              span: Span(p!(15), p!(22))
+              span: Span(14, 21),
+              ..Default::default()
             },
             Node {
               leading_discards: vec![Discard(
-                Node::no_discards(NodeKind::Key("arg"), Span(p!(25), p!(29))),
-                Span(p!(23), p!(29))
+                Node::no_discards(NodeKind::Key("arg"), Span(24, 28)),
+                Span(22, 28),
+                vec![]
               )],
               kind: NodeKind::Str("Hello, World"),
-              span: Span(p!(30), p!(44))
+              span: Span(29, 43),
+              ..Default::default()
             }
           ],
           vec![Discard(
             Node::no_discards(
               NodeKind::List(
                 vec![
-                  Node::no_discards(NodeKind::Key("call"), Span(p!(48), p!(53))),
-                  Node::no_discards(NodeKind::Symbol("fn"), Span(p!(54), p!(56))),
-                  Node::no_discards(NodeKind::Key("with"), Span(p!(57), p!(62))),
-                  Node::no_discards(NodeKind::Symbol("arg"), Span(p!(63), p!(66))),
+                  Node::no_discards(NodeKind::Key("call"), Span(47, 52)),
+                  Node::no_discards(NodeKind::Symbol("fn"), Span(53, 55)),
+                  Node::no_discards(NodeKind::Key("with"), Span(56, 61)),
+                  Node::no_discards(NodeKind::Symbol("arg"), Span(62, 65)),
                 ],
                 vec![]
               ),
-              Span(p!(47), p!(67))
+              Span(46, 66)
             ),
-            Span(p!(45), p!(67))
+            Span(44, 66),
+            vec![]
           )]
         ),
+        ..Default::default()
       }
     );
 
@@ -993,34 +996,41 @@ mod test {
         parse::parse(&mut SourceReader::new(e)).unwrap(),
         Node {
           leading_discards: vec![Discard(
-            Node::no_discards(NodeKind::Str("s"), Span(p!(4), p!(7))),
-            Span(p!(1), p!(7))
+            Node::no_discards(NodeKind::Str("s"), Span(3, 6)),
+            Span(0, 6),
+            vec![]
           )],
-          span: Span(p!(8), p!(35)),
+          span: Span(7, 34),
           kind: NodeKind::Set(
             vec![
-              Node::no_discards(NodeKind::Int(1), Span(p!(11), p!(12))),
+              Node::no_discards(NodeKind::Int(1), Span(10, 11)),
               Node {
                 leading_discards: vec![Discard(
-                  Node::no_discards(NodeKind::Double((2.2).into()), Span(p!(15), p!(18))),
-                  Span(p!(13), p!(18))
+                  Node::no_discards(NodeKind::Double((2.2).into()), Span(14, 17)),
+                  Span(12, 17),
+                  vec![]
                 )],
                 kind: NodeKind::Int(3),
-                span: Span(p!(19), p!(20))
+                span: Span(18, 19),
+                ..Default::default()
               },
             ],
             vec![Discard(
               Node {
                 leading_discards: vec![Discard(
-                  Node::no_discards(NodeKind::Symbol("four"), Span(p!(25), p!(29))),
-                  Span(p!(23), p!(29))
+                  Node::no_discards(NodeKind::Symbol("four"), Span(24, 28)),
+                  Span(22, 28),
+                  vec![]
                 )],
                 kind: NodeKind::Rational((4, 1)),
-                span: Span(p!(30), p!(33)),
+                span: Span(29, 32),
+                ..Default::default()
               },
-              Span(p!(21), p!(33))
+              Span(20, 32),
+              vec![]
             )]
           ),
+          ..Default::default()
         }
       );
     }
@@ -1032,25 +1042,26 @@ mod test {
       parse::parse(&mut parse::SourceReader::new(e)).unwrap(),
       Node {
         leading_discards: vec![Discard(
-          Node::no_discards(NodeKind::Str("t"), Span(p!(4), p!(7))),
-          Span(p!(1), p!(7))
+          Node::no_discards(NodeKind::Str("t"), Span(3, 6)),
+          Span(0, 6),
+          vec![]
         )],
         kind: NodeKind::Tagged(
           "uuid",
-          Span(p!(9), p!(13)),
+          Span(8, 12),
           Box::new(Node {
             leading_discards: vec![Discard(
-              Node::no_discards(
-                NodeKind::Str("in base64: +B1Prn3sEdCnZQAAAKDJHg"),
-                Span(p!(16), p!(51))
-              ),
-              Span(p!(14), p!(51))
+              Node::no_discards(NodeKind::Str("in base64: +B1Prn3sEdCnZQAAAKDJHg"), Span(15, 50)),
+              Span(13, 50),
+              vec![]
             )],
             kind: NodeKind::Str("f81d4fae-7dec-11d0-a765-00a0c91e6bf6"),
-            span: Span(p!(2, 5, 55), p!(2, 43, 93))
+            span: Span(55, 93),
+            ..Default::default()
           })
         ),
-        span: Span(p!(8), p!(2, 43, 93))
+        span: Span(7, 93),
+        ..Default::default()
       }
     );
 
@@ -1064,42 +1075,30 @@ mod test {
           NodeKind::Vector(
             vec![],
             vec![
+              Discard(Node::no_discards(NodeKind::Key("key"), Span(3, 7)), Span(1, 7), vec![]),
               Discard(
-                Node::no_discards(NodeKind::Key("key"), Span(p!(4), p!(8))),
-                Span(p!(2), p!(8))
-              ),
-              Discard(
-                Node::no_discards(NodeKind::Symbol("sym"), Span(p!(12), p!(15))),
-                Span(p!(10), p!(15))
-              ),
-              Discard(
-                Node::no_discards(NodeKind::Str("str"), Span(p!(19), p!(24))),
-                Span(p!(17), p!(24))
-              ),
-              Discard(
-                Node::no_discards(NodeKind::Int(1), Span(p!(28), p!(29))),
-                Span(p!(26), p!(29))
-              ),
-              Discard(
-                Node::no_discards(NodeKind::Double(3.14.into()), Span(p!(33), p!(37))),
-                Span(p!(31), p!(37))
-              ),
-              Discard(
-                Node::no_discards(NodeKind::Rational((22, 7)), Span(p!(41), p!(45))),
-                Span(p!(39), p!(45))
+                Node::no_discards(NodeKind::Symbol("sym"), Span(11, 14)),
+                Span(9, 14),
+                vec![]
               ),
+              Discard(Node::no_discards(NodeKind::Str("str"), Span(18, 23)), Span(16, 23), vec![]),
+              Discard(Node::no_discards(NodeKind::Int(1), Span(27, 28)), Span(25, 28), vec![]),
               Discard(
-                Node::no_discards(NodeKind::Char('c'), Span(p!(49), p!(51))),
-                Span(p!(47), p!(51))
+                Node::no_discards(NodeKind::Double(3.14.into()), Span(32, 36)),
+                Span(30, 36),
+                vec![]
               ),
               Discard(
-                Node::no_discards(NodeKind::Bool(false), Span(p!(55), p!(60))),
-                Span(p!(53), p!(60))
+                Node::no_discards(NodeKind::Rational((22, 7)), Span(40, 44)),
+                Span(38, 44),
+                vec![]
               ),
-              Discard(Node::no_discards(NodeKind::Nil, Span(p!(64), p!(67))), Span(p!(62), p!(67))),
+              Discard(Node::no_discards(NodeKind::Char('c'), Span(48, 50)), Span(46, 50), vec![]),
+              Discard(Node::no_discards(NodeKind::Bool(false), Span(54, 59)), Span(52, 59), vec![]),
+              Discard(Node::no_discards(NodeKind::Nil, Span(63, 66)), Span(61, 66), vec![]),
             ],
           ),
-          Span(p!(1), p!(68))
+          Span(0, 67)
         )
       );
     }
@@ -1111,30 +1110,78 @@ mod test {
       Node::no_discards(
         NodeKind::List(
           vec![
-            Node::no_discards(NodeKind::Symbol("foo"), Span(p!(2), p!(5))),
+            Node::no_discards(NodeKind::Symbol("foo"), Span(1, 4)),
             Node {
               kind: NodeKind::Int(3),
               leading_discards: vec![Discard(
                 Node::no_discards(
                   NodeKind::Tagged(
                     "uuid",
-                    Span(p!(10), p!(14)),
+                    Span(9, 13),
                     Box::new(Node::no_discards(
                       NodeKind::Str("f81d4fae-7dec-11d0-a765-00a0c91e6bf6"),
-                      Span(p!(15), p!(53))
+                      Span(14, 52)
                     ))
                   ),
-                  Span(p!(9), p!(53))
+                  Span(8, 52)
                 ),
-                Span(p!(6), p!(53))
+                Span(5, 52),
+                vec![]
               )],
-              span: Span(p!(54), p!(55))
+              span: Span(53, 54),
+              ..Default::default()
             },
           ],
           vec![]
         ),
-        Span(p!(1), p!(56))
+        Span(0, 55)
       )
     );
   }
+
+  #[test]
+  fn trivia() {
+    use parse::Trivia;
+
+    for source in [
+      "#!/usr/bin/env bb\n;; config\n{:a 1, :b [2 3] ; trailing\n}",
+      "[ #_ ; gone\n :x #_#_ 1 2 ,\t(f #inst  \"2024\" #{ 1 } )  ; end\n ]",
+      "\n#_ :top ; and\n  #cat/tag #_ 1 \n {:k #_ v , \\c \"s\" 1} ",
+      "( )",
+    ] {
+      let mut reader = SourceReader::new(source);
+      let node = parse::parse_with_trivia(&mut reader).unwrap();
+
+      assert_eq!(format!("{}{}", node.display_with_source(source), reader.remaining()), source);
+    }
+
+    let node = parse::parse_with_trivia(&mut SourceReader::new("[1 ;one\n]")).unwrap();
+    assert_eq!(
+      node.trailing_trivia,
+      [Trivia::Whitespace(" "), Trivia::Comment(";one"), Trivia::Whitespace("\n")]
+    );
+    assert!(parse::parse(&mut SourceReader::new("[1 ;one\n]")).unwrap().trailing_trivia.is_empty());
+  }
+
+  #[test]
+  fn display() {
+    let source = "[1  2 #_ 3 {:a 1 :b  2} #inst \"x\" (f(g)) 0x10 +5 \"a\\tb\"]";
+    let node = parse::parse(&mut SourceReader::new(source)).unwrap();
+    assert_eq!(node.to_string(), "[1 2 #_3 {:a 1, :b 2} #inst \"x\" (f(g)) 16 5 \"a\\tb\"]");
+    assert_eq!(
+      node.display_with_source(source).to_string(),
+      "[1 2 #_3 {:a 1, :b 2} #inst \"x\" (f(g)) 0x10 +5 \"a\\tb\"]"
+    );
+
+    // What's new has no span, so it's spaced out like `Edn` does
+    let source = "[1 ; one\n 2]";
+    let mut node = parse::parse_with_trivia(&mut SourceReader::new(source)).unwrap();
+    let NodeKind::Vector(items, _) = &mut node.kind else { panic!("unexpected") };
+    items.insert(1, Node::no_discards(NodeKind::Key("new"), Span::default()));
+    items.push(Node::no_discards(
+      NodeKind::List(vec![Node::no_discards(NodeKind::Int(3), Span::default())], vec![]),
+      Span::default(),
+    ));
+    assert_eq!(node.display_with_source(source).to_string(), "[1 :new ; one\n 2 (3)]");
+  }
 }
@@ -0,0 +1,109 @@
+#[cfg(feature = "preserve-order")]
+mod test {
+  use core::hash::BuildHasher;
+  use std::collections::hash_map::RandomState;
+
+  use clojure_reader::edn::{self, Edn};
+
+  #[test]
+  fn display_keeps_source_order() {
+    let source = "{:name \"app\", :version 2, :deps {zeta {}, alpha {}}, :tags #{:z :a :m}}";
+    assert_eq!(edn::read_string(source).unwrap().to_string(), source);
+
+    let mut edn = edn::read_string("{:b 1}").unwrap();
+    edn.assoc(&Edn::Key("a"), Edn::Int(2)).unwrap();
+    assert_eq!(edn.to_string(), "{:b 1, :a 2}");
+  }
+
+  #[test]
+  fn order_does_not_matter_for_equality() {
+    let ab = edn::read_string("{:a 1 :b #{1 2}}").unwrap();
+    let ba = edn::read_string("{:b #{2 1} :a 1}").unwrap();
+    assert_eq!(ab, ba);
+    assert_eq!(ab.cmp(&ba), core::cmp::Ordering::Equal);
+
+    let state = RandomState::new();
+    assert_eq!(state.hash_one(&ab), state.hash_one(&ba));
+    assert_ne!(state.hash_one(&ab), state.hash_one(edn::read_string("{:a 1}").unwrap()));
+
+    assert!(edn::read_string("#{{:a 1 :b 2} {:b 2 :a 1}}").is_err());
+  }
+
+  #[test]
+  fn removing_keeps_the_rest_in_order() {
+    let mut edn = edn::read_string("{:c 1 :a 2 :b 3}").unwrap();
+    edn.dissoc(&Edn::Key("c")).unwrap();
+    assert_eq!(edn.to_string(), "{:a 2, :b 3}");
+  }
+
+  #[test]
+  fn iterators_and_entries_keep_order() {
+    let mut map = edn::Map::from([(Edn::Key("c"), Edn::Int(1)), (Edn::Key("a"), Edn::Int(2))]);
+    *map.entry(Edn::Key("b")).or_insert(Edn::Nil) = Edn::Int(3);
+    let _ = map.entry(Edn::Key("c")).and_modify(|v| *v = Edn::Int(0)).or_insert(Edn::Nil);
+    let keys: Vec<_> = map.keys().collect();
+    assert_eq!(keys, [&Edn::Key("c"), &Edn::Key("a"), &Edn::Key("b")]);
+    assert_eq!(map.iter().rev().next(), Some((&Edn::Key("b"), &Edn::Int(3))));
+    let values: Vec<_> = map.into_values().collect();
+    assert_eq!(values, [Edn::Int(0), Edn::Int(2), Edn::Int(3)]);
+
+    let set: edn::Set<i64> = [3, 1, 2].into();
+    let values: edn::ordered::SetIntoIter<i64> = set.into_iter();
+    assert_eq!(values.len(), 3);
+    assert_eq!(values.collect::<Vec<_>>(), [3, 1, 2]);
+  }
+
+  #[cfg(feature = "derive")]
+  #[test]
+  fn serde_keeps_source_order() {
+    use core::fmt;
+
+    use clojure_reader::{de, ser};
+    use serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
+    use serde_derive::Serialize;
+
+    // The keys of a map, in the order they're visited
+    #[derive(Debug, PartialEq)]
+    struct Keys(Vec<String>);
+
+    impl<'de> Deserialize<'de> for Keys {
+      fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct KeysVisitor;
+
+        impl<'de> Visitor<'de> for KeysVisitor {
+          type Value = Keys;
+
+          fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("a map")
+          }
+
+          fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Keys, A::Error> {
+            let mut keys = vec![];
+            while let Some((key, ())) = map.next_entry::<String, ()>()? {
+              keys.push(key);
+            }
+            Ok(Keys(keys))
+          }
+        }
+
+        deserializer.deserialize_map(KeysVisitor)
+      }
+    }
+
+    let edn = edn::read_string("{:zeta nil :alpha nil :mid nil}").unwrap();
+    assert_eq!(
+      de::from_edn::<Keys>(edn).unwrap(),
+      Keys(vec!["zeta".into(), "alpha".into(), "mid".into()])
+    );
+
+    #[derive(Serialize)]
+    struct Config {
+      name: &'static str,
+      deps: Vec<i64>,
+    }
+
+    let mut buffer = String::new();
+    let edn = ser::to_edn(&Config { name: "app", deps: vec![] }, &mut buffer).unwrap();
+    assert_eq!(edn.to_string(), "{:name \"app\", :deps []}");
+  }
+}
@@ -1,8 +1,5 @@
-extern crate alloc;
-
-use alloc::collections::{BTreeMap, BTreeSet};
-
 use clojure_reader::edn::{self, Edn};
+use clojure_reader::error::Code;
 
 #[test]
 fn parse_empty() {
@@ -10,7 +7,7 @@ fn parse_empty() {
   assert_eq!(edn::read_string("#_42").unwrap(), Edn::Nil);
   assert_eq!(edn::read_string("[]").unwrap(), Edn::Vector(Vec::new()));
   assert_eq!(edn::read_string("()").unwrap(), Edn::List(Vec::new()));
-  assert_eq!(edn::read_string("{}").unwrap(), Edn::Map(BTreeMap::new()));
+  assert_eq!(edn::read_string("{}").unwrap(), Edn::Map(edn::Map::new()));
 }
 
 #[test]
@@ -26,6 +23,10 @@ fn strings() {
   assert_eq!(edn::read_string("\"猫 are 猫\"").unwrap(), Edn::Str("猫 are 猫"));
 
   assert_eq!(edn::read_string(r#""foo\rbar""#).unwrap(), Edn::Str("foo\\rbar"));
+  assert_eq!(
+    edn::read_string(r#""a \"quoted\" \\ 猫\\""#).unwrap(),
+    Edn::Str(r#"a \"quoted\" \\ 猫\\"#)
+  );
 }
 
 #[test]
@@ -41,10 +42,10 @@ fn maps() {
     }";
   assert_eq!(
     edn::read_string(e).unwrap(),
-    Edn::Map(BTreeMap::from([
+    Edn::Map(edn::Map::from([
       (Edn::Key("cat"), Edn::Str("猫")),
       (Edn::Key("num"), Edn::Int(-36930)),
-      (Edn::Map(BTreeMap::from([(Edn::Key("foo"), Edn::Str("bar"))])), Edn::Str("foobar")),
+      (Edn::Map(edn::Map::from([(Edn::Key("foo"), Edn::Str("bar"))])), Edn::Str("foobar")),
       (Edn::Key("r"), Edn::Rational((42, 4242))),
       (Edn::Key("lisp"), Edn::List(vec![Edn::List(vec![])])),
     ]))
@@ -53,9 +54,9 @@ fn maps() {
 
 #[test]
 fn whitespace() {
-  let expected_result = Edn::Map(BTreeMap::from([(
+  let expected_result = Edn::Map(edn::Map::from([(
     Edn::Key("somevec"),
-    Edn::Vector(vec![Edn::Map(BTreeMap::from([(Edn::Key("value"), Edn::Int(42))]))]),
+    Edn::Vector(vec![Edn::Map(edn::Map::from([(Edn::Key("value"), Edn::Int(42))]))]),
   )]));
 
   let e = "{:somevec
@@ -77,6 +78,10 @@ fn whitespace() {
 
   let e = "{:somevec,[{:value,42}]}";
   assert_eq!(edn::read_string(e).unwrap(), expected_result);
+
+  // Unicode whitespace separates forms too
+  let e = "{:somevec\u{3000}[{:value\u{a0}42}\u{2028}]}";
+  assert_eq!(edn::read_string(e).unwrap(), expected_result);
 }
 
 #[test]
@@ -84,11 +89,11 @@ fn sets() {
   let e = "#{:cat 1 true #{:cat true} 2 [42]}";
   assert_eq!(
     edn::read_string(e).unwrap(),
-    Edn::Set(BTreeSet::from([
+    Edn::Set(edn::Set::from([
       Edn::Key("cat"),
       Edn::Int(1),
       Edn::Bool(true),
-      Edn::Set(BTreeSet::from([Edn::Key("cat"), Edn::Bool(true)])),
+      Edn::Set(edn::Set::from([Edn::Key("cat"), Edn::Bool(true)])),
       Edn::Int(2),
       (Edn::Vector(vec![Edn::Int(42)])),
     ]))
@@ -219,6 +224,16 @@ fn comments_can_end_with_cr() {
   assert_eq!(edn::read_string(";comment\r42").unwrap(), Edn::Int(42));
 }
 
+#[test]
+fn shebang_comments() {
+  assert_eq!(edn::read_string("#!/usr/bin/env bb\n42").unwrap(), Edn::Int(42));
+  assert_eq!(
+    edn::read_string("[1 #!two\n3]").unwrap(),
+    Edn::Vector(vec![Edn::Int(1), Edn::Int(3)])
+  );
+  assert_eq!(edn::read_string("#!").unwrap(), Edn::Nil);
+}
+
 #[test]
 fn read_forms() {
   let s = "(def foo 42)(sum '(1 2 3)) #_(foo the bar (cat)) 42 nil 2";
@@ -265,7 +280,7 @@ fn tagged() {
   );
   assert_eq!(
     edn::read_string("#:foo {}").unwrap(),
-    Edn::Tagged(":foo", Box::new(Edn::Map(BTreeMap::new())))
+    Edn::Tagged(":foo", Box::new(Edn::Map(edn::Map::new())))
   );
   assert_eq!(
     edn::read_string("#foo\"bar\"").unwrap(),
@@ -285,7 +300,7 @@ fn tagged() {
         "bar",
         Box::new(Edn::Tagged(
           "ニャンキャット",
-          Box::new(Edn::Map(BTreeMap::from([(
+          Box::new(Edn::Map(edn::Map::from([(
             Edn::Key("baz"),
             Edn::Tagged("tag42", Box::new(Edn::Str("wut")))
           )])))
@@ -306,3 +321,50 @@ fn discard_tagged_values() {
   assert_eq!(edn::read_string("#_ #foo #{1 1}").unwrap(), Edn::Nil);
   assert_eq!(edn::read_string("#_ [#{1 1}]").unwrap(), Edn::Nil);
 }
+
+#[test]
+fn namespaced_maps_expand() {
+  let read = |source| {
+    let namespaces = edn::Namespaces::new().current("user").aliases(&[("s", "clojure.string")]);
+    let mut buffer = String::new();
+    edn::read_string_with(source, &namespaces, &mut buffer).map(|edn| edn.to_string())
+  };
+  let expanded = |source| edn::read_string(source).unwrap().to_string();
+
+  assert_eq!(
+    read(r#"#:thingy {:foo 1 :baz/bar 2 :_/bare 3 sym 4 "s" 5 42 6}"#).unwrap(),
+    expanded(r#"{:thingy/foo 1 :baz/bar 2 :bare 3 thingy/sym 4 "s" 5 42 6}"#)
+  );
+  assert_eq!(
+    read("[#::{:a 1} #::s{:b #:c{:d 2}} #inst #:e{:f 3} {#:g{:h 4} 5}]").unwrap(),
+    expanded("[{:user/a 1} {:clojure.string/b {:c/d 2}} #inst {:e/f 3} {{:g/h 4} 5}]")
+  );
+  assert_eq!(read("#:a{}").unwrap(), "{}");
+  // Keys and items that end up the same fail at the second one
+  let duplicates = [
+    ("#:a{:b 1 :a/b 2}", Code::HashMapDuplicateKey, 9),
+    ("#{#:a{:b 1} {:a/b 1}}", Code::SetDuplicateKey, 12),
+    ("{#:a{:b 1} 1,\n {:a/b 1} 2}", Code::HashMapDuplicateKey, 15),
+    ("[{[#::{:x 1}] 1 [{:user/x 1}] 2}]", Code::HashMapDuplicateKey, 16),
+    ("#{#{#:a{:b #:c{:d 1}}} #{{:a/b {:c/d 1}}}}", Code::SetDuplicateKey, 23),
+    ("#{#:a{:b 1} {:a/b 2} #:a{:_/b 1} {:b 1}}", Code::SetDuplicateKey, 33),
+  ];
+  for (source, code, ptr) in duplicates {
+    let err = read(source).unwrap_err();
+    assert_eq!((err.code, err.ptr), (code, Some(ptr)), "{source}");
+    assert!(err.line.is_some(), "{source}");
+  }
+  assert!(read("#{#:a{:b 1} {:a/b 2} #:a{:_/b 1}}").is_ok());
+  assert_eq!(read("[#{#:a{:b 1}} {#:a{:b 1} 1}]").unwrap(), expanded("[#{{:a/b 1}} {{:a/b 1} 1}]"));
+
+  // `/` is a name too, like in Clojure
+  assert_eq!(read("#:a{/ 1 :_// 2}").unwrap(), expanded("{a// 1 :/ 2}"));
+  assert_eq!(read("#:a{/ 1 a// 2}").unwrap_err().code, Code::HashMapDuplicateKey);
+
+  let err = read("[#::t{:a 1}]").unwrap_err();
+  assert_eq!((err.code, err.line, err.column), (Code::InvalidTag, Some(1), Some(3)));
+  let mut buffer = String::new();
+  let err = edn::read_string_with("#::{:a 1}", &edn::Namespaces::new(), &mut buffer).unwrap_err();
+  assert_eq!(err.code, Code::InvalidTag);
+  assert_eq!(edn::read_string("#::{:a 1}").unwrap_err().code, Code::InvalidTag);
+}
@@ -1,9 +1,5 @@
 #[cfg(feature = "arbitrary-nums")]
 mod test {
-  extern crate alloc;
-
-  use alloc::collections::BTreeMap;
-
   use clojure_reader::edn::{self, Edn};
 
   #[test]
@@ -120,11 +116,11 @@ mod test {
     }";
     assert_eq!(
       edn::read_string(e).unwrap(),
-      Edn::Map(BTreeMap::from([
+      Edn::Map(edn::Map::from([
         (Edn::Key("cat"), Edn::Str("猫")),
         (Edn::Key("num"), Edn::Int(-36930)),
         (read_big_float("40.42"), Edn::Str("forty dot forty-two")),
-        (Edn::Map(BTreeMap::from([(Edn::Key("foo"), Edn::Str("bar"))])), Edn::Str("foobar")),
+        (Edn::Map(edn::Map::from([(Edn::Key("foo"), Edn::Str("bar"))])), Edn::Str("foobar")),
         (Edn::Key("r"), Edn::Rational((42, 4242))),
         (Edn::Key("lisp"), Edn::List(vec![Edn::List(vec![])])),
       ]))
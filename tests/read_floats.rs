@@ -1,9 +1,5 @@
 #[cfg(feature = "floats")]
 mod test {
-  extern crate alloc;
-
-  use alloc::collections::BTreeMap;
-
   use clojure_reader::edn::{self, Edn};
 
   #[test]
@@ -26,11 +22,11 @@ mod test {
     }";
     assert_eq!(
       edn::read_string(e).unwrap(),
-      Edn::Map(BTreeMap::from([
+      Edn::Map(edn::Map::from([
         (Edn::Key("cat"), Edn::Str("猫")),
         (Edn::Key("num"), Edn::Int(-36930)),
         (Edn::Double((40.42).into()), Edn::Str("forty dot forty-two")),
-        (Edn::Map(BTreeMap::from([(Edn::Key("foo"), Edn::Str("bar"))])), Edn::Str("foobar")),
+        (Edn::Map(edn::Map::from([(Edn::Key("foo"), Edn::Str("bar"))])), Edn::Str("foobar")),
         (Edn::Key("r"), Edn::Rational((42, 4242))),
         (Edn::Key("lisp"), Edn::List(vec![Edn::List(vec![])])),
       ]))
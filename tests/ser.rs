@@ -6,9 +6,11 @@ mod test {
   use alloc::string::String;
   use alloc::vec::Vec;
 
-  use clojure_reader::ser::to_string;
+  use clojure_reader::de::{RawEdn, from_edn, from_edn_with, from_str};
+  use clojure_reader::edn::{self, Edn};
+  use clojure_reader::ser::{EnumRepr, Serializer, to_edn, to_string};
   use serde::ser;
-  use serde_derive::Serialize;
+  use serde_derive::{Deserialize, Serialize};
 
   #[test]
   fn maybe() {
@@ -139,6 +141,76 @@ mod test {
     assert_eq!(r#"#E/Struct {:a 1, :b 42}"#, to_string(&E::Struct { a: 1, b: 42 }).unwrap());
   }
 
+  #[test]
+  fn enum_reprs() {
+    #[derive(Serialize)]
+    enum E {
+      Unit,
+      Newtype(u32),
+      Tuple(u32, u32),
+      Struct { a: u32, b: usize },
+    }
+
+    let all = [E::Unit, E::Newtype(1), E::Tuple(1, 2), E::Struct { a: 1, b: 42 }];
+    let write = |repr| {
+      let mut serializer = Serializer::new().enums(repr);
+      serde::Serialize::serialize(&all, &mut serializer).unwrap();
+      serializer.into_inner()
+    };
+    assert_eq!(
+      write(EnumRepr::Namespaced("my.app")),
+      "[#my.app/Unit nil #my.app/Newtype 1 #my.app/Tuple [1 2] #my.app/Struct {:a 1, :b 42}]"
+    );
+    assert_eq!(
+      write(EnumRepr::Keyword),
+      "[:Unit #E/Newtype 1 #E/Tuple [1 2] #E/Struct {:a 1, :b 42}]"
+    );
+    assert_eq!(write(EnumRepr::Map), "[:Unit {:Newtype 1} {:Tuple [1 2]} {:Struct {:a 1, :b 42}}]");
+    assert_eq!(
+      write(EnumRepr::Vector),
+      "[:Unit [:Newtype 1] [:Tuple [1 2]] [:Struct {:a 1, :b 42}]]"
+    );
+  }
+
+  #[test]
+  fn namespaced_fields() {
+    #[derive(Serialize)]
+    #[serde(rename = "#:user")]
+    struct User {
+      name: &'static str,
+      #[serde(rename = "account/id")]
+      id: u32,
+    }
+
+    #[derive(Serialize)]
+    struct Plain {
+      name: &'static str,
+    }
+
+    let write = |serializer: Serializer| {
+      let mut serializer = serializer;
+      serde::Serialize::serialize(
+        &(User { name: "ada", id: 1 }, Plain { name: "bob" }),
+        &mut serializer,
+      )
+      .unwrap();
+      serializer.into_inner()
+    };
+    assert_eq!(write(Serializer::new()), "[{:user/name \"ada\", :account/id 1} {:name \"bob\"}]");
+    assert_eq!(
+      write(Serializer::new().namespaced_maps(true)),
+      "[#:user{:name \"ada\", :account/id 1} {:name \"bob\"}]"
+    );
+    assert_eq!(
+      write(Serializer::new().namespace("app")),
+      "[{:user/name \"ada\", :account/id 1} {:app/name \"bob\"}]"
+    );
+
+    let mut buffer = String::new();
+    let edn = to_edn(&User { name: "ada", id: 1 }, &mut buffer).unwrap();
+    assert_eq!(edn, edn::read_string("{:user/name \"ada\", :account/id 1}").unwrap());
+  }
+
   #[test]
   fn internally_tagged_enum() {
     #[derive(Serialize)]
@@ -214,4 +286,149 @@ mod test {
       "Err(EdnError { code: Serde(\"silly cats\"), line: None, column: None, ptr: None })"
     );
   }
+
+  // `to_edn` builds what `to_string` writes, and `from_edn` reads it back
+  fn round_trip<'a, T>(value: &T, buffer: &'a mut String) -> Edn<'a>
+  where
+    T: serde::Serialize + serde::Deserialize<'a> + PartialEq + core::fmt::Debug,
+  {
+    let source = to_string(value).unwrap();
+    let edn = to_edn(value, buffer).unwrap();
+    assert_eq!(edn, edn::read_string(&source).unwrap());
+    assert_eq!(&from_edn::<T>(edn.clone()).unwrap(), value);
+    edn
+  }
+
+  #[test]
+  fn to_edn_matches_to_string() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    enum E {
+      Unit,
+      Newtype(u32),
+      Tuple(u32, u32),
+      Struct { a: u32, b: usize },
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    #[serde(rename_all = "kebab-case")]
+    struct Test {
+      int: u32,
+      silly_cat: bool,
+      foo: BTreeMap<u8, i8>,
+      names: BTreeMap<String, Option<f64>>,
+      bar: Vec<u16>,
+      character: char,
+      tup: (u8, String),
+      empty: (),
+      enums: Vec<E>,
+    }
+
+    let test = Test {
+      int: 42,
+      silly_cat: true,
+      foo: BTreeMap::from([(1, -1), (2, -42)]),
+      names: BTreeMap::from([("猫".to_string(), Some(1.5)), ("dog".to_string(), None)]),
+      bar: vec![1, 2, 42, 3],
+      character: '\n',
+      tup: (42, "cat".to_string()),
+      empty: (),
+      enums: vec![E::Unit, E::Newtype(1), E::Tuple(1, 2), E::Struct { a: 1, b: 42 }],
+    };
+    let mut buffer = String::new();
+    let edn = round_trip(&test, &mut buffer);
+    assert_eq!(edn.get(&Edn::Key("silly-cat")), Some(&Edn::Bool(true)));
+
+    let mut buffer = String::new();
+    assert_eq!(to_edn(&E::Unit, &mut buffer).unwrap(), Edn::Tagged("E/Unit", Box::new(Edn::Nil)));
+    let mut buffer = String::new();
+    round_trip(&Some(vec![Some(1), None]), &mut buffer);
+  }
+
+  #[test]
+  fn to_edn_with_conventions() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    enum E {
+      Unit,
+      Newtype(u32),
+      Tuple(u32, u32),
+      Struct { a: u32 },
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Test {
+      name: String,
+      enums: Vec<E>,
+    }
+
+    let test = Test {
+      name: "cat".to_string(),
+      enums: vec![E::Unit, E::Newtype(1), E::Tuple(1, 2), E::Struct { a: 3 }],
+    };
+    let reprs = [
+      EnumRepr::Tagged,
+      EnumRepr::Namespaced("my.ns"),
+      EnumRepr::Keyword,
+      EnumRepr::Map,
+      EnumRepr::Vector,
+    ];
+    for repr in reprs {
+      for (namespace, namespaced_maps) in [(None, false), (Some("app"), false), (Some("app"), true)]
+      {
+        let configured = || {
+          let serializer = Serializer::new().enums(repr).namespaced_maps(namespaced_maps);
+          match namespace {
+            Some(namespace) => serializer.namespace(namespace),
+            None => serializer,
+          }
+        };
+        let mut text = configured();
+        serde::Serialize::serialize(&test, &mut text).unwrap();
+        let source = text.into_inner();
+        let mut buffer = String::new();
+        let edn = configured().to_edn(&test, &mut buffer).unwrap();
+        assert_eq!(edn, edn::read_string(&source).unwrap(), "{source}");
+        assert_eq!(from_edn_with::<Test>(edn, repr, namespace).unwrap(), test, "{source}");
+      }
+    }
+
+    let serializer = Serializer::new().enums(EnumRepr::Vector);
+    let mut buffer = String::new();
+    let edn = serializer.to_edn(&E::Newtype(1), &mut buffer).unwrap();
+    assert_eq!(edn, Edn::Vector(vec![Edn::Key("Newtype"), Edn::Int(1)]));
+    let serializer = Serializer::new().enums(EnumRepr::Keyword);
+    let mut buffer = String::new();
+    assert_eq!(serializer.to_edn(&E::Unit, &mut buffer).unwrap(), Edn::Key("Unit"));
+  }
+
+  #[test]
+  fn to_edn_keeps_raw_edn() {
+    #[derive(Serialize, Deserialize)]
+    struct Raw<'a> {
+      #[serde(borrow)]
+      form: RawEdn<'a>,
+    }
+
+    let raw: Raw<'_> = from_str("{:form #inst \"2024\" :other 1}").unwrap();
+    let mut buffer = String::new();
+    let edn = to_edn(&raw, &mut buffer).unwrap();
+    assert_eq!(edn, edn::read_string("{:form #inst \"2024\"}").unwrap());
+  }
+
+  #[test]
+  fn to_edn_errors() {
+    struct Fails;
+
+    impl serde::Serialize for Fails {
+      fn serialize<S>(&self, _serializer: S) -> Result<S::Ok, S::Error>
+      where
+        S: serde::Serializer,
+      {
+        Err(ser::Error::custom("silly cats"))
+      }
+    }
+
+    let mut buffer = String::new();
+    let err = to_edn(&vec![Fails], &mut buffer).unwrap_err();
+    assert_eq!(err.code, clojure_reader::error::Code::Serde("silly cats".to_string()));
+  }
 }
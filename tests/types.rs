@@ -0,0 +1,145 @@
+#[cfg(feature = "serde")]
+mod test {
+  extern crate alloc;
+
+  use alloc::collections::{BTreeMap, BTreeSet};
+  use alloc::string::{String, ToString};
+  use alloc::vec::Vec;
+
+  use clojure_reader::de::{from_edn, from_str};
+  use clojure_reader::edn::{self, Edn};
+  use clojure_reader::error::Code;
+  use clojure_reader::ser::{to_edn, to_string};
+  use clojure_reader::types::{Keyword, Rational, Set, Symbol, Tagged};
+  use serde_derive::{Deserialize, Serialize};
+
+  #[derive(Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug)]
+  #[serde(rename_all = "kebab-case")]
+  enum Status {
+    Active,
+    OnHold,
+  }
+
+  #[derive(Serialize, Deserialize, PartialEq, Debug)]
+  #[serde(rename_all = "kebab-case")]
+  struct Account {
+    status: Keyword<Status>,
+    kind: Keyword,
+    owner: Symbol<String>,
+    roles: Set<BTreeSet<Keyword>>,
+    opened: Tagged<String>,
+    share: Rational,
+    meta: BTreeMap<Keyword<Status>, Set<Vec<i64>>>,
+  }
+
+  fn account() -> Account {
+    Account {
+      status: Keyword(Status::OnHold),
+      kind: Keyword("user/admin".to_string()),
+      owner: Symbol("ada".to_string()),
+      roles: Set(BTreeSet::from([Keyword("read".to_string()), Keyword("write".to_string())])),
+      opened: Tagged { tag: "inst".to_string(), value: "2024-01-01".to_string() },
+      share: Rational(1, 3),
+      meta: BTreeMap::from([(Keyword(Status::Active), Set(vec![1, 2]))]),
+    }
+  }
+
+  const ACCOUNT: &str = "{:status :on-hold, :kind :user/admin, :owner ada, :roles #{:read :write}, \
+                         :opened #inst \"2024-01-01\", :share 1/3, :meta {:active #{1 2}}}";
+
+  #[test]
+  fn to_string_writes_them_as_edn() {
+    assert_eq!(to_string(&account()).unwrap(), ACCOUNT);
+    assert_eq!(to_string(&Set(Vec::<i64>::new())).unwrap(), "#{}");
+    assert_eq!(
+      to_string(&Tagged { tag: "my/tag".to_string(), value: Set(vec![Keyword("a")]) }).unwrap(),
+      "#my/tag #{:a}"
+    );
+  }
+
+  #[test]
+  fn from_str_reads_them() {
+    assert_eq!(from_str::<Account>(ACCOUNT).unwrap(), account());
+
+    let keyword: Keyword<&str> = from_str(":borrowed").unwrap();
+    assert_eq!(keyword, Keyword("borrowed"));
+    let tagged: Tagged<Tagged<i64>> = from_str("#a #b 1").unwrap();
+    assert_eq!(tagged.value, Tagged { tag: "b".to_string(), value: 1 });
+    let tagged: Tagged<BTreeMap<Keyword, i64>> = from_str("#:ns{:a 1}").unwrap();
+    assert_eq!(tagged.tag, ":ns");
+  }
+
+  #[test]
+  fn edn_round_trips() {
+    let mut buffer = String::new();
+    let edn = to_edn(&account(), &mut buffer).unwrap();
+    assert_eq!(edn, edn::read_string(ACCOUNT).unwrap());
+    assert_eq!(edn.get(&Edn::Key("owner")), Some(&Edn::Symbol("ada")));
+    assert_eq!(from_edn::<Account>(edn).unwrap(), account());
+  }
+
+  #[test]
+  fn only_markers_take_tagged_and_rational_apart() {
+    let tagged: Tagged<Vec<Rational>> = from_str("#E/Tuple [4/2]").unwrap();
+    assert_eq!(tagged, Tagged { tag: "E/Tuple".to_string(), value: vec![Rational(4, 2)] });
+    let rational: Rational = from_edn(edn::read_string("1/2").unwrap()).unwrap();
+    assert_eq!(rational, Rational(1, 2));
+
+    let err = from_str::<(String, Vec<Rational>)>("#E/Tuple [4/2]").unwrap_err();
+    assert_eq!(
+      err.code,
+      Code::Serde("invalid type: tagged value, expected a tuple of size 2".into())
+    );
+    let err = from_edn::<(i64, i64)>(edn::read_string("1/2").unwrap()).unwrap_err();
+    assert_eq!(err.code, Code::Serde("invalid type: ratio, expected a tuple of size 2".into()));
+  }
+
+  #[test]
+  fn errors() {
+    assert_eq!(
+      format!("{:?}", from_str::<Keyword>("\"str\"").unwrap_err()),
+      "EdnError { code: Serde(\"can't convert Str(\\\"str\\\") into a keyword\"), line: Some(1), column: Some(1), ptr: Some(0) }"
+    );
+    assert_eq!(
+      format!("{:?}", from_str::<Set<Vec<i64>>>("[1 2]").unwrap_err()),
+      "EdnError { code: Serde(\"can't convert Vector([Int(1), Int(2)]) into a set\"), line: Some(1), column: Some(1), ptr: Some(0) }"
+    );
+    assert_eq!(
+      format!("{:?}", to_string(&Keyword(1)).unwrap_err()),
+      "EdnError { code: Serde(\"a keyword or a symbol has to be named by a string\"), line: None, column: None, ptr: None }"
+    );
+    assert_eq!(
+      format!("{:?}", to_string(&Set(1)).unwrap_err()),
+      "EdnError { code: Serde(\"can't serialize a set from something else\"), line: None, column: None, ptr: None }"
+    );
+    assert_eq!(
+      format!("{:?}", to_string(&Tagged { tag: "1".to_string(), value: 1 }).unwrap_err()),
+      "EdnError { code: Serde(\"invalid tag 1\"), line: None, column: None, ptr: None }"
+    );
+  }
+
+  #[cfg(feature = "arbitrary-nums")]
+  #[test]
+  fn big_numbers() {
+    use clojure_reader::types::{BigDec, BigInt};
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Big {
+      int: BigInt,
+      dec: BigDec,
+    }
+
+    let source = "{:int 123456789012345678901234567890N, :dec 1.5M}";
+    let big: Big = from_str(source).unwrap();
+    assert_eq!(big.int.0.to_string(), "123456789012345678901234567890");
+    assert_eq!(to_string(&big).unwrap(), source);
+
+    let mut buffer = String::new();
+    let edn = to_edn(&big, &mut buffer).unwrap();
+    assert_eq!(edn, edn::read_string(source).unwrap());
+    assert_eq!(from_edn::<Big>(edn).unwrap(), big);
+
+    let big: Big = from_str("{:int 1 :dec 2}").unwrap();
+    assert_eq!(to_string(&big).unwrap(), "{:int 1N, :dec 2M}");
+  }
+}
@@ -1,9 +1,11 @@
 #[cfg(feature = "unstable")]
 mod test {
+  use std::ops::Range;
+
   use clojure_reader::{
     edn::{self, Edn},
     error::Code,
-    parse::{self, Node, NodeKind, Position, SourceReader, Span},
+    parse::{self, Node, NodeKind, SourceReader, Span},
   };
 
   #[test]
@@ -23,15 +25,14 @@ mod test {
     let map_node = parse::parse(&mut SourceReader::new("{:a 1 :a [2]}")).unwrap();
     let map_err = Edn::try_from(map_node).unwrap_err();
     assert_eq!(map_err.code, Code::HashMapDuplicateKey);
-    assert_eq!(map_err.line, Some(1));
-    assert_eq!(map_err.column, Some(13));
+    // A node doesn't keep its source, so only the offset is known
+    assert_eq!(map_err.line, None);
+    assert_eq!(map_err.column, None);
     assert_eq!(map_err.ptr, Some(12));
 
     let set_node = parse::parse(&mut SourceReader::new("#{:cat 1 2 [42] 2}")).unwrap();
     let set_err = Edn::try_from(set_node).unwrap_err();
     assert_eq!(set_err.code, Code::SetDuplicateKey);
-    assert_eq!(set_err.line, Some(1));
-    assert_eq!(set_err.column, Some(18));
     assert_eq!(set_err.ptr, Some(17));
   }
 
@@ -41,8 +42,6 @@ mod test {
     let err = Edn::try_from(node).unwrap_err();
 
     assert_eq!(err.code, Code::InvalidTag);
-    assert_eq!(err.line, Some(1));
-    assert_eq!(err.column, Some(2));
     assert_eq!(err.ptr, Some(1));
 
     for input in [
@@ -121,10 +120,469 @@ mod test {
     let mut reader = SourceReader::new("(cat) [42]");
     let _ = parse::parse(&mut reader).unwrap();
 
-    let (position, source) = reader.finish();
+    let (ptr, source) = reader.finish();
 
-    assert_eq!(position, Position { line: 1, column: 6, ptr: 5 });
+    assert_eq!(ptr, 5);
     assert_eq!(source, "(cat) [42]");
-    assert_eq!(&source[position.ptr..], " [42]");
+    assert_eq!(&source[ptr..], " [42]");
+  }
+
+  #[test]
+  fn spanned_edn_matches_read_string() {
+    let input = r#"#_ :ignored {:foo [1 #_2 {:bar nil}] :baz #{true #_false :qux} :tagged #inst "1985-04-12T23:20:50.52Z"} trailing"#;
+
+    let mut reader = SourceReader::new(input);
+    let spanned = parse::parse_spanned(&mut reader).unwrap();
+    let edn = edn::read_string(input).unwrap();
+
+    assert_eq!(spanned, edn);
+    assert_eq!(Edn::from(spanned), edn);
+    assert_eq!(reader.remaining(), " trailing");
+  }
+
+  #[test]
+  fn spanned_edn_knows_where_keys_and_values_are() {
+    let source = "{:servers [{:port 80}\n           {:port 443}]\n #{:a} #:cat{:name \"猫\"}}";
+    let spanned = parse::parse_spanned(&mut SourceReader::new(source)).unwrap();
+
+    let servers = spanned.get(&Edn::Key("servers")).unwrap();
+    assert_eq!(servers.span(), Span(10, 45));
+
+    let (key, port) = servers.nth(1).unwrap().get_key_value(&Edn::Key("port")).unwrap();
+    assert_eq!(*key, Edn::Key("port"));
+    assert_eq!(key.span.0, 34);
+    assert_eq!(*port, Edn::Int(443));
+    assert_eq!(port.span.0, 40);
+
+    let set = edn::read_string("#{:a}").unwrap();
+    let (key, cat) = spanned.get_key_value(&set).unwrap();
+    assert_eq!(key.span.0, 47);
+    assert_eq!(*cat.get(&Edn::Key("cat/name")).unwrap(), Edn::Str("猫"));
+    let name = cat.get(&Edn::Key("cat/name")).unwrap().span;
+    assert_eq!(&source[Range::from(name)], "\"猫\"");
+    assert!(cat.contains(&Edn::Key("cat/name")));
+    assert!(!cat.contains(&Edn::Key("name")));
+
+    assert!(spanned.contains(&Edn::Key("servers")));
+    assert!(servers.contains(&edn::read_string("{:port 80}").unwrap()));
+    assert!(!servers.contains(&edn::read_string("{:port 8080}").unwrap()));
+    assert!(key.contains(&Edn::Key("a")));
+    assert_eq!(spanned.nth(0), None);
+    assert_eq!(servers.get(&Edn::Int(0)), None);
+  }
+
+  #[test]
+  fn spanned_maps_and_sets_keep_the_order_they_are_read_in() {
+    let source = "{:z 1 #{[1 2] {:a #{3 4}}} 2 :a 3}";
+    let spanned = parse::parse_spanned(&mut SourceReader::new(source)).unwrap();
+    let parse::SpannedKind::Map(map) = &spanned.kind else { panic!("{spanned:?}") };
+
+    let keys: Vec<_> = map.iter().map(|(key, _)| &source[Range::from(key.span)]).collect();
+    assert_eq!(keys, [":z", "#{[1 2] {:a #{3 4}}}", ":a"]);
+    let key = edn::read_string("#{{:a #{4 3}} [1 2]}").unwrap();
+    assert_eq!(*map.get(&key).unwrap(), Edn::Int(2));
+    assert!(!map.contains_key(&edn::read_string("#{[1 2]}").unwrap()));
+    assert_eq!(spanned, edn::read_string(source).unwrap());
+
+    let err = parse::parse_spanned(&mut SourceReader::new("#{#{1 2} [] #{2 1}}")).unwrap_err();
+    assert_eq!((err.code, err.ptr), (Code::SetDuplicateKey, Some(18)));
+  }
+
+  #[test]
+  fn spanned_edn_is_validated() {
+    let err = parse::parse_spanned(&mut SourceReader::new("[{:a 1 :a [2]}]")).unwrap_err();
+    assert_eq!((err.code, err.ptr), (Code::HashMapDuplicateKey, Some(13)));
+
+    let err = parse::parse_spanned(&mut SourceReader::new("#{:cat 1 2 [42] 2}")).unwrap_err();
+    assert_eq!((err.code, err.ptr), (Code::SetDuplicateKey, Some(17)));
+
+    let err = parse::parse_spanned(&mut SourceReader::new("#foo/bar/baz nil")).unwrap_err();
+    assert_eq!((err.code, err.ptr), (Code::InvalidTag, Some(1)));
+
+    // Duplicates inside discards don't matter, just like `read_string`
+    let spanned = parse::parse_spanned(&mut SourceReader::new("[#_{:a 1 :a 2} 1]")).unwrap();
+    assert_eq!(spanned, Edn::Vector(vec![Edn::Int(1)]));
+  }
+
+  /// Collects the `:name` of every map, wherever it is, without building a tree
+  struct Names;
+
+  enum Form<'e> {
+    Key(&'e str),
+    Str(&'e str),
+    Names(Vec<&'e str>),
+  }
+
+  impl<'e> Form<'e> {
+    fn into_names(self) -> Vec<&'e str> {
+      match self {
+        Form::Names(names) => names,
+        Form::Key(_) | Form::Str(_) => Vec::new(),
+      }
+    }
+  }
+
+  impl<'e> parse::Builder<'e> for Names {
+    type Item = Form<'e>;
+    type Discard = ();
+    type VectorContext = Vec<&'e str>;
+    type ListContext = Vec<&'e str>;
+    type MapContext = (Vec<&'e str>, Option<Form<'e>>);
+    type SetContext = Vec<&'e str>;
+
+    fn atom(&self, atom: parse::Atom<'e>, _span: Span) -> Form<'e> {
+      match atom {
+        parse::Atom::Key(key) => Form::Key(key),
+        parse::Atom::Str(str) => Form::Str(str),
+        _ => Form::Names(Vec::new()),
+      }
+    }
+
+    fn with_leading_discards(&self, item: Form<'e>, _: Vec<()>) -> Form<'e> {
+      item
+    }
+
+    fn new_vector_context(&self) -> Vec<&'e str> {
+      Vec::new()
+    }
+
+    fn new_list_context(&self) -> Vec<&'e str> {
+      Vec::new()
+    }
+
+    fn new_map_context(&self) -> (Vec<&'e str>, Option<Form<'e>>) {
+      (Vec::new(), None)
+    }
+
+    fn new_set_context(&self) -> Vec<&'e str> {
+      Vec::new()
+    }
+
+    fn add_to_vector(
+      &self,
+      ctx: &mut Vec<&'e str>,
+      parsed: parse::Parsed<Form<'e>>,
+      _: Vec<()>,
+    ) -> Result<(), clojure_reader::error::Error> {
+      if let Form::Names(names) = parsed.item {
+        ctx.extend(names);
+      }
+      Ok(())
+    }
+
+    fn add_to_list(
+      &self,
+      ctx: &mut Vec<&'e str>,
+      parsed: parse::Parsed<Form<'e>>,
+      discards: Vec<()>,
+    ) -> Result<(), clojure_reader::error::Error> {
+      self.add_to_vector(ctx, parsed, discards)
+    }
+
+    fn add_to_map(
+      &self,
+      ctx: &mut (Vec<&'e str>, Option<Form<'e>>),
+      parsed: parse::Parsed<Form<'e>>,
+      _: Vec<()>,
+    ) -> Result<(), clojure_reader::error::Error> {
+      let (names, key) = ctx;
+      match (key.take(), parsed.item) {
+        (None, key_form) => *key = Some(key_form),
+        (Some(Form::Key("name")), Form::Str(name)) => names.push(name),
+        (Some(_), Form::Names(nested)) => names.extend(nested),
+        (Some(_), _) => {}
+      }
+      Ok(())
+    }
+
+    fn add_to_set(
+      &self,
+      ctx: &mut Vec<&'e str>,
+      parsed: parse::Parsed<Form<'e>>,
+      discards: Vec<()>,
+    ) -> Result<(), clojure_reader::error::Error> {
+      self.add_to_vector(ctx, parsed, discards)
+    }
+
+    fn finish_vector(
+      &self,
+      ctx: Vec<&'e str>,
+      _: Vec<()>,
+      span: Span,
+    ) -> Result<parse::Parsed<Form<'e>>, clojure_reader::error::Error> {
+      Ok(parse::Parsed::new(Form::Names(ctx), span))
+    }
+
+    fn finish_set(
+      &self,
+      ctx: Vec<&'e str>,
+      discards: Vec<()>,
+      _validate: bool,
+      span: Span,
+    ) -> Result<parse::Parsed<Form<'e>>, clojure_reader::error::Error> {
+      self.finish_vector(ctx, discards, span)
+    }
+
+    fn finish_map(
+      &self,
+      ctx: (Vec<&'e str>, Option<Form<'e>>),
+      discards: Vec<()>,
+      _validate: bool,
+      close_pos: usize,
+      span: Span,
+    ) -> Result<parse::Parsed<Form<'e>>, clojure_reader::error::Error> {
+      if ctx.1.is_some() {
+        return Err(clojure_reader::error::Error {
+          code: Code::UnexpectedEOF,
+          line: None,
+          column: None,
+          ptr: Some(close_pos),
+        });
+      }
+      self.finish_vector(ctx.0, discards, span)
+    }
+
+    fn finish_list(
+      &self,
+      ctx: Vec<&'e str>,
+      discards: Vec<()>,
+      span: Span,
+    ) -> Result<parse::Parsed<Form<'e>>, clojure_reader::error::Error> {
+      self.finish_vector(ctx, discards, span)
+    }
+
+    fn tag(
+      &self,
+      tag: &'e str,
+      tag_span: Span,
+      value: parse::Parsed<Form<'e>>,
+      _: Vec<()>,
+      span: Span,
+    ) -> Result<parse::Parsed<Form<'e>>, clojure_reader::error::Error> {
+      if tag == "secret" {
+        return Err(clojure_reader::error::Error {
+          code: Code::InvalidTag,
+          line: None,
+          column: None,
+          ptr: Some(tag_span.0),
+        });
+      }
+      Ok(parse::Parsed::new(value.item, span))
+    }
+
+    fn discard(&self, _value: parse::Parsed<Form<'e>>, _: Vec<()>, _discard_span: Span) {}
+  }
+
+  #[test]
+  fn parse_with_a_custom_builder() {
+    let source =
+      r#"[{:name "cat" :tags #{:a}} #_{:name "hidden"} ({:id 1 :nested {:name "gato"}})] 42"#;
+    let mut reader = SourceReader::new(source);
+    let names = parse::parse_with(&mut reader, &Names).unwrap().into_names();
+    assert_eq!(names, ["cat", "gato"]);
+    assert_eq!(reader.remaining(), " 42");
+
+    let names = parse::parse_with(&mut SourceReader::new(" "), &Names).unwrap().into_names();
+    assert!(names.is_empty());
+
+    // Errors from the builder are located like any other, tags start after the `#`
+    let err = parse::parse_with(&mut SourceReader::new("[1\n #secret {:name \"x\"}]"), &Names)
+      .err()
+      .unwrap();
+    assert_eq!(err.code, Code::InvalidTag);
+    assert_eq!((err.line, err.column, err.ptr), (Some(2), Some(3), Some(5)));
+  }
+
+  fn events(source: &str) -> Vec<Result<(parse::Event<'_>, Span), Code>> {
+    parse::Events::new(&mut SourceReader::new(source))
+      .map(|event| event.map_err(|e| e.code))
+      .collect()
+  }
+
+  #[test]
+  fn events_follow_the_source() {
+    use parse::{Atom, Event};
+
+    assert_eq!(
+      events("[1 #_(:a) #inst \"x\" {:k #{}}] nil"),
+      [
+        Ok((Event::StartVector, Span(0, 1))),
+        Ok((Event::Atom(Atom::Int(1)), Span(1, 2))),
+        Ok((Event::Discard, Span(3, 5))),
+        Ok((Event::StartList, Span(5, 6))),
+        Ok((Event::Atom(Atom::Key("a")), Span(6, 8))),
+        Ok((Event::End, Span(5, 9))),
+        Ok((Event::Tag("inst"), Span(10, 15))),
+        Ok((Event::Atom(Atom::Str("x")), Span(16, 19))),
+        Ok((Event::StartMap, Span(20, 21))),
+        Ok((Event::Atom(Atom::Key("k")), Span(21, 23))),
+        Ok((Event::StartSet, Span(24, 26))),
+        Ok((Event::End, Span(24, 27))),
+        Ok((Event::End, Span(20, 28))),
+        Ok((Event::End, Span(0, 29))),
+        Ok((Event::Atom(Atom::Nil), Span(30, 33))),
+      ]
+    );
+    assert!(events(" ; nothing").is_empty());
+    // Nothing is kept to find duplicates with
+    assert_eq!(events("#{1 1}").len(), 4);
+  }
+
+  #[test]
+  fn events_are_validated() {
+    assert_eq!(events("[1 2)").last(), Some(&Err(Code::UnmatchedDelimiter(')'))));
+    assert_eq!(events("(1 2").last(), Some(&Err(Code::UnexpectedEOF)));
+    assert_eq!(events("{:a 1 :b}").last(), Some(&Err(Code::UnexpectedEOF)));
+    assert_eq!(events("{:a 1 #_:b}").len(), 6);
+    assert_eq!(events("[#_]").last(), Some(&Err(Code::UnmatchedDelimiter(']'))));
+    assert_eq!(events("#1 x").last(), Some(&Err(Code::InvalidTag)));
+    assert_eq!(events("#:cat [1]").last(), Some(&Err(Code::InvalidTag)));
+    assert_eq!(events("#:cat {:a 1}").len(), 5);
+    assert_eq!(events("#_ ").last(), Some(&Err(Code::UnexpectedEOF)));
+
+    // And stop at the first error
+    let mut reader = SourceReader::new("1 ] 2");
+    let mut events = parse::Events::new(&mut reader);
+    assert!(events.next().unwrap().is_ok());
+    let err = events.next().unwrap().unwrap_err();
+    assert_eq!(
+      (err.code, err.line, err.column, err.ptr),
+      (Code::UnmatchedDelimiter(']'), Some(1), Some(3), Some(2))
+    );
+    assert!(events.next().is_none());
+  }
+
+  #[test]
+  fn events_skip_forms() {
+    use parse::{Atom, Event, Events};
+
+    fn next<'e>(events: &mut Events<'_, 'e>) -> Event<'e> {
+      events.next().unwrap().unwrap().0
+    }
+
+    let mut reader = SourceReader::new("[[1 [2]] #tag #_ 3 {:a 4} 5] 6");
+    let mut events = Events::new(&mut reader);
+
+    assert_eq!(next(&mut events), Event::StartVector);
+    assert_eq!(next(&mut events), Event::StartVector);
+    events.skip_form().unwrap();
+    assert_eq!(next(&mut events), Event::Tag("tag"));
+    events.skip_form().unwrap();
+    assert_eq!(next(&mut events), Event::Atom(Atom::Int(5)));
+    // Nothing to skip after an atom
+    events.skip_form().unwrap();
+    assert_eq!(next(&mut events), Event::End);
+    assert_eq!(next(&mut events), Event::Atom(Atom::Int(6)));
+    assert!(events.next().is_none());
+
+    // Skipped forms are still read
+    let mut reader = SourceReader::new("[[1 2) 3]");
+    let mut events = Events::new(&mut reader);
+    events.next();
+    events.next();
+    assert_eq!(events.skip_form().unwrap_err().code, Code::UnmatchedDelimiter(')'));
+  }
+
+  #[test]
+  fn formatter_follows_cljfmt() {
+    use parse::{Formatter, Indent};
+
+    let formatter = Formatter::default();
+    for (source, formatted) in [
+      (
+        "  ;; header\n\n\n\n(ns foo\n(:require [a :as b]\n[c :as d]))\n(defn f\n[x]\n   (let [y 1\nz 2]\n  (when x   \n(g x)\n  y)))  \n",
+        ";; header\n\n\n(ns foo\n  (:require [a :as b]\n            [c :as d]))\n(defn f\n  [x]\n  (let [y 1\n        z 2]\n    (when x\n      (g x)\n      y)))\n",
+      ),
+      ("{:a 1\n:b [1 2\n3]\n  :c #{1\n2}}", "{:a 1\n :b [1 2\n     3]\n :c #{1\n      2}}"),
+      ("( foo(bar)[1] )", "(foo (bar) [1])"),
+      ("(foo\nbar\nbaz)", "(foo\n bar\n baz)"),
+      ("(reify P\n(foo [x]\nx))", "(reify P\n  (foo [x]\n    x))"),
+      ("(clojure.core/let [x 1]\nx)", "(clojure.core/let [x 1]\n  x)"),
+      // Comments still end their lines
+      ("[ ; one\n 1 #_ 2 ; two\n ]", "[; one\n 1 #_ 2 ; two\n ]"),
+      ("#_ 1\n#_ #_ 2 3 ; done\n\n", "#_ 1\n#_ #_ 2 3 ; done\n"),
+      ("1,\n2 ,\n\n 3", "1,\n2 ,\n\n3"),
+      ("(f \"two\n  lines\" \n x)", "(f \"two\n  lines\"\n   x)"),
+      ("", ""),
+    ] {
+      let output = formatter.format(source).unwrap();
+      assert_eq!(output, formatted);
+      assert_eq!(formatter.format(&output).unwrap(), output);
+    }
+
+    let formatter = Formatter::default().indent("foo", &[Indent::Block(1)]).max_blank_lines(0);
+    assert_eq!(formatter.format("(foo 1\n\n\n2)").unwrap(), "(foo 1\n  2)");
+    assert_eq!(formatter.format("(foo\n1\n2)").unwrap(), "(foo\n 1\n  2)");
+
+    let err = formatter.format("[1 #_]").unwrap_err();
+    assert_eq!((err.code, err.ptr), (Code::UnmatchedDelimiter(']'), Some(5)));
+    assert_eq!(formatter.format("1 #_").unwrap_err().code, Code::UnexpectedEOF);
+  }
+
+  fn new(kind: NodeKind<'_>) -> Node<'_> {
+    Node::no_discards(kind, Span::default())
+  }
+
+  #[test]
+  fn zipper_moves_around() {
+    let mut zipper = parse::Zipper::new("#_ 0 [1 {:a #tag 2}] (3)").unwrap();
+    assert_eq!(zipper.node().span, Span(5, 20));
+    assert!(zipper.up().is_none() && zipper.left().is_none());
+
+    zipper.down().unwrap().right().unwrap().down().unwrap();
+    assert_eq!(zipper.node().kind, NodeKind::Key("a"));
+    zipper.right().unwrap().down().unwrap();
+    assert_eq!(zipper.node().kind, NodeKind::Int(2));
+    assert!(zipper.right().is_none() && zipper.down().is_none());
+    assert_eq!(zipper.node().kind, NodeKind::Int(2));
+
+    zipper.up().unwrap().up().unwrap().up().unwrap().right().unwrap();
+    assert_eq!(zipper.node().span, Span(21, 24));
+    zipper.left().unwrap();
+    assert_eq!(zipper.node().span, Span(5, 20));
+
+    // Depth first, on to the next top level form
+    assert_eq!(zipper.find_next_value(&Edn::Int(3)).unwrap().node().span, Span(22, 23));
+    assert!(zipper.find_next_value(&Edn::Int(0)).is_none());
+    // It's still at the 3, which isn't looked at again
+    assert!(zipper.find_next_value(&Edn::Int(3)).is_none());
+
+    let err = parse::Zipper::new(" #_ 1 ; nothing").unwrap_err();
+    assert_eq!(err.code, Code::UnexpectedEOF);
+  }
+
+  #[test]
+  fn zipper_edits_keep_the_source() {
+    use parse::Zipper;
+
+    let mut zipper =
+      Zipper::new("{:deps {a/a {:mvn/version \"1\"}} ; deps\n :paths [\"src\"]}\n").unwrap();
+    let paths =
+      NodeKind::Vector(vec![new(NodeKind::Str("src")), new(NodeKind::Str("test"))], vec![]);
+    zipper.assoc(new(NodeKind::Key("paths")), new(paths)).unwrap();
+    zipper.assoc(new(NodeKind::Key("aliases")), new(NodeKind::Map(vec![], vec![]))).unwrap();
+    assert_eq!(
+      zipper.to_source(),
+      "{:deps {a/a {:mvn/version \"1\"}} ; deps\n :paths [\"src\" \"test\"]\n :aliases {}}\n"
+    );
+    zipper.down().unwrap().remove().unwrap();
+    assert_eq!(zipper.to_source(), "{:paths [\"src\" \"test\"]\n :aliases {}}\n");
+    assert!(zipper.down().unwrap().assoc(new(NodeKind::Nil), new(NodeKind::Nil)).is_none());
+
+    let source = ";; top\n(ns foo)\n\n(defn f [x]\n  (g x)) ; g\n(g 1)";
+    let mut zipper = Zipper::new(source).unwrap();
+    while zipper.find_next_value(&Edn::Symbol("g")).is_some() {
+      zipper.replace(new(NodeKind::Symbol("h")));
+    }
+    assert_eq!(zipper.to_source(), ";; top\n(ns foo)\n\n(defn f [x]\n  (h x)) ; g\n(h 1)");
+
+    let mut zipper = Zipper::new("[a\n b] [1 2 3]").unwrap();
+    zipper.down().unwrap().right().unwrap().insert_right(new(NodeKind::Symbol("c"))).unwrap();
+    zipper.up().unwrap().right().unwrap().down().unwrap();
+    zipper.insert_right(new(NodeKind::Int(9))).unwrap().remove().unwrap();
+    assert_eq!(zipper.to_source(), "[a\n b\n c] [9 2 3]");
+    assert_eq!(zipper.node().span, Span(7, 14));
+    zipper.insert_right(new(NodeKind::Nil)).unwrap();
+    assert_eq!(zipper.to_source(), "[a\n b\n c] [9 2 3] nil");
   }
 }